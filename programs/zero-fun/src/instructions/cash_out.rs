@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{CashOutEvent, GameError, GameSession};
+
+#[derive(Accounts)]
+pub struct CashOutAccounts<'info> {
+    player: Signer<'info>,
+
+    #[account(mut)]
+    game_session: AccountLoader<'info, GameSession>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<CashOutAccounts>) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(game_session.is_active(), GameError::GameSessionNotActive);
+
+    // In hash chain mode the last round has to be revealed before cashing out.
+    require!(
+        !(game_session.is_hash_chain() && game_session.has_unrevealed_round()),
+        GameError::RoundNotRevealed
+    );
+
+    Ok(())
+}
+
+pub fn cash_out_handler(ctx: Context<CashOutAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut game_session = ctx.accounts.game_session.load_mut()?;
+
+    game_session.cash_out(now);
+
+    emit!(CashOutEvent {
+        game_session: ctx.accounts.game_session.key(),
+        round_count: game_session.next_player_move_position,
+        reveal_deadline: game_session.get_reveal_deadline()
+    });
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{CashOutCrashBetEvent, CrashBet, CrashRound, CrashState, GameError};
+
+#[derive(Accounts)]
+pub struct CashOutCrashBetAccounts<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"crash-state"],
+        bump = crash_state.bump
+    )]
+    pub crash_state: Account<'info, CrashState>,
+
+    pub crash_round: Account<'info, CrashRound>,
+
+    #[account(mut)]
+    pub crash_bet: Account<'info, CrashBet>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<CashOutCrashBetAccounts>) -> Result<()> {
+    let crash_round = &ctx.accounts.crash_round;
+    let crash_bet = &ctx.accounts.crash_bet;
+
+    require!(
+        crash_bet.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        crash_bet.is_for_round(&crash_round.key()),
+        GameError::InvalidCrashRound
+    );
+
+    // Once the round is revealed the crash point is known.
+    require!(crash_round.is_open(), GameError::CrashRoundNotOpen);
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        !crash_round.is_betting_open(now),
+        GameError::BettingNotClosed
+    );
+
+    Ok(())
+}
+
+/// Cashes the bet out at the current multiplier, if it is below the bet's target.
+pub fn cash_out_crash_bet_handler(ctx: Context<CashOutCrashBetAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let current_multiplier = ctx
+        .accounts
+        .crash_state
+        .get_multiplier(now - ctx.accounts.crash_round.betting_deadline);
+
+    let crash_bet = &mut ctx.accounts.crash_bet;
+
+    require!(
+        current_multiplier < crash_bet.cash_out_target,
+        GameError::InvalidCashOutTarget
+    );
+
+    crash_bet.cash_out_target = current_multiplier;
+
+    emit!(CashOutCrashBetEvent {
+        crash_round: ctx.accounts.crash_round.key(),
+        player: ctx.accounts.player.key(),
+        cash_out_target: current_multiplier
+    });
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{ClaimReferralRewardsEvent, GameError, ReferrerRewards};
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewardsAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"referrer-rewards", referrer.key().as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<ClaimReferralRewardsAccounts>) -> Result<()> {
+    require_gt!(
+        ctx.accounts.referrer_rewards.unclaimed,
+        0,
+        GameError::NoReferralRewards
+    );
+
+    Ok(())
+}
+
+pub fn claim_referral_rewards_handler(ctx: Context<ClaimReferralRewardsAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let amount = ctx.accounts.referrer_rewards.unclaimed;
+
+    // Only the accrued rewards are moved, the account's rent stays with it.
+    **ctx
+        .accounts
+        .referrer_rewards
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.referrer.try_borrow_mut_lamports()? += amount;
+
+    let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+
+    referrer_rewards.unclaimed = 0;
+    referrer_rewards.total_claimed = referrer_rewards.total_claimed.saturating_add(amount);
+
+    emit!(ClaimReferralRewardsEvent {
+        referrer: ctx.accounts.referrer.key(),
+        amount
+    });
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{CloseGlobalStateEvent, CrashState, GameError, GlobalState};
+
+#[derive(Accounts)]
+pub struct CloseGlobalStateAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"global-state"],
+        bump,
+        close = admin
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: The crash state, it is only read if the crash game was initialized.
+    #[account(
+        seeds = [b"crash-state"],
+        bump
+    )]
+    pub crash_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<CloseGlobalStateAccounts>) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+
+    require!(
+        global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    require!(
+        global_state.is_winding_down(),
+        GameError::GameNotWindingDown
+    );
+
+    // Every session and crash bet must have been settled or defaulted, as they are settled
+    // against the global state and the vault.
+    require_eq!(global_state.open_sessions, 0, GameError::SessionsStillOpen);
+
+    // An open round can still take bets once the game is active again, so it has to be
+    // revealed first.
+    if ctx.accounts.crash_state.owner.eq(&crate::ID) {
+        let crash_state =
+            CrashState::try_deserialize(&mut &ctx.accounts.crash_state.try_borrow_data()?[..])?;
+
+        require!(!crash_state.has_open_round, GameError::CrashRoundOpen);
+    }
+
+    Ok(())
+}
+
+pub fn close_global_state_handler(ctx: Context<CloseGlobalStateAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    // The vault is closed along with the global state, whatever it holds goes to the admin.
+    let amount = ctx.accounts.vault.lamports();
+
+    **ctx.accounts.vault.try_borrow_mut_lamports()? = 0;
+    **ctx.accounts.admin.try_borrow_mut_lamports()? += amount;
+
+    emit!(CloseGlobalStateEvent {
+        admin: ctx.accounts.admin.key(),
+        amount
+    });
+
+    Ok(())
+}
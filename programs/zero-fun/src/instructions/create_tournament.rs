@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::{CreateTournamentEvent, GameConfig, GameError, GlobalState, Tournament, HASH_LENGTH};
+
+/// Arguments for creating a tournament.
+/// - tournament_id: The id the tournament's address is derived from.
+/// - entry_fee: The amount each entrant pays into the prize pool.
+/// - start_time: The time entries open.
+/// - end_time: The time entries close and moves stop counting.
+/// - public_config_seed: The public config seed every entrant's session is played on.
+/// - prize_shares: The share of the prize pool paid to each place (in bps), from the first.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct CreateTournamentArgs {
+    pub tournament_id: u64,
+    pub entry_fee: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub public_config_seed: [u8; HASH_LENGTH],
+    pub prize_shares: Vec<u16>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: CreateTournamentArgs)]
+pub struct CreateTournamentAccounts<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Tournament::INIT_SPACE,
+        seeds = [b"tournament", args.tournament_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    /// The config of the variant the tournament is played with.
+    pub game_config: Account<'info, GameConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<CreateTournamentAccounts>, args: &CreateTournamentArgs) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    require!(
+        ctx.accounts.game_config.is_active(),
+        GameError::GameVariantNotActive
+    );
+
+    require!(
+        args.start_time < args.end_time && Tournament::are_prize_shares_valid(&args.prize_shares),
+        GameError::InvalidTournamentParameters
+    );
+
+    Ok(())
+}
+
+pub fn create_tournament_handler(
+    ctx: Context<CreateTournamentAccounts>,
+    args: CreateTournamentArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let variant = ctx.accounts.game_config.get_variant();
+
+    ctx.accounts.tournament.set_inner(Tournament::new(
+        args.tournament_id,
+        variant,
+        args.entry_fee,
+        args.start_time,
+        args.end_time,
+        args.public_config_seed,
+        args.prize_shares.clone(),
+        ctx.bumps.tournament,
+    ));
+
+    emit!(CreateTournamentEvent {
+        tournament: ctx.accounts.tournament.key(),
+        tournament_id: args.tournament_id,
+        variant,
+        entry_fee: args.entry_fee,
+        start_time: args.start_time,
+        end_time: args.end_time,
+        public_config_seed: args.public_config_seed,
+        prize_shares: args.prize_shares
+    });
+
+    Ok(())
+}
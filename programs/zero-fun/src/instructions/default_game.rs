@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::{DefaultGameEvent, GameError, GameSession};
+use crate::{DefaultGameEvent, GameError, GameSession, GlobalState, PlayerProfile};
 
 #[derive(Accounts)]
 pub struct DefaultGameAccounts<'info> {
-    player: Signer<'info>,
+    /// The player must sign unless the game is winding down, anyone can default their
+    /// sessions then.
+    #[account(mut)]
+    player: SystemAccount<'info>,
 
     #[account(mut)]
     user_vault: UncheckedAccount<'info>,
@@ -13,29 +16,67 @@ pub struct DefaultGameAccounts<'info> {
         mut,
         close = player
     )]
-    game_session: Account<'info, GameSession>,
+    game_session: AccountLoader<'info, GameSession>,
+
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"global-state"],
+        bump
+    )]
+    global_state: Account<'info, GlobalState>,
 }
 
 #[inline(always)]
 fn checks(ctx: &Context<DefaultGameAccounts>) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    // Tournament sessions are scored through `submit_tournament_score` instead.
+    require!(!game_session.is_tournament(), GameError::TournamentSession);
+
+    // Practice sessions are closed through `reveal_practice_game` instead.
+    require!(!game_session.is_practice(), GameError::PracticeSession);
+
+    // Daily challenge sessions are settled through `settle_daily_challenge` instead, the
+    // shared seed would otherwise be revealed before the leaderboard is settled.
     require!(
-        ctx.accounts
-            .game_session
-            .is_vault_for_game(ctx.accounts.user_vault.key),
+        !game_session.is_daily_challenge(),
+        GameError::DailyChallengeSession
+    );
+
+    require!(
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
         GameError::InvalidVault
     );
 
     require!(
-        ctx.accounts
-            .game_session
-            .is_owned_by_player(ctx.accounts.player.key),
+        game_session.is_owned_by_player(ctx.accounts.player.key),
         GameError::InvalidPlayer
     );
 
+    let global_state = &ctx.accounts.global_state;
+
+    require!(
+        ctx.accounts.player.is_signer || global_state.is_winding_down(),
+        ErrorCode::AccountNotSigner
+    );
+
     let now = Clock::get()?.unix_timestamp;
 
+    // While winding down active sessions can be defaulted at once, the rest are left for
+    // the admin to settle within the settlement window.
+    let can_wind_down = global_state.is_winding_down()
+        && (game_session.is_active() || global_state.is_wind_down_settlement_over(now));
+
     require!(
-        ctx.accounts.game_session.can_default(now),
+        can_wind_down || game_session.can_default(now),
         GameError::TooSoonToDefault
     );
 
@@ -45,11 +86,17 @@ fn checks(ctx: &Context<DefaultGameAccounts>) -> Result<()> {
 pub fn default_game_handler(ctx: Context<DefaultGameAccounts>) -> Result<()> {
     checks(&ctx)?;
 
+    let deposit = ctx.accounts.game_session.load()?.deposit;
+
     // Transfer the player's deposit back.
     **ctx.accounts.player.try_borrow_mut_lamports()? += ctx.accounts.user_vault.lamports();
 
     **ctx.accounts.user_vault.try_borrow_mut_lamports()? = 0;
 
+    ctx.accounts.player_profile.record_game_defaulted(deposit);
+
+    ctx.accounts.global_state.record_session_closed();
+
     emit!(DefaultGameEvent {
         game_session: ctx.accounts.game_session.key()
     });
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    DistributeTournamentPrizesEvent, GameError, GlobalState, Tournament, TournamentStatus,
+};
+
+/// The accounts of the players on the leaderboard are passed as the remaining accounts,
+/// in the order of the leaderboard.
+#[derive(Accounts)]
+pub struct DistributeTournamentPrizesAccounts<'info> {
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[inline(always)]
+fn checks<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, DistributeTournamentPrizesAccounts<'info>>,
+) -> Result<()> {
+    let tournament = &ctx.accounts.tournament;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(tournament.is_revealed(), GameError::InvalidTournamentStatus);
+
+    require!(
+        tournament.is_scoring_window_expired(now),
+        GameError::ScoringWindowNotExpired
+    );
+
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        tournament.leaderboard.len(),
+        GameError::InvalidPrizeRecipient
+    );
+
+    for (recipient, entry) in ctx.remaining_accounts.iter().zip(&tournament.leaderboard) {
+        require!(
+            recipient.key.eq(&entry.player) && recipient.is_writable,
+            GameError::InvalidPrizeRecipient
+        );
+    }
+
+    Ok(())
+}
+
+/// Pays each place on the leaderboard its share of the prize pool, the shares of places
+/// nobody took and the rest of the pool go to the global vault. Anyone can call it.
+pub fn distribute_tournament_prizes_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeTournamentPrizesAccounts<'info>>,
+) -> Result<()> {
+    checks(&ctx)?;
+
+    let tournament = &mut ctx.accounts.tournament;
+
+    let prizes: Vec<u64> = (0..tournament.leaderboard.len())
+        .map(|place| tournament.get_prize(place))
+        .collect();
+
+    let tournament_info = tournament.to_account_info();
+
+    for (recipient, prize) in ctx.remaining_accounts.iter().zip(&prizes) {
+        **tournament_info.try_borrow_mut_lamports()? -= prize;
+        **recipient.try_borrow_mut_lamports()? += prize;
+    }
+
+    // The shares never add up to more than the pool.
+    let house_share = tournament.prize_pool - prizes.iter().sum::<u64>();
+
+    **tournament_info.try_borrow_mut_lamports()? -= house_share;
+    **ctx.accounts.vault.try_borrow_mut_lamports()? += house_share;
+
+    tournament.prize_pool = 0;
+    tournament.status = TournamentStatus::Distributed;
+
+    emit!(DistributeTournamentPrizesEvent {
+        tournament: tournament.key(),
+        leaderboard: tournament.leaderboard.clone(),
+        prizes,
+        house_share
+    });
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    EnterTournamentEvent, GameConfig, GameError, GameSession, GlobalState, PlayerProfile,
+    Tournament,
+};
+
+#[derive(Accounts)]
+pub struct EnterTournamentAccounts<'info> {
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+
+    /// Every entrant's session is on the tournament's public config seed, so a player can
+    /// only enter once.
+    #[account(
+        init,
+        payer = player,
+        space = GameSession::INITIAL_SPACE,
+        seeds = [b"game-session".as_ref(), tournament.public_config_seed.as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub game_config: Account<'info, GameConfig>,
+
+    /// The player's lifetime statistics, created with their first session.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<EnterTournamentAccounts>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        ctx.accounts.global_state.is_active(),
+        GameError::GameNotActive
+    );
+
+    require!(
+        ctx.accounts.tournament.is_running(now),
+        GameError::TournamentNotOpen
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(ctx.accounts.tournament.variant.into()),
+        GameError::InvalidGameConfig
+    );
+
+    // A profile created by this instruction has no limits yet.
+    ctx.accounts
+        .player_profile
+        .check_limits(ctx.accounts.tournament.entry_fee, now)?;
+
+    Ok(())
+}
+
+pub fn enter_tournament_handler(ctx: Context<EnterTournamentAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let tournament = &mut ctx.accounts.tournament;
+
+    {
+        let game_session = &mut ctx.accounts.game_session.load_init()?;
+
+        // The entry fee is held by the tournament, the session has no deposit of its own.
+        **game_session = GameSession::new(
+            ctx.accounts.player.key(),
+            0,
+            tournament.key(),
+            tournament.public_config_seed,
+            &[],
+            now,
+        );
+
+        game_session.set_variant(tournament.variant);
+        game_session.enter_tournament(tournament.key());
+    }
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: tournament.to_account_info(),
+            },
+        ),
+        tournament.entry_fee,
+    )?;
+
+    tournament.prize_pool = tournament
+        .prize_pool
+        .checked_add(tournament.entry_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    tournament.entry_count = tournament
+        .entry_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    player_profile.initialize_if_needed(ctx.accounts.player.key(), ctx.bumps.player_profile);
+    player_profile.apply_pending_limits(now);
+    player_profile.record_game_started(tournament.entry_fee, now);
+
+    emit!(EnterTournamentEvent {
+        tournament: tournament.key(),
+        player: ctx.accounts.player.key(),
+        game_session: ctx.accounts.game_session.key()
+    });
+
+    Ok(())
+}
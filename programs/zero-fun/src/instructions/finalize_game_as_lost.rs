@@ -1,8 +1,8 @@
 use anchor_lang::{prelude::*, solana_program::hash::hashv};
 
 use crate::{
-    FinalizeGameAsLostEvent, GameError, GameSession, GlobalState, HASH_LENGTH, MAX_MOVE_TYPE_COUNT,
-    PUBLIC_SEED,
+    accrue_referral_reward, FinalizeGameAsLostEvent, GameConfig, GameError, GameRules, GameSession,
+    GlobalState, PlayerProfile, ReferrerRewards, HASH_LENGTH, PUBLIC_SEED,
 };
 
 /// Arguments for finalizing a game session as a loss.
@@ -12,7 +12,7 @@ use crate::{
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct FinalizeGameAsLostArgs {
     pub private_config_seed: [u8; HASH_LENGTH],
-    pub fail_position: u8,
+    pub fail_position: u16,
 }
 
 #[derive(Accounts)]
@@ -21,7 +21,7 @@ pub struct FinalizeGameAsLostAccounts<'info> {
         mut,
         close = player
     )]
-    pub game_session: Account<'info, GameSession>,
+    pub game_session: AccountLoader<'info, GameSession>,
 
     #[account(mut)]
     pub player: SystemAccount<'info>,
@@ -38,66 +38,101 @@ pub struct FinalizeGameAsLostAccounts<'info> {
     )]
     pub vault: UncheckedAccount<'info>,
 
+    #[account(mut)]
     pub global_state: Account<'info, GlobalState>,
+
+    pub game_config: Account<'info, GameConfig>,
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// The rewards account of the player's referrer, required if the player was referred.
+    #[account(
+        mut,
+        seeds = [b"referrer-rewards", player_profile.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
 }
 
 #[inline(always)]
 fn checks(ctx: &Context<FinalizeGameAsLostAccounts>, args: &FinalizeGameAsLostArgs) -> Result<()> {
+    let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+    let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+    // Tournament sessions are scored through `submit_tournament_score` instead.
+    require!(!game_session.is_tournament(), GameError::TournamentSession);
+
+    // Practice sessions are closed through `reveal_practice_game` instead.
+    require!(!game_session.is_practice(), GameError::PracticeSession);
+
+    // Daily challenge sessions are settled through `settle_daily_challenge` instead, the
+    // shared seed would otherwise be revealed before the leaderboard is settled.
     require!(
-        ctx.accounts.game_session.is_active(),
-        GameError::GameSessionNotActive
+        !game_session.is_daily_challenge(),
+        GameError::DailyChallengeSession
     );
 
+    // A cashed out session can still be proven lost until its reveal window expires.
+    if game_session.is_cashed_out() {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            !game_session.is_reveal_window_expired(now),
+            GameError::RevealWindowExpired
+        );
+    } else {
+        require!(game_session.is_active(), GameError::GameSessionNotActive);
+    }
+
+    // Hash chain sessions are settled round by round through `reveal_round`.
     require!(
-        ctx.accounts
-            .game_session
-            .is_owned_by_player(ctx.accounts.player.key),
+        !game_session.is_hash_chain(),
+        GameError::InvalidFairnessMode
+    );
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
         GameError::InvalidPlayer
     );
 
     require!(
-        ctx.accounts
-            .game_session
-            .is_vault_for_game(ctx.accounts.user_vault.key),
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
         GameError::InvalidVault
     );
 
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
     // Verify the public config was previously commited to.
     let public_config_seed: [u8; HASH_LENGTH] =
         hashv(&[PUBLIC_SEED.as_ref(), args.private_config_seed.as_ref()]).to_bytes();
 
     require!(
-        ctx.accounts
-            .game_session
-            .public_config_seed
-            .eq(&public_config_seed),
+        game_session.public_config_seed.eq(&public_config_seed),
         GameError::InvalidGameSeed
     );
 
-    // Get the number of moves for this round
-    let public_config_seed_for_move: [u8; HASH_LENGTH] = hashv(&[
-        &[args.fail_position],
-        ctx.accounts.game_session.public_config_seed.as_ref(),
-    ])
-    .to_bytes();
-
-    let move_type_count_for_round =
-        (public_config_seed_for_move[0] % (u8::try_from(MAX_MOVE_TYPE_COUNT).unwrap() - 1)) + 2;
-
-    // Get the move for failure
-    let private_config_seed_for_move: [u8; HASH_LENGTH] =
-        hashv(&[&[args.fail_position], args.private_config_seed.as_ref()]).to_bytes();
+    // Verify the player made a losing move at that position
+    let player_move = game_session
+        .get_player_move(player_moves, usize::from(args.fail_position))
+        .ok_or(GameError::InvalidFailPosition)?;
 
-    let fail_move = private_config_seed_for_move[0] % move_type_count_for_round;
-
-    // Verify the player made that move
     require!(
-        ctx.accounts
-            .game_session
-            .next_player_move_position
-            .gt(&args.fail_position)
-            && ctx.accounts.game_session.player_moves[usize::from(args.fail_position)]
-                .eq(&fail_move),
+        ctx.accounts.game_config.parameters.is_losing_move(
+            game_session,
+            &args.private_config_seed,
+            args.fail_position,
+            player_move
+        ),
         GameError::InvalidFailPosition
     );
     Ok(())
@@ -109,10 +144,30 @@ pub fn finalize_game_as_lost_handler(
 ) -> Result<()> {
     checks(&ctx, &args)?;
 
+    let deposit = ctx.accounts.game_session.load()?.deposit;
+
+    let user_vault_balance = ctx.accounts.user_vault.lamports();
+
     // Transfer funds to the main vault
-    **ctx.accounts.vault.try_borrow_mut_lamports()? += ctx.accounts.user_vault.lamports();
+    **ctx.accounts.vault.try_borrow_mut_lamports()? += user_vault_balance;
     **ctx.accounts.user_vault.try_borrow_mut_lamports()? = 0;
 
+    let global_state = &mut ctx.accounts.global_state;
+
+    global_state.record_swept(user_vault_balance);
+    global_state.record_session_closed();
+
+    ctx.accounts.player_profile.record_game_lost();
+
+    accrue_referral_reward(
+        &ctx.accounts.player_profile,
+        ctx.accounts.referrer_rewards.as_mut(),
+        &ctx.accounts.vault.to_account_info(),
+        &mut ctx.accounts.global_state,
+        ctx.accounts.game_session.key(),
+        deposit,
+    )?;
+
     emit!(FinalizeGameAsLostEvent {
         game_session: ctx.accounts.game_session.key(),
         private_config_seed: args.private_config_seed
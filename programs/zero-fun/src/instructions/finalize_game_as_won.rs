@@ -1,21 +1,25 @@
 use anchor_lang::{
-    prelude::*, solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ADDRESS,
+    prelude::*,
+    solana_program::{hash::hashv, sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ADDRESS},
 };
 
 use crate::{
-    is_signature_valid, FinalizeGameAsWonEvent, GameError, GameSession, GlobalState,
-    FINALIZE_WIN_ACTION, HASH_LENGTH, MAX_BPS,
+    accrue_referral_reward, is_signature_valid, pay_out_jackpot, FinalizeGameAsWonEvent, GameError,
+    GameSession, GlobalState, Jackpot, PayoutCircuitBreakerEvent, PlayerProfile, ReferrerRewards,
+    FINALIZE_WIN_ACTION, HASH_LENGTH, PUBLIC_SEED,
 };
 
 /// Arguments for finalizing a game session as a win.
 /// - payout: The amount of lamports to be paid out to the player upon winning the game.
-/// - private_config_seed: The SHA-256 hash seed used to derive the private configuration
-///   of the game.
 /// - deadline: A timestamp indicating the deadline for the signature provided.
+/// - private_config_seed: The SHA-256 hash seed used to derive the private configuration
+///   of the game, it is part of the signed commitment so a win can't be settled without
+///   revealing it, the session is then checked against the jackpot with it.
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct FinalizeGameAsWonArgs {
     pub payout: u64,
     pub deadline: i64,
+    pub private_config_seed: [u8; HASH_LENGTH],
 }
 
 #[derive(Accounts)]
@@ -25,7 +29,7 @@ pub struct FinalizeGameAsWonAccounts<'info> {
         mut,
         close = player
     )]
-    pub game_session: Account<'info, GameSession>,
+    pub game_session: AccountLoader<'info, GameSession>,
 
     #[account(mut)]
     pub player: Signer<'info>,
@@ -42,6 +46,7 @@ pub struct FinalizeGameAsWonAccounts<'info> {
     )]
     pub vault: UncheckedAccount<'info>,
 
+    #[account(mut)]
     pub global_state: Account<'info, GlobalState>,
 
     pub system_program: Program<'info, System>,
@@ -51,13 +56,53 @@ pub struct FinalizeGameAsWonAccounts<'info> {
         address = INSTRUCTIONS_SYSVAR_ADDRESS
     )]
     pub instructions_sysvar: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// The rewards account of the player's referrer, required if the player was referred.
+    #[account(
+        mut,
+        seeds = [b"referrer-rewards", player_profile.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
 }
 
 #[inline(always)]
 fn checks(ctx: &Context<FinalizeGameAsWonAccounts>, args: &FinalizeGameAsWonArgs) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    // Tournament sessions are scored through `submit_tournament_score` instead.
+    require!(!game_session.is_tournament(), GameError::TournamentSession);
+
+    // Practice sessions are closed through `reveal_practice_game` instead.
+    require!(!game_session.is_practice(), GameError::PracticeSession);
+
+    // Daily challenge sessions are settled through `settle_daily_challenge` instead.
     require!(
-        ctx.accounts.game_session.is_active(),
-        GameError::GameSessionNotActive
+        !game_session.is_daily_challenge(),
+        GameError::DailyChallengeSession
+    );
+
+    require!(game_session.is_active(), GameError::GameSessionNotActive);
+
+    // Signed wins are held back once the circuit breaker trips, in case the message
+    // signer was compromised.
+    require!(
+        !ctx.accounts.global_state.is_restricted(),
+        GameError::SettlementRestricted
     );
 
     let now = Clock::get()?.unix_timestamp;
@@ -65,27 +110,29 @@ fn checks(ctx: &Context<FinalizeGameAsWonAccounts>, args: &FinalizeGameAsWonArgs
     require_gt!(args.deadline, now, GameError::DeadlinePassed);
 
     require!(
-        ctx.accounts
-            .game_session
-            .is_owned_by_player(ctx.accounts.player.key),
+        game_session.is_owned_by_player(ctx.accounts.player.key),
         GameError::InvalidPlayer
     );
 
     require!(
-        ctx.accounts
-            .game_session
-            .is_vault_for_game(ctx.accounts.user_vault.key),
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
         GameError::InvalidVault
     );
 
+    // Verify the public config was previously commited to.
+    let public_config_seed: [u8; HASH_LENGTH] =
+        hashv(&[PUBLIC_SEED.as_ref(), args.private_config_seed.as_ref()]).to_bytes();
+
+    require!(
+        game_session.public_config_seed.eq(&public_config_seed),
+        GameError::InvalidGameSeed
+    );
+
     // Verify the payout does not exceed the maximum allowed payout.
     let current_max_payout = ctx
         .accounts
-        .vault
-        .lamports()
-        .checked_mul(u64::from(ctx.accounts.global_state.max_payout))
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        / MAX_BPS;
+        .global_state
+        .get_max_payout(ctx.accounts.vault.lamports())?;
 
     require_gt!(
         current_max_payout,
@@ -110,7 +157,10 @@ fn checks(ctx: &Context<FinalizeGameAsWonAccounts>, args: &FinalizeGameAsWonArgs
             // they are all implictly included in the commitment.
             // It is also tied to the session as the session's key is derived from it, so it
             // cannot be reused for sessions.
-            ctx.accounts.game_session.public_config_seed.as_ref(),
+            game_session.public_config_seed.as_ref(),
+            // The private config seed is signed over as well so the house has to reveal it,
+            // the jackpot is checked with it.
+            args.private_config_seed.as_ref(),
         ];
 
         // Verify the ED25519 signature is valid.
@@ -128,6 +178,19 @@ pub fn finalize_game_as_won_handler(
 ) -> Result<()> {
     checks(&ctx, &args)?;
 
+    // The jackpot is hit by surviving its move threshold, or by a rare event derived from
+    // the private config seed.
+    let (deposit, is_jackpot_hit) = {
+        let game_session = ctx.accounts.game_session.load()?;
+
+        let jackpot = &ctx.accounts.jackpot;
+
+        let is_jackpot_hit = jackpot.is_hit_by_moves(game_session.get_player_move_count())
+            || jackpot.is_hit(&args.private_config_seed, &game_session.client_seed);
+
+        (game_session.deposit, is_jackpot_hit)
+    };
+
     // Transfer the winnings to the player
     **ctx.accounts.player.try_borrow_mut_lamports()? +=
         ctx.accounts.user_vault.lamports() + args.payout;
@@ -139,6 +202,47 @@ pub fn finalize_game_as_won_handler(
     // Deduct the payout from the global vault
     **ctx.accounts.vault.try_borrow_mut_lamports()? -= args.payout;
 
+    let global_state = &mut ctx.accounts.global_state;
+
+    global_state.record_paid_out(args.payout);
+    global_state.record_session_closed();
+
+    // The jackpot pays out of its own pool, apart from the global vault.
+    let jackpot_payout = if is_jackpot_hit {
+        pay_out_jackpot(
+            &mut ctx.accounts.jackpot,
+            &ctx.accounts.player.to_account_info(),
+            ctx.accounts.game_session.key(),
+        )?
+    } else {
+        0
+    };
+
+    ctx.accounts
+        .player_profile
+        .record_game_won(deposit + args.payout + jackpot_payout);
+
+    accrue_referral_reward(
+        &ctx.accounts.player_profile,
+        ctx.accounts.referrer_rewards.as_mut(),
+        &ctx.accounts.vault.to_account_info(),
+        &mut ctx.accounts.global_state,
+        ctx.accounts.game_session.key(),
+        deposit,
+    )?;
+
+    let global_state = &mut ctx.accounts.global_state;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if global_state.record_payout(args.payout, now) {
+        emit!(PayoutCircuitBreakerEvent {
+            game_session: ctx.accounts.game_session.key(),
+            payouts_in_window: global_state.get_payouts_in_window(now),
+            threshold: global_state.payout_breaker_threshold
+        });
+    }
+
     emit!(FinalizeGameAsWonEvent {
         payout: args.payout,
         game_session: ctx.accounts.game_session.key()
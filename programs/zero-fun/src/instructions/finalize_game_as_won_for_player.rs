@@ -2,7 +2,10 @@ use anchor_lang::{
     prelude::*, solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ADDRESS,
 };
 
-use crate::{FinalizeGameAsWonForPlayerEvent, GameError, GameSession, GlobalState};
+use crate::{
+    accrue_referral_reward, FinalizeGameAsWonForPlayerEvent, GameError, GameSession, GlobalState,
+    PlayerProfile, ReferrerRewards,
+};
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct FinalizeGameAsWonForPlayerArgs {
@@ -17,7 +20,7 @@ pub struct FinalizeGameAsWonForPlayerAccounts<'info> {
         // get the rent for the vault & game session
         close = vault
     )]
-    pub game_session: Account<'info, GameSession>,
+    pub game_session: AccountLoader<'info, GameSession>,
 
     #[account(mut)]
     pub player: SystemAccount<'info>,
@@ -34,30 +37,56 @@ pub struct FinalizeGameAsWonForPlayerAccounts<'info> {
     )]
     pub vault: UncheckedAccount<'info>,
 
+    #[account(mut)]
     pub global_state: Account<'info, GlobalState>,
 
     pub admin: Signer<'info>,
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// The rewards account of the player's referrer, required if the player was referred.
+    #[account(
+        mut,
+        seeds = [b"referrer-rewards", player_profile.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
 }
 
 #[inline(always)]
-fn checks(ctx: &Context<FinalizeGameAsWonForPlayerAccounts>) -> Result<()> {
-    // Verify that the game session has been marked as won by the player
+fn checks(
+    ctx: &Context<FinalizeGameAsWonForPlayerAccounts>,
+    args: &FinalizeGameAsWonForPlayerArgs,
+) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    // Tournament sessions are scored through `submit_tournament_score` instead.
+    require!(!game_session.is_tournament(), GameError::TournamentSession);
+
+    // Practice sessions are closed through `reveal_practice_game` instead.
+    require!(!game_session.is_practice(), GameError::PracticeSession);
+
+    // Daily challenge sessions are settled through `settle_daily_challenge` instead.
     require!(
-        ctx.accounts.game_session.is_won(),
-        GameError::GameSessionNotWon
+        !game_session.is_daily_challenge(),
+        GameError::DailyChallengeSession
     );
 
+    // Verify that the game session has been marked as won by the player
+    require!(game_session.is_won(), GameError::GameSessionNotWon);
+
     require!(
-        ctx.accounts
-            .game_session
-            .is_owned_by_player(ctx.accounts.player.key),
+        game_session.is_owned_by_player(ctx.accounts.player.key),
         GameError::InvalidPlayer
     );
 
     require!(
-        ctx.accounts
-            .game_session
-            .is_vault_for_game(ctx.accounts.user_vault.key),
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
         GameError::InvalidVault
     );
 
@@ -66,6 +95,18 @@ fn checks(ctx: &Context<FinalizeGameAsWonForPlayerAccounts>) -> Result<()> {
         GameError::InvalidAdmin
     );
 
+    // Verify the payout does not exceed the maximum allowed payout.
+    let current_max_payout = ctx
+        .accounts
+        .global_state
+        .get_max_payout(ctx.accounts.vault.lamports())?;
+
+    require_gt!(
+        current_max_payout,
+        args.payout,
+        GameError::PayoutExceedsMaximum
+    );
+
     Ok(())
 }
 
@@ -73,13 +114,14 @@ pub fn finalize_game_as_won_for_player_handler(
     ctx: Context<FinalizeGameAsWonForPlayerAccounts>,
     args: FinalizeGameAsWonForPlayerArgs,
 ) -> Result<()> {
-    checks(&ctx)?;
+    checks(&ctx, &args)?;
+
+    let deposit = ctx.accounts.game_session.load()?.deposit;
 
     // Transfer the winnings to the player
-    **ctx.accounts.player.try_borrow_mut_lamports()? +=
-        ctx.accounts.game_session.deposit + args.payout;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += deposit + args.payout;
 
-    let rent_exempt_fee = ctx.accounts.user_vault.lamports() - ctx.accounts.game_session.deposit;
+    let rent_exempt_fee = ctx.accounts.user_vault.lamports() - deposit;
 
     // The vault has had it's lamports(both the deposit and rent) transferred
     // back to the user and global vault
@@ -89,6 +131,24 @@ pub fn finalize_game_as_won_for_player_handler(
     // the rent for the vault & game session, that is deducted from the payout
     **ctx.accounts.vault.try_borrow_mut_lamports()? -= args.payout - rent_exempt_fee;
 
+    let global_state = &mut ctx.accounts.global_state;
+
+    global_state.record_paid_out(args.payout);
+    global_state.record_session_closed();
+
+    ctx.accounts
+        .player_profile
+        .record_game_won(deposit + args.payout);
+
+    accrue_referral_reward(
+        &ctx.accounts.player_profile,
+        ctx.accounts.referrer_rewards.as_mut(),
+        &ctx.accounts.vault.to_account_info(),
+        &mut ctx.accounts.global_state,
+        ctx.accounts.game_session.key(),
+        deposit,
+    )?;
+
     emit!(FinalizeGameAsWonForPlayerEvent {
         admin: ctx.accounts.admin.key(),
         payout: args.payout,
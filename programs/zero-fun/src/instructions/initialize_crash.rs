@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{CrashState, GameError, GlobalState, HASH_LENGTH};
+
+/// Arguments for initializing the crash game.
+/// - chain_head: The head of the reverse hash chain the house reveals a link of for
+///   each round.
+/// - betting_period: How long players can join a round after it is started (in seconds).
+/// - multiplier_growth: How fast the multiplier grows once betting closes (in bps per
+///   second).
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct InitializeCrashArgs {
+    pub chain_head: [u8; HASH_LENGTH],
+    pub betting_period: i64,
+    pub multiplier_growth: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCrashAccounts<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CrashState::INIT_SPACE,
+        seeds = [b"crash-state"],
+        bump
+    )]
+    pub crash_state: Account<'info, CrashState>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<InitializeCrashAccounts>, args: &InitializeCrashArgs) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    require!(
+        args.betting_period > 0 && args.multiplier_growth > 0,
+        GameError::InvalidCrashParameters
+    );
+
+    Ok(())
+}
+
+pub fn initialize_crash_handler(
+    ctx: Context<InitializeCrashAccounts>,
+    args: InitializeCrashArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    ctx.accounts.crash_state.set_inner(CrashState::new(
+        args.chain_head,
+        args.betting_period,
+        args.multiplier_growth,
+        ctx.bumps.crash_state,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{FeeVault, GameError, GlobalState};
+
+#[derive(Accounts)]
+pub struct InitializeFeeVaultAccounts<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [b"fee-vault"],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<InitializeFeeVaultAccounts>) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    Ok(())
+}
+
+pub fn initialize_fee_vault_handler(ctx: Context<InitializeFeeVaultAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    ctx.accounts
+        .fee_vault
+        .set_inner(FeeVault::new(ctx.bumps.fee_vault));
+
+    Ok(())
+}
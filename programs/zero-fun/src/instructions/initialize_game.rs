@@ -4,8 +4,9 @@ use anchor_lang::{
 };
 
 use crate::{
-    GameError, GameSession, GlobalState, InitializeGameEvent, HASH_LENGTH, MAX_BPS,
-    MAX_METADATA_LENGTH,
+    Difficulty, FeeVault, GameConfig, GameError, GameRules, GameSession, GlobalState,
+    InitializeGameEvent, Jackpot, JackpotContributionEvent, PlayerProfile, ProtocolFeeEvent,
+    HASH_LENGTH, MAX_METADATA_LENGTH,
 };
 
 /// Arguments for initializing a new game session.
@@ -13,11 +14,27 @@ use crate::{
 ///   the game.
 /// - game_metadata: Arbitrary metadata about the game, such as the algorithm version,
 ///   configuration parameters, etc. Limited to 64 bytes.
+/// - hash_chain_head: If provided the session reveals a link of a reverse hash chain
+///   per round instead of the private config seed at the end, this is the head of
+///   that chain.
+/// - client_seed: If provided it is mixed into the outcome by the variants that use it,
+///   the player picks it after the house committed to its seed.
+/// - difficulty: How risky each round is, it changes the number of tiles and fail tiles
+///   per round.
+/// - auto_play_commitment: If provided the session is played by revealing all its moves
+///   at once through `reveal_auto_play`, this is the commitment to them.
+/// - practice: If set the session is played for free, with no deposit and no vault, and
+///   closed through `reveal_practice_game`.
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Default)]
 pub struct InitializeGameArgs {
     pub public_config_seed: [u8; HASH_LENGTH],
     pub game_metadata: String,
     pub deposit: u64,
+    pub hash_chain_head: Option<[u8; HASH_LENGTH]>,
+    pub client_seed: Option<[u8; HASH_LENGTH]>,
+    pub difficulty: Difficulty,
+    pub auto_play_commitment: Option<[u8; HASH_LENGTH]>,
+    pub practice: bool,
 }
 
 #[derive(Accounts)]
@@ -26,18 +43,19 @@ pub struct InitializeGameAccounts<'info> {
     #[account(
         init,
         payer = player,
-        space = 8 + GameSession::INIT_SPACE,
+        space = GameSession::INITIAL_SPACE,
         // The seeds are expected to be unique for each game session because it is a commitment to
         // the both the public configuration which was derived from a random seed.
         seeds = [b"game-session".as_ref(), args.public_config_seed.as_ref(), {player.key().as_ref()}],
         bump
     )]
-    pub game_session: Account<'info, GameSession>,
+    pub game_session: AccountLoader<'info, GameSession>,
 
     #[account(mut)]
     pub player: Signer<'info>,
 
-    /// CHECK: This is the vault account where the player's deposit will be stored.
+    /// CHECK: This is the vault account where the player's deposit will be stored, practice
+    /// sessions have none.
     #[account(
         init,
         space = 0,
@@ -45,7 +63,7 @@ pub struct InitializeGameAccounts<'info> {
         seeds = [b"vault", args.public_config_seed.as_ref(), player.key.as_ref()],
         bump
     )]
-    pub user_vault: UncheckedAccount<'info>,
+    pub user_vault: Option<UncheckedAccount<'info>>,
 
     /// CHECK: This is the global vault account.
     #[account(
@@ -54,9 +72,38 @@ pub struct InitializeGameAccounts<'info> {
     )]
     pub vault: UncheckedAccount<'info>,
 
+    #[account(mut)]
     pub global_state: Account<'info, GlobalState>,
 
     pub system_program: Program<'info, System>,
+
+    /// The config of the variant the session is played with.
+    pub game_config: Account<'info, GameConfig>,
+
+    /// The jackpot that takes its share of the deposit.
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    /// The player's lifetime statistics, created with their first session.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// The fee vault that takes the protocol fee out of the deposit.
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
 }
 
 #[inline(always)]
@@ -68,20 +115,27 @@ fn checks(ctx: &Context<InitializeGameAccounts>, args: &InitializeGameArgs) -> R
         GameError::MetadataTooLong
     );
 
-    // Verify the deposit is within the allowed maximum deposit.
-    let current_max_deposit = ctx
-        .accounts
-        .vault
-        .lamports()
-        .checked_mul(u64::from(ctx.accounts.global_state.max_deposit))
-        .ok_or(ProgramError::ArithmeticOverflow)?
-        / MAX_BPS;
+    // Practice sessions have nothing at stake, so they have no vault and the deposit limit
+    // holds trivially. They are closed through `reveal_practice_game`, so they can't be
+    // settled round by round as hash chain sessions are.
+    if args.practice {
+        require!(
+            args.deposit == 0 && ctx.accounts.user_vault.is_none(),
+            GameError::InvalidPracticeGame
+        );
 
-    require_gte!(
-        current_max_deposit,
-        args.deposit,
-        GameError::DepositExceedsMaximum
-    );
+        require!(
+            args.hash_chain_head.is_none(),
+            GameError::InvalidPracticeGame
+        );
+    } else {
+        require!(ctx.accounts.user_vault.is_some(), GameError::InvalidVault);
+
+        // Verify the deposit is within the allowed deposit limits.
+        ctx.accounts
+            .global_state
+            .check_deposit(args.deposit, ctx.accounts.vault.lamports())?;
+    }
 
     // Verfiy that the game is still active
     require!(
@@ -89,6 +143,46 @@ fn checks(ctx: &Context<InitializeGameAccounts>, args: &InitializeGameArgs) -> R
         GameError::GameNotActive
     );
 
+    require!(
+        ctx.accounts.game_config.is_active(),
+        GameError::GameVariantNotActive
+    );
+
+    require!(
+        args.hash_chain_head.is_none() || ctx.accounts.game_config.parameters.supports_hash_chain(),
+        GameError::InvalidFairnessMode
+    );
+
+    // Auto-play records every move at once, while hash chain sessions reveal each round
+    // before the next one is played.
+    require!(
+        args.hash_chain_head.is_none() || args.auto_play_commitment.is_none(),
+        GameError::InvalidFairnessMode
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .parameters
+            .supports_difficulty(args.difficulty),
+        GameError::InvalidDifficulty
+    );
+
+    // A profile created by this instruction has no limits yet, and practice sessions are
+    // only held back by the player's exclusion as they have nothing at stake.
+    let now = Clock::get()?.unix_timestamp;
+
+    if args.practice {
+        require!(
+            !ctx.accounts.player_profile.is_excluded(now),
+            GameError::PlayerSelfExcluded
+        );
+    } else {
+        ctx.accounts
+            .player_profile
+            .check_limits(args.deposit, now)?;
+    }
+
     Ok(())
 }
 
@@ -98,33 +192,144 @@ pub fn initialize_game_handler(
 ) -> Result<()> {
     checks(&ctx, &args)?;
 
-    let game_session = &mut ctx.accounts.game_session;
-
     let now = Clock::get()?.unix_timestamp;
 
-    game_session.set_inner(GameSession::new(
-        ctx.accounts.player.key(),
-        args.deposit,
-        *ctx.accounts.vault.key,
-        args.public_config_seed,
-        args.game_metadata,
-        now,
-    ));
-
-    transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.player.to_account_info(),
-                to: ctx.accounts.user_vault.to_account_info(),
-            },
-        ),
-        args.deposit,
-    )?;
+    // The jackpot's share and the protocol fee are taken out of the deposit before the
+    // session is played.
+    let jackpot_contribution = ctx.accounts.jackpot.get_contribution(args.deposit);
+
+    let protocol_fee = ctx.accounts.global_state.get_protocol_fee(args.deposit);
+
+    let deposit = args.deposit - jackpot_contribution - protocol_fee;
+
+    let vault = ctx
+        .accounts
+        .user_vault
+        .as_ref()
+        .map_or(Pubkey::default(), |user_vault| user_vault.key());
+
+    {
+        let game_session = &mut ctx.accounts.game_session.load_init()?;
+
+        **game_session = GameSession::new(
+            ctx.accounts.player.key(),
+            deposit,
+            vault,
+            args.public_config_seed,
+            args.game_metadata.as_bytes(),
+            now,
+        );
+
+        game_session.set_variant(ctx.accounts.game_config.get_variant());
+        game_session.set_default_windows(
+            ctx.accounts.global_state.default_offset,
+            ctx.accounts.global_state.max_session_lifetime,
+        );
+        game_session.set_difficulty(args.difficulty);
+
+        if let Some(hash_chain_head) = args.hash_chain_head {
+            game_session.enable_hash_chain(hash_chain_head);
+        }
+
+        if let Some(auto_play_commitment) = args.auto_play_commitment {
+            game_session.enable_auto_play(auto_play_commitment);
+        }
+
+        if let Some(client_seed) = args.client_seed {
+            game_session.client_seed = client_seed;
+        }
+
+        if args.practice {
+            game_session.enable_practice();
+        }
+    }
+
+    if let Some(user_vault) = &ctx.accounts.user_vault {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: user_vault.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+
+    if jackpot_contribution > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.jackpot.to_account_info(),
+                },
+            ),
+            jackpot_contribution,
+        )?;
+
+        let jackpot = &mut ctx.accounts.jackpot;
+
+        jackpot.balance += jackpot_contribution;
+        jackpot.total_contributed += jackpot_contribution;
+
+        emit!(JackpotContributionEvent {
+            game_session: ctx.accounts.game_session.key(),
+            amount: jackpot_contribution,
+            balance: jackpot.balance
+        });
+    }
+
+    if protocol_fee > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                },
+            ),
+            protocol_fee,
+        )?;
+
+        let fee_vault = &mut ctx.accounts.fee_vault;
+
+        fee_vault.balance += protocol_fee;
+        fee_vault.total_collected += protocol_fee;
+
+        emit!(ProtocolFeeEvent {
+            game_session: ctx.accounts.game_session.key(),
+            amount: protocol_fee
+        });
+    }
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    player_profile.initialize_if_needed(ctx.accounts.player.key(), ctx.bumps.player_profile);
+
+    player_profile.apply_pending_limits(now);
+
+    // Practice sessions have nothing at stake, so they don't count towards the stats.
+    if !args.practice {
+        player_profile.record_game_started(args.deposit, now);
+
+        ctx.accounts.global_state.record_session_opened(deposit);
+    }
 
     emit!(InitializeGameEvent {
         game_session: ctx.accounts.game_session.key(),
-        game_session_account: (*ctx.accounts.game_session).clone()
+        player: ctx.accounts.player.key(),
+        deposit,
+        vault,
+        public_config_seed: args.public_config_seed,
+        game_metadata: args.game_metadata,
+        hash_chain_head: args.hash_chain_head,
+        client_seed: args.client_seed,
+        variant: ctx.accounts.game_config.get_variant(),
+        difficulty: args.difficulty,
+        auto_play_commitment: args.auto_play_commitment,
+        practice: args.practice
     });
 
     Ok(())
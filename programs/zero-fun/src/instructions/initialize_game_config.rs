@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameConfig, GameError, GameParameters, GameRules, GameState, GlobalState};
+
+/// Arguments for registering a game variant.
+/// - parameters: The parameters of the variant, they decide which variant the config
+///   is for and can't be changed later.
+/// - initial_state: The initial state of the variant, it can be changed later.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct InitializeGameConfigArgs {
+    pub parameters: GameParameters,
+    pub initial_state: GameState,
+}
+
+#[derive(Accounts)]
+#[instruction(args: InitializeGameConfigArgs)]
+pub struct InitializeGameConfigAccounts<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"game-config", [u8::from(args.parameters.get_variant())].as_ref()],
+        bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(
+    ctx: &Context<InitializeGameConfigAccounts>,
+    args: &InitializeGameConfigArgs,
+) -> Result<()> {
+    // Only the admin can register a variant.
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    args.parameters.validate_parameters()?;
+
+    Ok(())
+}
+
+pub fn initialize_game_config_handler(
+    ctx: Context<InitializeGameConfigAccounts>,
+    args: InitializeGameConfigArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    ctx.accounts.game_config.set_inner(GameConfig::new(
+        args.initial_state,
+        args.parameters,
+        ctx.bumps.game_config,
+    ));
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::{GameState, GlobalState};
+use crate::{GameState, GlobalState, MAX_BPS};
 use anchor_lang::prelude::*;
 
 const INITIALIZER_KEY: Pubkey = pubkey!("4w5ezXcjV8RdJLPAQmwVonevgUVfuAZSDMdWtURc1CRY");
@@ -8,11 +8,13 @@ const INITIALIZER_KEY: Pubkey = pubkey!("4w5ezXcjV8RdJLPAQmwVonevgUVfuAZSDMdWtUR
 /// - max_payout: Maximum payout allowed (in bps).
 /// - initial_state: The initial state the game is in,
 /// it can be changed later.
+/// - house_edge: The house edge applied to payouts computed on-chain (in bps).
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct InitializeGlobalStateArgs {
-    pub max_deposit: u8,
-    pub max_payout: u8,
+    pub max_deposit: u16,
+    pub max_payout: u16,
     pub initial_state: GameState,
+    pub house_edge: u16,
 }
 
 #[derive(Accounts)]
@@ -49,7 +51,10 @@ pub struct InitializeGlobalStateAccounts<'info> {
 }
 
 #[inline(always)]
-fn checks(ctx: &Context<InitializeGlobalStateAccounts>) -> Result<()> {
+fn checks(
+    ctx: &Context<InitializeGlobalStateAccounts>,
+    args: &InitializeGlobalStateArgs,
+) -> Result<()> {
     // Ensure the initializer is the bootstrap key
     require_keys_eq!(
         ctx.accounts.initializer.key(),
@@ -57,6 +62,24 @@ fn checks(ctx: &Context<InitializeGlobalStateAccounts>) -> Result<()> {
         crate::GameError::InvalidBootstrapKey
     );
 
+    require_gte!(
+        MAX_BPS,
+        u64::from(args.max_deposit),
+        crate::GameError::InvalidLimits
+    );
+
+    require_gte!(
+        MAX_BPS,
+        u64::from(args.max_payout),
+        crate::GameError::InvalidLimits
+    );
+
+    require_gte!(
+        MAX_BPS,
+        u64::from(args.house_edge),
+        crate::GameError::InvalidHouseEdge
+    );
+
     Ok(())
 }
 
@@ -64,7 +87,7 @@ pub fn initialize_global_state_handler(
     ctx: Context<InitializeGlobalStateAccounts>,
     args: InitializeGlobalStateArgs,
 ) -> Result<()> {
-    checks(&ctx)?;
+    checks(&ctx, &args)?;
 
     let global_state = &mut ctx.accounts.global_state;
 
@@ -75,6 +98,7 @@ pub fn initialize_global_state_handler(
         args.max_payout,
         args.initial_state,
         ctx.bumps.vault,
+        args.house_edge,
     ));
 
     Ok(())
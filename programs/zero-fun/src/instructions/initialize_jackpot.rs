@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, GlobalState, Jackpot};
+
+/// Arguments for initializing the jackpot.
+/// - contribution: The share of each deposit added to the pool (in bps).
+/// - odds: A won session hits the jackpot with odds of `1 / odds`.
+/// - move_threshold: A won session that survived this many moves always hits the jackpot,
+///   0 is no threshold.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct InitializeJackpotArgs {
+    pub contribution: u16,
+    pub odds: u32,
+    pub move_threshold: u16,
+}
+
+#[derive(Accounts)]
+pub struct InitializeJackpotAccounts<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Jackpot::INIT_SPACE,
+        seeds = [b"jackpot"],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<InitializeJackpotAccounts>, args: &InitializeJackpotArgs) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    require!(
+        Jackpot::are_parameters_valid(args.contribution, args.odds),
+        GameError::InvalidJackpotParameters
+    );
+
+    Ok(())
+}
+
+pub fn initialize_jackpot_handler(
+    ctx: Context<InitializeJackpotAccounts>,
+    args: InitializeJackpotArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    ctx.accounts.jackpot.set_inner(Jackpot::new(
+        args.contribution,
+        args.odds,
+        args.move_threshold,
+        ctx.bumps.jackpot,
+    ));
+
+    Ok(())
+}
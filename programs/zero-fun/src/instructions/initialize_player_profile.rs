@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::PlayerProfile;
+
+#[derive(Accounts)]
+pub struct InitializePlayerProfileAccounts<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// CHECK: This is the player the profile is for, it doesn't need to sign.
+    pub player: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Profiles are created with the player's first session, this creates one for a player
+/// whose sessions were started before profiles existed so they can still be settled.
+pub fn initialize_player_profile_handler(
+    ctx: Context<InitializePlayerProfileAccounts>,
+) -> Result<()> {
+    ctx.accounts.player_profile.set_inner(PlayerProfile::new(
+        ctx.accounts.player.key(),
+        ctx.bumps.player_profile,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    CrashBet, CrashRound, GameError, GlobalState, JoinCrashRoundEvent, PlayerProfile, MAX_BPS,
+    MAX_CRASH_POINT,
+};
+
+/// Arguments for joining a crash round.
+/// - deposit: The amount bet, it is held in the global vault.
+/// - cash_out_target: The multiplier the bet is cashed out at (in bps), it can be
+///   lowered while the round is running.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct JoinCrashRoundArgs {
+    pub deposit: u64,
+    pub cash_out_target: u64,
+}
+
+#[derive(Accounts)]
+pub struct JoinCrashRoundAccounts<'info> {
+    #[account(mut)]
+    pub crash_round: Account<'info, CrashRound>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + CrashBet::INIT_SPACE,
+        seeds = [b"crash-bet", crash_round.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub crash_bet: Account<'info, CrashBet>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// The player's lifetime statistics, created with their first session.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<JoinCrashRoundAccounts>, args: &JoinCrashRoundArgs) -> Result<u64> {
+    require!(
+        ctx.accounts.global_state.is_active(),
+        GameError::GameNotActive
+    );
+
+    let crash_round = &ctx.accounts.crash_round;
+
+    require!(crash_round.is_open(), GameError::CrashRoundNotOpen);
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(crash_round.is_betting_open(now), GameError::BettingClosed);
+
+    require!(
+        args.cash_out_target > MAX_BPS && args.cash_out_target <= MAX_CRASH_POINT,
+        GameError::InvalidCashOutTarget
+    );
+
+    // Verify the deposit is within the allowed deposit limits.
+    let vault_lamports = ctx.accounts.vault.lamports();
+
+    ctx.accounts
+        .global_state
+        .check_deposit(args.deposit, vault_lamports)?;
+
+    // Verify the round's bets together can't win more than the maximum payout, the
+    // bets are all settled against the same vault.
+    let profit = u64::try_from(
+        u128::from(args.deposit) * u128::from(args.cash_out_target - MAX_BPS) / u128::from(MAX_BPS),
+    )
+    .map_err(|_| ProgramError::ArithmeticOverflow)?;
+
+    let potential_payout = crash_round
+        .potential_payout
+        .checked_add(profit)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    require_gte!(
+        ctx.accounts.global_state.get_max_payout(vault_lamports)?,
+        potential_payout,
+        GameError::PayoutExceedsMaximum
+    );
+
+    // A profile created by this instruction has no limits yet.
+    ctx.accounts
+        .player_profile
+        .check_limits(args.deposit, now)?;
+
+    Ok(potential_payout)
+}
+
+pub fn join_crash_round_handler(
+    ctx: Context<JoinCrashRoundAccounts>,
+    args: JoinCrashRoundArgs,
+) -> Result<()> {
+    let potential_payout = checks(&ctx, &args)?;
+
+    ctx.accounts.crash_round.potential_payout = potential_payout;
+
+    ctx.accounts.crash_bet.set_inner(CrashBet::new(
+        ctx.accounts.crash_round.key(),
+        ctx.accounts.player.key(),
+        args.deposit,
+        args.cash_out_target,
+        ctx.bumps.crash_bet,
+    ));
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        args.deposit,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    player_profile.initialize_if_needed(ctx.accounts.player.key(), ctx.bumps.player_profile);
+    player_profile.apply_pending_limits(now);
+    player_profile.record_game_started(args.deposit, now);
+
+    let global_state = &mut ctx.accounts.global_state;
+
+    global_state.record_session_opened(args.deposit);
+    global_state.record_crash_stake_opened(args.deposit);
+
+    emit!(JoinCrashRoundEvent {
+        crash_round: ctx.accounts.crash_round.key(),
+        player: ctx.accounts.player.key(),
+        deposit: args.deposit,
+        cash_out_target: args.cash_out_target
+    });
+
+    Ok(())
+}
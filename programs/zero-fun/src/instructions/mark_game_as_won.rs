@@ -6,30 +6,43 @@ use crate::{GameError, GameSession, GameSessionStatus, MarkGameAsWonEvent};
 pub struct MarkGameAsWonAccounts<'info> {
     player: Signer<'info>,
 
-    game_session: Account<'info, GameSession>,
+    #[account(mut)]
+    game_session: AccountLoader<'info, GameSession>,
 }
 
 #[inline(always)]
 fn checks(ctx: &Context<MarkGameAsWonAccounts>) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    // Tournament sessions are scored through `submit_tournament_score` instead.
+    require!(!game_session.is_tournament(), GameError::TournamentSession);
+
+    // Practice sessions are closed through `reveal_practice_game` instead.
+    require!(!game_session.is_practice(), GameError::PracticeSession);
+
+    // Daily challenge sessions are settled through `settle_daily_challenge` instead.
     require!(
-        ctx.accounts
-            .game_session
-            .is_owned_by_player(ctx.accounts.player.key),
-        GameError::InvalidPlayer
+        !game_session.is_daily_challenge(),
+        GameError::DailyChallengeSession
     );
 
     require!(
-        ctx.accounts.game_session.is_active(),
-        GameError::GameSessionNotActive
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
     );
 
+    require!(game_session.is_active(), GameError::GameSessionNotActive);
+
     Ok(())
 }
 
 pub fn mark_game_as_won_handler(ctx: Context<MarkGameAsWonAccounts>) -> Result<()> {
     checks(&ctx)?;
 
-    ctx.accounts.game_session.status = GameSessionStatus::Won;
+    ctx.accounts
+        .game_session
+        .load_mut()?
+        .set_status(GameSessionStatus::Won);
 
     emit!(MarkGameAsWonEvent {
         game_session: ctx.accounts.game_session.key()
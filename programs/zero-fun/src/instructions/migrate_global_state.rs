@@ -0,0 +1,88 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{GameError, GlobalState, LegacyGlobalState, MigrateGlobalStateEvent};
+
+#[derive(Accounts)]
+pub struct MigrateGlobalStateAccounts<'info> {
+    /// CHECK: The global state in its legacy layout, it is deserialized in the checks.
+    #[account(
+        mut,
+        seeds = [b"global-state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<MigrateGlobalStateAccounts>) -> Result<LegacyGlobalState> {
+    let data = ctx.accounts.global_state.try_borrow_data()?;
+
+    // Only a global state in the legacy layout can be migrated.
+    require_eq!(
+        data.len(),
+        8 + LegacyGlobalState::SPACE,
+        GameError::GlobalStateAlreadyMigrated
+    );
+
+    require!(
+        data[..8].eq(GlobalState::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let legacy_global_state = LegacyGlobalState::try_from_slice(&data[8..])?;
+
+    require!(
+        legacy_global_state.admin.eq(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    Ok(legacy_global_state)
+}
+
+pub fn migrate_global_state_handler(ctx: Context<MigrateGlobalStateAccounts>) -> Result<()> {
+    let legacy_global_state = checks(&ctx)?;
+
+    let global_state = ctx.accounts.global_state.to_account_info();
+
+    let new_length = 8 + GlobalState::INIT_SPACE;
+
+    // The admin covers the rent for the fields added since.
+    let rent_top_up = Rent::get()?
+        .minimum_balance(new_length)
+        .saturating_sub(global_state.lamports());
+
+    if rent_top_up > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: global_state.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+
+    global_state.realloc(new_length, true)?;
+
+    legacy_global_state
+        .migrate()
+        .try_serialize(&mut &mut global_state.try_borrow_mut_data()?[..])?;
+
+    emit!(MigrateGlobalStateEvent {
+        admin: ctx.accounts.admin.key(),
+    });
+
+    Ok(())
+}
@@ -27,3 +27,114 @@ pub use initialize_global_state::*;
 
 pub mod withdraw;
 pub use withdraw::*;
+
+pub mod reveal_round;
+pub use reveal_round::*;
+
+pub mod cash_out;
+pub use cash_out::*;
+
+pub mod settle_cash_out;
+pub use settle_cash_out::*;
+
+pub mod initialize_game_config;
+pub use initialize_game_config::*;
+
+pub mod update_game_config;
+pub use update_game_config::*;
+
+pub mod initialize_crash;
+pub use initialize_crash::*;
+
+pub mod start_crash_round;
+pub use start_crash_round::*;
+
+pub mod join_crash_round;
+pub use join_crash_round::*;
+
+pub mod cash_out_crash_bet;
+pub use cash_out_crash_bet::*;
+
+pub mod reveal_crash_round;
+pub use reveal_crash_round::*;
+
+pub mod settle_crash_bet;
+pub use settle_crash_bet::*;
+
+pub mod reveal_auto_play;
+pub use reveal_auto_play::*;
+
+pub mod create_tournament;
+pub use create_tournament::*;
+
+pub mod enter_tournament;
+pub use enter_tournament::*;
+
+pub mod reveal_tournament;
+pub use reveal_tournament::*;
+
+pub mod submit_tournament_score;
+pub use submit_tournament_score::*;
+
+pub mod distribute_tournament_prizes;
+pub use distribute_tournament_prizes::*;
+
+pub mod refund_tournament_entry;
+pub use refund_tournament_entry::*;
+
+pub mod initialize_jackpot;
+pub use initialize_jackpot::*;
+
+pub mod update_jackpot;
+pub use update_jackpot::*;
+
+pub mod open_daily_challenge;
+pub use open_daily_challenge::*;
+
+pub mod start_daily_challenge;
+pub use start_daily_challenge::*;
+
+pub mod reveal_daily_challenge;
+pub use reveal_daily_challenge::*;
+
+pub mod settle_daily_challenge;
+pub use settle_daily_challenge::*;
+
+pub mod reveal_practice_game;
+pub use reveal_practice_game::*;
+
+pub mod initialize_player_profile;
+pub use initialize_player_profile::*;
+
+pub mod set_player_limits;
+pub use set_player_limits::*;
+
+pub mod self_exclude;
+pub use self_exclude::*;
+
+pub mod set_referrer;
+pub use set_referrer::*;
+
+pub mod claim_referral_rewards;
+pub use claim_referral_rewards::*;
+
+pub mod initialize_fee_vault;
+pub use initialize_fee_vault::*;
+
+pub mod withdraw_fees;
+pub use withdraw_fees::*;
+
+pub mod migrate_global_state;
+pub use migrate_global_state::*;
+
+pub mod reconcile_bankroll;
+pub use reconcile_bankroll::*;
+
+pub mod close_global_state;
+pub use close_global_state::*;
+
+pub mod refund_legacy_game_session;
+pub use refund_legacy_game_session::*;
+
+pub mod refund_daily_challenge_session;
+pub use refund_daily_challenge_session::*;
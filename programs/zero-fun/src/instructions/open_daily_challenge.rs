@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    DailyChallenge, GameConfig, GameError, GlobalState, OpenDailyChallengeEvent, HASH_LENGTH,
+};
+
+/// Arguments for opening a daily challenge.
+/// - day: The number of days since the unix epoch the challenge runs on, it can't be
+///   in the past.
+/// - public_config_seed: The public config seed every player's session is played on.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct OpenDailyChallengeArgs {
+    pub day: u64,
+    pub public_config_seed: [u8; HASH_LENGTH],
+}
+
+#[derive(Accounts)]
+#[instruction(args: OpenDailyChallengeArgs)]
+pub struct OpenDailyChallengeAccounts<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DailyChallenge::INIT_SPACE,
+        seeds = [b"daily-challenge", args.day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub daily_challenge: Account<'info, DailyChallenge>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    /// The config of the variant the challenge is played with.
+    pub game_config: Account<'info, GameConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<OpenDailyChallengeAccounts>, args: &OpenDailyChallengeArgs) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    require!(
+        ctx.accounts.game_config.is_active(),
+        GameError::GameVariantNotActive
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require_gte!(
+        args.day,
+        DailyChallenge::get_day(now),
+        GameError::InvalidDailyChallengeParameters
+    );
+
+    Ok(())
+}
+
+pub fn open_daily_challenge_handler(
+    ctx: Context<OpenDailyChallengeAccounts>,
+    args: OpenDailyChallengeArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let variant = ctx.accounts.game_config.get_variant();
+
+    ctx.accounts.daily_challenge.set_inner(DailyChallenge::new(
+        args.day,
+        variant,
+        args.public_config_seed,
+        ctx.bumps.daily_challenge,
+    ));
+
+    emit!(OpenDailyChallengeEvent {
+        daily_challenge: ctx.accounts.daily_challenge.key(),
+        day: args.day,
+        variant,
+        public_config_seed: args.public_config_seed
+    });
+
+    Ok(())
+}
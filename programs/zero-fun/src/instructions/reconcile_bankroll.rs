@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{GlobalState, ReconcileBankrollEvent};
+
+#[derive(Accounts)]
+pub struct ReconcileBankrollAccounts<'info> {
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+}
+
+/// Checks the vault's balance against the bankroll ledger and emits the ledger, anyone can
+/// call it.
+pub fn reconcile_bankroll_handler(ctx: Context<ReconcileBankrollAccounts>) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+
+    let vault_balance = ctx.accounts.vault.lamports();
+
+    let unaccounted = global_state.reconcile(
+        vault_balance,
+        Rent::get()?.minimum_balance(ctx.accounts.vault.data_len()),
+    )?;
+
+    emit!(ReconcileBankrollEvent {
+        vault_balance,
+        total_deposited: global_state.total_deposited,
+        total_paid_out: global_state.total_paid_out,
+        total_swept: global_state.total_swept,
+        total_withdrawn: global_state.total_withdrawn,
+        open_sessions: global_state.open_sessions,
+        unaccounted
+    });
+
+    Ok(())
+}
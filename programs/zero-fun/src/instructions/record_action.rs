@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{GameError, GameSession, GlobalState};
+use crate::{
+    grow_move_storage, CashOutEvent, GameConfig, GameError, GameRules, GameSession, GlobalState,
+    PACKED_MOVE_PAGE_LENGTH,
+};
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Default)]
 pub struct RecordActionArgs {
@@ -9,32 +12,63 @@ pub struct RecordActionArgs {
 
 #[derive(Accounts)]
 pub struct RecordActionAccounts<'info> {
+    /// The player pays for any extra move storage the session needs.
+    #[account(mut)]
     player: Signer<'info>,
 
     global_state: Account<'info, GlobalState>,
 
-    game_session: Account<'info, GameSession>,
+    #[account(mut)]
+    game_session: AccountLoader<'info, GameSession>,
+
+    system_program: Program<'info, System>,
+
+    game_config: Account<'info, GameConfig>,
 }
 
 #[inline(always)]
-fn checks(ctx: &Context<RecordActionAccounts>) -> Result<()> {
+fn checks(ctx: &Context<RecordActionAccounts>, args: &RecordActionArgs) -> Result<()> {
+    let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+    let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
     require!(
-        ctx.accounts
-            .game_session
-            .is_owned_by_player(ctx.accounts.player.key),
+        game_session.is_owned_by_player(ctx.accounts.player.key),
         GameError::InvalidPlayer
     );
 
+    require!(game_session.is_active(), GameError::GameSessionNotActive);
+
+    // Auto-play sessions reveal all their moves at once.
+    require!(!game_session.is_auto_play(), GameError::AutoPlaySession);
+
+    // In hash chain mode each round has to be revealed before the next one is played.
     require!(
-        ctx.accounts.game_session.is_active(),
-        GameError::GameSessionNotActive
+        !(game_session.is_hash_chain() && game_session.has_unrevealed_round()),
+        GameError::RoundNotRevealed
     );
 
     require!(
-        ctx.accounts.global_state.is_active(),
+        ctx.accounts.global_state.is_playable(),
         GameError::GameNotActive
     );
 
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
+    require!(
+        ctx.accounts.game_config.is_active(),
+        GameError::GameVariantNotActive
+    );
+
+    ctx.accounts
+        .game_config
+        .parameters
+        .validate_move(game_session, player_moves, args.action)?;
+
     Ok(())
 }
 
@@ -42,16 +76,55 @@ pub fn record_action_handler(
     ctx: Context<RecordActionAccounts>,
     args: RecordActionArgs,
 ) -> Result<()> {
-    checks(&ctx)?;
+    checks(&ctx, &args)?;
+
+    let is_move_storage_full = {
+        let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+        let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+        game_session.is_move_storage_full(player_moves)
+    };
+
+    // The player covers the rent for the extra page.
+    if is_move_storage_full {
+        let game_session = ctx.accounts.game_session.to_account_info();
+
+        let new_length = game_session.data_len() + PACKED_MOVE_PAGE_LENGTH;
+
+        grow_move_storage(
+            &game_session,
+            &ctx.accounts.player.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            new_length,
+        )?;
+    }
+
+    let mut game_session_data = ctx.accounts.game_session.as_ref().try_borrow_mut_data()?;
+    let (game_session, player_moves) = GameSession::split_account_data_mut(&mut game_session_data);
 
     // Update the last action time
     let now = Clock::get()?.unix_timestamp;
-    ctx.accounts.game_session.last_action_time = now;
+    game_session.last_action_time = now;
 
     // Record the player's move
-    ctx.accounts
-        .game_session
-        .set_next_player_move(args.action)?;
+    game_session.set_next_player_move(player_moves, args.action)?;
+
+    // Nothing is left to play, so the session is cashed out right away and the house
+    // has to reveal its seed.
+    if ctx
+        .accounts
+        .game_config
+        .parameters
+        .is_complete(game_session.get_player_move_count())
+    {
+        game_session.cash_out(now);
+
+        emit!(CashOutEvent {
+            game_session: ctx.accounts.game_session.key(),
+            round_count: game_session.next_player_move_position,
+            reveal_deadline: game_session.get_reveal_deadline()
+        });
+    }
 
     Ok(())
 }
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    DailyChallenge, GameError, GameSession, GlobalState, PlayerProfile,
+    RefundDailyChallengeSessionEvent,
+};
+
+#[derive(Accounts)]
+pub struct RefundDailyChallengeSessionAccounts<'info> {
+    pub daily_challenge: Account<'info, DailyChallenge>,
+
+    #[account(
+        mut,
+        close = player
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// CHECK: This is the vault account where the player's deposit is stored.
+    #[account(mut)]
+    pub user_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RefundDailyChallengeSessionAccounts>) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    require!(
+        game_session.is_daily_challenge()
+            && game_session
+                .public_config_seed
+                .eq(&ctx.accounts.daily_challenge.public_config_seed),
+        GameError::InvalidDailyChallenge
+    );
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
+        GameError::InvalidVault
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // The house never revealed the seed, so the sessions can't be settled.
+    require!(
+        ctx.accounts.daily_challenge.is_open(),
+        GameError::InvalidDailyChallengeStatus
+    );
+
+    require!(
+        ctx.accounts.daily_challenge.is_reveal_window_expired(now),
+        GameError::RevealWindowNotExpired
+    );
+
+    Ok(())
+}
+
+/// Pays a player their deposit back if the house didn't reveal the daily challenge's seed
+/// in time, anyone can call it.
+pub fn refund_daily_challenge_session_handler(
+    ctx: Context<RefundDailyChallengeSessionAccounts>,
+) -> Result<()> {
+    checks(&ctx)?;
+
+    let deposit = ctx.accounts.game_session.load()?.deposit;
+
+    // The deposit(with the vault's rent) goes back to the player.
+    let user_vault_balance = ctx.accounts.user_vault.lamports();
+
+    **ctx.accounts.user_vault.try_borrow_mut_lamports()? = 0;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += user_vault_balance;
+
+    ctx.accounts.global_state.record_session_closed();
+
+    ctx.accounts.player_profile.record_game_defaulted(deposit);
+
+    emit!(RefundDailyChallengeSessionEvent {
+        daily_challenge: ctx.accounts.daily_challenge.key(),
+        player: ctx.accounts.player.key(),
+        game_session: ctx.accounts.game_session.key(),
+        amount: user_vault_balance
+    });
+
+    Ok(())
+}
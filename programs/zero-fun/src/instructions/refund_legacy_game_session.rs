@@ -0,0 +1,106 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+use crate::{GameError, GameSession, GlobalState, LegacyGameSession, RefundLegacyGameSessionEvent};
+
+#[derive(Accounts)]
+pub struct RefundLegacyGameSessionAccounts<'info> {
+    /// CHECK: The game session in its legacy layout, it is deserialized in the checks.
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub game_session: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// CHECK: This is the vault account where the player's deposit is stored, its address
+    /// is verified in the checks.
+    #[account(mut)]
+    pub user_vault: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"global-state"],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RefundLegacyGameSessionAccounts>) -> Result<LegacyGameSession> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    let data = ctx.accounts.game_session.try_borrow_data()?;
+
+    // Only a session in the legacy layout can be refunded, every other one is settled.
+    require_eq!(
+        data.len(),
+        8 + LegacyGameSession::SPACE,
+        GameError::NotLegacyGameSession
+    );
+
+    require!(
+        data[..8].eq(GameSession::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    // The metadata is stored with its length so the rest of the account may be unused.
+    let legacy_game_session = LegacyGameSession::deserialize(&mut &data[8..])?;
+
+    require!(
+        legacy_game_session.player.eq(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    // Legacy sessions stored the global vault instead of their own, so it is derived.
+    let (user_vault, _) = Pubkey::find_program_address(
+        &[
+            b"vault",
+            legacy_game_session.public_config_seed.as_ref(),
+            legacy_game_session.player.as_ref(),
+        ],
+        &crate::ID,
+    );
+
+    require!(
+        user_vault.eq(ctx.accounts.user_vault.key),
+        GameError::InvalidVault
+    );
+
+    Ok(legacy_game_session)
+}
+
+pub fn refund_legacy_game_session_handler(
+    ctx: Context<RefundLegacyGameSessionAccounts>,
+) -> Result<()> {
+    let legacy_game_session = checks(&ctx)?;
+
+    // The deposit was never recorded in the bankroll ledger, so it is handed back as is.
+    let user_vault_balance = ctx.accounts.user_vault.lamports();
+
+    **ctx.accounts.player.try_borrow_mut_lamports()? += user_vault_balance;
+    **ctx.accounts.user_vault.try_borrow_mut_lamports()? = 0;
+
+    let game_session = ctx.accounts.game_session.to_account_info();
+
+    let game_session_balance = game_session.lamports();
+
+    **ctx.accounts.player.try_borrow_mut_lamports()? += game_session_balance;
+    **game_session.try_borrow_mut_lamports()? = 0;
+
+    game_session.assign(&system_program::ID);
+    game_session.realloc(0, false)?;
+
+    emit!(RefundLegacyGameSessionEvent {
+        game_session: ctx.accounts.game_session.key(),
+        player: legacy_game_session.player,
+        amount: user_vault_balance
+    });
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, GameSession, PlayerProfile, RefundTournamentEntryEvent, Tournament};
+
+#[derive(Accounts)]
+pub struct RefundTournamentEntryAccounts<'info> {
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        close = player
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RefundTournamentEntryAccounts>) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    require!(
+        game_session.is_tournament()
+            && game_session.is_vault_for_game(&ctx.accounts.tournament.key()),
+        GameError::InvalidTournament
+    );
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // The house never revealed the seed, so the sessions can't be scored.
+    require!(
+        ctx.accounts.tournament.is_open(),
+        GameError::InvalidTournamentStatus
+    );
+
+    require!(
+        ctx.accounts.tournament.is_reveal_window_expired(now),
+        GameError::RevealWindowNotExpired
+    );
+
+    Ok(())
+}
+
+/// Pays an entrant their entry fee back if the house didn't reveal the tournament's seed in
+/// time, anyone can call it.
+pub fn refund_tournament_entry_handler(ctx: Context<RefundTournamentEntryAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let tournament = &mut ctx.accounts.tournament;
+
+    let amount = tournament.entry_fee;
+
+    tournament.prize_pool = tournament
+        .prize_pool
+        .checked_sub(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    **tournament.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += amount;
+
+    ctx.accounts.player_profile.record_game_defaulted(amount);
+
+    emit!(RefundTournamentEntryEvent {
+        tournament: tournament.key(),
+        player: ctx.accounts.player.key(),
+        amount
+    });
+
+    Ok(())
+}
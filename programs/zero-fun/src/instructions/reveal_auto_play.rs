@@ -0,0 +1,156 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    grow_move_storage, CashOutEvent, GameConfig, GameError, GameRules, GameSession, GlobalState,
+    RevealAutoPlayEvent, AUTO_PLAY_SEED, HASH_LENGTH, MAX_AUTO_PLAY_MOVE_COUNT,
+    PACKED_MOVE_PAGE_LENGTH,
+};
+
+/// Arguments for revealing the moves of an auto-play session.
+/// - moves: The planned moves, they are repeated until `stop_after` moves were made.
+/// - stop_after: The number of moves the session is played for.
+/// - salt: A random value picked by the player, so the commitment can't be guessed.
+///
+/// The commitment is `HASH("auto-play" + LE16(stop_after) + salt + moves)`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RevealAutoPlayArgs {
+    pub moves: Vec<u8>,
+    pub stop_after: u16,
+    pub salt: [u8; HASH_LENGTH],
+}
+
+#[derive(Accounts)]
+pub struct RevealAutoPlayAccounts<'info> {
+    /// The player pays for any extra move storage the session needs.
+    #[account(mut)]
+    player: Signer<'info>,
+
+    global_state: Account<'info, GlobalState>,
+
+    #[account(mut)]
+    game_session: AccountLoader<'info, GameSession>,
+
+    system_program: Program<'info, System>,
+
+    game_config: Account<'info, GameConfig>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RevealAutoPlayAccounts>, args: &RevealAutoPlayArgs) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(game_session.is_active(), GameError::GameSessionNotActive);
+
+    require!(game_session.is_auto_play(), GameError::NotAutoPlaySession);
+
+    require!(
+        ctx.accounts.global_state.is_playable(),
+        GameError::GameNotActive
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
+    require!(
+        ctx.accounts.game_config.is_active(),
+        GameError::GameVariantNotActive
+    );
+
+    require!(
+        !args.moves.is_empty()
+            && args.stop_after > 0
+            && usize::from(args.stop_after) <= MAX_AUTO_PLAY_MOVE_COUNT,
+        GameError::InvalidAutoPlayMoves
+    );
+
+    // Verify the moves are the ones committed to when the session was created.
+    let commitment: [u8; HASH_LENGTH] = hashv(&[
+        AUTO_PLAY_SEED.as_ref(),
+        args.stop_after.to_le_bytes().as_ref(),
+        args.salt.as_ref(),
+        args.moves.as_ref(),
+    ])
+    .to_bytes();
+
+    require!(
+        game_session.auto_play_commitment.eq(&commitment),
+        GameError::InvalidCommitment
+    );
+
+    Ok(())
+}
+
+/// Records every move of an auto-play session and cashes it out, it is then settled like
+/// any other cashed out session.
+pub fn reveal_auto_play_handler(
+    ctx: Context<RevealAutoPlayAccounts>,
+    args: RevealAutoPlayArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    // Grow the move storage once to fit every move.
+    let required_length = {
+        let game_session = ctx.accounts.game_session.load()?;
+
+        let move_bytes =
+            (usize::from(args.stop_after) * game_session.get_move_bit_width()).div_ceil(8);
+
+        GameSession::PLAYER_MOVES_OFFSET
+            + move_bytes.div_ceil(PACKED_MOVE_PAGE_LENGTH) * PACKED_MOVE_PAGE_LENGTH
+    };
+
+    let game_session_info = ctx.accounts.game_session.to_account_info();
+
+    if game_session_info.data_len() < required_length {
+        grow_move_storage(
+            &game_session_info,
+            &ctx.accounts.player.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            required_length,
+        )?;
+    }
+
+    let mut game_session_data = ctx.accounts.game_session.as_ref().try_borrow_mut_data()?;
+    let (game_session, player_moves) = GameSession::split_account_data_mut(&mut game_session_data);
+
+    let rules = &ctx.accounts.game_config.parameters;
+
+    for player_move in args.moves.iter().cycle().take(usize::from(args.stop_after)) {
+        // Single-round variants stop once there is nothing left to play.
+        if rules.is_complete(game_session.get_player_move_count()) {
+            break;
+        }
+
+        rules.validate_move(game_session, player_moves, *player_move)?;
+
+        game_session.set_next_player_move(player_moves, *player_move)?;
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    game_session.cash_out(now);
+
+    emit!(RevealAutoPlayEvent {
+        game_session: ctx.accounts.game_session.key(),
+        moves: args.moves,
+        stop_after: args.stop_after,
+        salt: args.salt
+    });
+
+    emit!(CashOutEvent {
+        game_session: ctx.accounts.game_session.key(),
+        round_count: game_session.next_player_move_position,
+        reveal_deadline: game_session.get_reveal_deadline()
+    });
+
+    Ok(())
+}
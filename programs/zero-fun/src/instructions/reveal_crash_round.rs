@@ -0,0 +1,87 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    get_crash_point, CrashRound, CrashRoundStatus, CrashState, GameError, RevealCrashRoundEvent,
+    HASH_LENGTH,
+};
+
+/// Arguments for revealing a crash round.
+/// - chain_link: The next link of the reverse hash chain, it hashes to the previously
+///   revealed link(or the committed head for the first round).
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RevealCrashRoundArgs {
+    pub chain_link: [u8; HASH_LENGTH],
+}
+
+#[derive(Accounts)]
+pub struct RevealCrashRoundAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"crash-state"],
+        bump = crash_state.bump
+    )]
+    pub crash_state: Account<'info, CrashState>,
+
+    #[account(
+        mut,
+        seeds = [b"crash-round", crash_round.round_index.to_le_bytes().as_ref()],
+        bump = crash_round.bump
+    )]
+    pub crash_round: Account<'info, CrashRound>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RevealCrashRoundAccounts>, args: &RevealCrashRoundArgs) -> Result<()> {
+    let crash_round = &ctx.accounts.crash_round;
+
+    require!(crash_round.is_open(), GameError::CrashRoundNotOpen);
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        !crash_round.is_betting_open(now),
+        GameError::BettingNotClosed
+    );
+
+    // Verify the link is the next one in the committed chain.
+    let previous_link: [u8; HASH_LENGTH] = hashv(&[args.chain_link.as_ref()]).to_bytes();
+
+    require!(
+        ctx.accounts.crash_state.chain_link.eq(&previous_link),
+        GameError::InvalidHashChainLink
+    );
+
+    Ok(())
+}
+
+/// Anyone holding the next link can reveal the round, the link itself proves it.
+pub fn reveal_crash_round_handler(
+    ctx: Context<RevealCrashRoundAccounts>,
+    args: RevealCrashRoundArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let crash_state = &mut ctx.accounts.crash_state;
+    let crash_round = &mut ctx.accounts.crash_round;
+
+    crash_state.chain_link = args.chain_link;
+    crash_state.has_open_round = false;
+
+    crash_round.chain_link = args.chain_link;
+    crash_round.crash_point = get_crash_point(&args.chain_link, crash_round.house_edge);
+    crash_round.revealed_late = crash_round.is_reveal_window_expired(now);
+    crash_round.status = CrashRoundStatus::Revealed;
+
+    emit!(RevealCrashRoundEvent {
+        crash_round: crash_round.key(),
+        round_index: crash_round.round_index,
+        chain_link: args.chain_link,
+        house_edge: crash_round.house_edge,
+        crash_point: crash_round.crash_point,
+        revealed_late: crash_round.revealed_late
+    });
+
+    Ok(())
+}
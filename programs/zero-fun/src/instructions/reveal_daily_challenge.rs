@@ -0,0 +1,77 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    DailyChallenge, DailyChallengeStatus, GameError, RevealDailyChallengeEvent, HASH_LENGTH,
+    PUBLIC_SEED,
+};
+
+/// Arguments for revealing a daily challenge's private config seed.
+/// - private_config_seed: The SHA-256 hash seed used to derive the private configuration
+///   of every session played on the challenge.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RevealDailyChallengeArgs {
+    pub private_config_seed: [u8; HASH_LENGTH],
+}
+
+#[derive(Accounts)]
+pub struct RevealDailyChallengeAccounts<'info> {
+    #[account(mut)]
+    pub daily_challenge: Account<'info, DailyChallenge>,
+}
+
+#[inline(always)]
+fn checks(
+    ctx: &Context<RevealDailyChallengeAccounts>,
+    args: &RevealDailyChallengeArgs,
+) -> Result<()> {
+    let daily_challenge = &ctx.accounts.daily_challenge;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        daily_challenge.is_open(),
+        GameError::InvalidDailyChallengeStatus
+    );
+
+    require!(
+        daily_challenge.has_ended(now),
+        GameError::DailyChallengeNotEnded
+    );
+
+    // Sessions may already have been refunded once the window expired.
+    require!(
+        !daily_challenge.is_reveal_window_expired(now),
+        GameError::RevealWindowExpired
+    );
+
+    // Verify the public config was previously commited to.
+    let public_config_seed: [u8; HASH_LENGTH] =
+        hashv(&[PUBLIC_SEED.as_ref(), args.private_config_seed.as_ref()]).to_bytes();
+
+    require!(
+        daily_challenge.public_config_seed.eq(&public_config_seed),
+        GameError::InvalidGameSeed
+    );
+
+    Ok(())
+}
+
+/// Anyone holding the private config seed can reveal it, the commitment proves it.
+pub fn reveal_daily_challenge_handler(
+    ctx: Context<RevealDailyChallengeAccounts>,
+    args: RevealDailyChallengeArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let daily_challenge = &mut ctx.accounts.daily_challenge;
+
+    daily_challenge.private_config_seed = args.private_config_seed;
+    daily_challenge.status = DailyChallengeStatus::Revealed;
+
+    emit!(RevealDailyChallengeEvent {
+        daily_challenge: daily_challenge.key(),
+        private_config_seed: args.private_config_seed
+    });
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    get_rounds_survived, GameConfig, GameError, GameSession, RevealPracticeGameEvent, HASH_LENGTH,
+    PUBLIC_SEED,
+};
+
+/// Arguments for revealing a practice session.
+/// - private_config_seed: The SHA-256 hash seed used to derive the private configuration
+///   of the game. It can be left out once the session can be defaulted, the session is
+///   then closed without an outcome.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RevealPracticeGameArgs {
+    pub private_config_seed: Option<[u8; HASH_LENGTH]>,
+}
+
+#[derive(Accounts)]
+pub struct RevealPracticeGameAccounts<'info> {
+    #[account(
+        mut,
+        close = player
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    pub game_config: Account<'info, GameConfig>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RevealPracticeGameAccounts>, args: &RevealPracticeGameArgs) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    require!(game_session.is_practice(), GameError::NotPracticeSession);
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
+    match args.private_config_seed {
+        Some(private_config_seed) => {
+            // Verify the public config was previously commited to.
+            let public_config_seed: [u8; HASH_LENGTH] =
+                hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+            require!(
+                game_session.public_config_seed.eq(&public_config_seed),
+                GameError::InvalidGameSeed
+            );
+        }
+        None => {
+            let now = Clock::get()?.unix_timestamp;
+
+            require!(game_session.can_default(now), GameError::TooSoonToDefault);
+        }
+    }
+
+    Ok(())
+}
+
+/// Marks the outcome of a practice session against its revealed seed and closes it,
+/// returning the rent to the player. Anyone holding the seed can reveal it, nothing is
+/// paid out either way.
+pub fn reveal_practice_game_handler(
+    ctx: Context<RevealPracticeGameAccounts>,
+    args: RevealPracticeGameArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let (rounds_survived, is_lost) = {
+        let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+        let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+        match args.private_config_seed {
+            Some(private_config_seed) => {
+                let rounds_survived = get_rounds_survived(
+                    &ctx.accounts.game_config.parameters,
+                    game_session,
+                    player_moves,
+                    &private_config_seed,
+                );
+
+                (
+                    Some(rounds_survived),
+                    usize::from(rounds_survived) < game_session.get_player_move_count(),
+                )
+            }
+            None => (None, false),
+        }
+    };
+
+    emit!(RevealPracticeGameEvent {
+        game_session: ctx.accounts.game_session.key(),
+        player: ctx.accounts.player.key(),
+        private_config_seed: args.private_config_seed,
+        rounds_survived,
+        is_lost
+    });
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    GameConfig, GameError, GameRules, GameSession, GameSessionStatus, GlobalState, PlayerProfile,
+    RevealRoundEvent, HASH_LENGTH,
+};
+
+/// Arguments for revealing the outcome of a round in a hash chain session.
+/// - hash_chain_link: The next link of the reverse hash chain, it hashes to the
+///   previously revealed link(or the committed head for the first round).
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RevealRoundArgs {
+    pub hash_chain_link: [u8; HASH_LENGTH],
+}
+
+#[derive(Accounts)]
+pub struct RevealRoundAccounts<'info> {
+    #[account(mut)]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    /// The player receives the rent for the session if the round was lost.
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// CHECK: This is the vault account where the player's deposit is stored.
+    #[account(mut)]
+    pub user_vault: UncheckedAccount<'info>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub game_config: Account<'info, GameConfig>,
+
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RevealRoundAccounts>, args: &RevealRoundArgs) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    // Practice sessions are closed through `reveal_practice_game` instead.
+    require!(!game_session.is_practice(), GameError::PracticeSession);
+
+    require!(game_session.is_active(), GameError::GameSessionNotActive);
+
+    require!(game_session.is_hash_chain(), GameError::InvalidFairnessMode);
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
+        GameError::InvalidVault
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
+    // The player must have made a move for the round being revealed.
+    require!(
+        game_session.has_unrevealed_round(),
+        GameError::NoRoundToReveal
+    );
+
+    // Verify the link is the next one in the committed chain.
+    let previous_link: [u8; HASH_LENGTH] = hashv(&[args.hash_chain_link.as_ref()]).to_bytes();
+
+    require!(
+        game_session.hash_chain_link.eq(&previous_link),
+        GameError::InvalidHashChainLink
+    );
+
+    Ok(())
+}
+
+pub fn reveal_round_handler(
+    ctx: Context<RevealRoundAccounts>,
+    args: RevealRoundArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let (round, survived) = {
+        let mut game_session_data = ctx.accounts.game_session.as_ref().try_borrow_mut_data()?;
+        let (game_session, player_moves) =
+            GameSession::split_account_data_mut(&mut game_session_data);
+
+        let round = game_session.revealed_round_count;
+
+        // The revealed link takes the place of the private config seed for this round
+        let survived = game_session
+            .get_player_move(player_moves, usize::from(round))
+            .is_some_and(|player_move| {
+                !ctx.accounts.game_config.parameters.is_losing_move(
+                    game_session,
+                    &args.hash_chain_link,
+                    round,
+                    player_move,
+                )
+            });
+
+        game_session.hash_chain_link = args.hash_chain_link;
+        // Overflow not possible it is bounded by the move count
+        game_session.revealed_round_count += 1;
+
+        if !survived {
+            game_session.set_status(GameSessionStatus::Lost);
+        }
+
+        (round, survived)
+    };
+
+    if !survived {
+        let user_vault_balance = ctx.accounts.user_vault.lamports();
+
+        // Transfer funds to the main vault
+        **ctx.accounts.vault.try_borrow_mut_lamports()? += user_vault_balance;
+        **ctx.accounts.user_vault.try_borrow_mut_lamports()? = 0;
+
+        let global_state = &mut ctx.accounts.global_state;
+
+        global_state.record_swept(user_vault_balance);
+        global_state.record_session_closed();
+
+        ctx.accounts.player_profile.record_game_lost();
+
+        ctx.accounts
+            .game_session
+            .close(ctx.accounts.player.to_account_info())?;
+    }
+
+    emit!(RevealRoundEvent {
+        game_session: ctx.accounts.game_session.key(),
+        round,
+        hash_chain_link: args.hash_chain_link,
+        survived
+    });
+
+    Ok(())
+}
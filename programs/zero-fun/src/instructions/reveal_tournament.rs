@@ -0,0 +1,70 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    GameError, RevealTournamentEvent, Tournament, TournamentStatus, HASH_LENGTH, PUBLIC_SEED,
+};
+
+/// Arguments for revealing a tournament's private config seed.
+/// - private_config_seed: The SHA-256 hash seed used to derive the private configuration
+///   of every entrant's session.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RevealTournamentArgs {
+    pub private_config_seed: [u8; HASH_LENGTH],
+}
+
+#[derive(Accounts)]
+pub struct RevealTournamentAccounts<'info> {
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<RevealTournamentAccounts>, args: &RevealTournamentArgs) -> Result<()> {
+    let tournament = &ctx.accounts.tournament;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(tournament.is_open(), GameError::InvalidTournamentStatus);
+
+    require!(tournament.has_ended(now), GameError::TournamentNotEnded);
+
+    // Once the window expires the entrants can take their entry fees back instead.
+    require!(
+        !tournament.is_reveal_window_expired(now),
+        GameError::RevealWindowExpired
+    );
+
+    // Verify the public config was previously commited to.
+    let public_config_seed: [u8; HASH_LENGTH] =
+        hashv(&[PUBLIC_SEED.as_ref(), args.private_config_seed.as_ref()]).to_bytes();
+
+    require!(
+        tournament.public_config_seed.eq(&public_config_seed),
+        GameError::InvalidGameSeed
+    );
+
+    Ok(())
+}
+
+/// Anyone holding the private config seed can reveal it, the commitment proves it.
+pub fn reveal_tournament_handler(
+    ctx: Context<RevealTournamentAccounts>,
+    args: RevealTournamentArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let tournament = &mut ctx.accounts.tournament;
+
+    tournament.private_config_seed = args.private_config_seed;
+    tournament.reveal_time = now;
+    tournament.status = TournamentStatus::Revealed;
+
+    emit!(RevealTournamentEvent {
+        tournament: tournament.key(),
+        private_config_seed: args.private_config_seed
+    });
+
+    Ok(())
+}
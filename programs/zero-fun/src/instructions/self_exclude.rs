@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, PlayerProfile, SelfExcludeEvent, MAX_SELF_EXCLUSION_PERIOD};
+
+/// Arguments for a player excluding themselves from playing.
+/// - period: The time from now no session can be started for, an exclusion in force
+///   is only ever extended.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SelfExcludeArgs {
+    pub period: i64,
+}
+
+#[derive(Accounts)]
+pub struct SelfExcludeAccounts<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(args: &SelfExcludeArgs) -> Result<()> {
+    require!(
+        args.period > 0 && args.period <= MAX_SELF_EXCLUSION_PERIOD,
+        GameError::InvalidSelfExclusionPeriod
+    );
+
+    Ok(())
+}
+
+pub fn self_exclude_handler(
+    ctx: Context<SelfExcludeAccounts>,
+    args: SelfExcludeArgs,
+) -> Result<()> {
+    checks(&args)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    player_profile.initialize_if_needed(ctx.accounts.player.key(), ctx.bumps.player_profile);
+    player_profile.self_exclude(now, args.period);
+
+    emit!(SelfExcludeEvent {
+        player: ctx.accounts.player.key(),
+        excluded_until: player_profile.excluded_until
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{PlayerLimits, PlayerProfile, SetPlayerLimitsEvent, LIMIT_COOLING_OFF_PERIOD};
+
+/// Arguments for setting a player's limits.
+/// - limits: The new limits, a limit of 0 is no limit. Tighter limits take effect
+///   immediately, looser ones after `LIMIT_COOLING_OFF_PERIOD`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SetPlayerLimitsArgs {
+    pub limits: PlayerLimits,
+}
+
+#[derive(Accounts)]
+pub struct SetPlayerLimitsAccounts<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_player_limits_handler(
+    ctx: Context<SetPlayerLimitsAccounts>,
+    args: SetPlayerLimitsArgs,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    player_profile.initialize_if_needed(ctx.accounts.player.key(), ctx.bumps.player_profile);
+
+    let is_pending = player_profile.set_limits(args.limits, now, LIMIT_COOLING_OFF_PERIOD);
+
+    emit!(SetPlayerLimitsEvent {
+        player: ctx.accounts.player.key(),
+        limits: args.limits,
+        pending_limits_time: is_pending.then_some(player_profile.pending_limits_time)
+    });
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, PlayerProfile, ReferrerRewards, SetReferrerEvent};
+
+#[derive(Accounts)]
+pub struct SetReferrerAccounts<'info> {
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// CHECK: This is the referrer, any account other than the player can refer them.
+    pub referrer: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + ReferrerRewards::INIT_SPACE,
+        seeds = [b"referrer-rewards", referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_rewards: Account<'info, ReferrerRewards>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<SetReferrerAccounts>) -> Result<()> {
+    require!(
+        !ctx.accounts.player_profile.has_referrer(),
+        GameError::ReferrerAlreadySet
+    );
+
+    require!(
+        ctx.accounts.referrer.key().ne(ctx.accounts.player.key)
+            && ctx.accounts.referrer.key().ne(&Pubkey::default()),
+        GameError::InvalidReferrer
+    );
+
+    Ok(())
+}
+
+/// Binds the player to their referrer, the referrer's rewards account is created here so
+/// the settlement instructions never have to pay for it.
+pub fn set_referrer_handler(ctx: Context<SetReferrerAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    player_profile.initialize_if_needed(ctx.accounts.player.key(), ctx.bumps.player_profile);
+    player_profile.referrer = ctx.accounts.referrer.key();
+
+    let referrer_rewards = &mut ctx.accounts.referrer_rewards;
+
+    referrer_rewards.initialize_if_needed(ctx.accounts.referrer.key(), ctx.bumps.referrer_rewards);
+    referrer_rewards.referral_count = referrer_rewards.referral_count.saturating_add(1);
+
+    emit!(SetReferrerEvent {
+        player: ctx.accounts.player.key(),
+        referrer: ctx.accounts.referrer.key()
+    });
+
+    Ok(())
+}
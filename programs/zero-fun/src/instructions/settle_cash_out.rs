@@ -0,0 +1,246 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{
+    accrue_referral_reward, get_payout_for_rounds, pay_out_jackpot, GameConfig, GameError,
+    GameRules, GameSession, GlobalState, Jackpot, PlayerProfile, ReferrerRewards,
+    SettleCashOutEvent, HASH_LENGTH, PUBLIC_SEED,
+};
+
+/// Arguments for settling a cashed out game session.
+/// - private_config_seed: The SHA-256 hash seed used to derive the private configuration
+///   of the game, when it is provided the session can be settled before the reveal
+///   window expires as it proves none of the recorded moves failed, the session is then
+///   also checked against the jackpot with it.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SettleCashOutArgs {
+    pub private_config_seed: Option<[u8; HASH_LENGTH]>,
+}
+
+#[derive(Accounts)]
+pub struct SettleCashOutAccounts<'info> {
+    #[account(
+        mut,
+        close = player
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// CHECK: This is the vault account where the player's deposit is stored.
+    #[account(mut)]
+    pub user_vault: UncheckedAccount<'info>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub game_config: Account<'info, GameConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// The rewards account of the player's referrer, required if the player was referred.
+    #[account(
+        mut,
+        seeds = [b"referrer-rewards", player_profile.referrer.as_ref()],
+        bump = referrer_rewards.bump
+    )]
+    pub referrer_rewards: Option<Account<'info, ReferrerRewards>>,
+}
+
+#[inline(always)]
+fn checks(
+    ctx: &Context<SettleCashOutAccounts>,
+    args: &SettleCashOutArgs,
+) -> Result<Option<[u8; HASH_LENGTH]>> {
+    let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+    let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+    // Tournament sessions are scored through `submit_tournament_score` instead.
+    require!(!game_session.is_tournament(), GameError::TournamentSession);
+
+    // Practice sessions are closed through `reveal_practice_game` instead.
+    require!(!game_session.is_practice(), GameError::PracticeSession);
+
+    // Daily challenge sessions are settled through `settle_daily_challenge` instead.
+    require!(
+        !game_session.is_daily_challenge(),
+        GameError::DailyChallengeSession
+    );
+
+    require!(
+        game_session.is_cashed_out(),
+        GameError::GameSessionNotCashedOut
+    );
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
+        GameError::InvalidVault
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
+    // Every round of a hash chain session was revealed before it cashed out.
+    if game_session.is_hash_chain() {
+        return Ok(None);
+    }
+
+    match args.private_config_seed {
+        Some(private_config_seed) => {
+            // Verify the public config was previously commited to.
+            let public_config_seed: [u8; HASH_LENGTH] =
+                hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+            require!(
+                game_session.public_config_seed.eq(&public_config_seed),
+                GameError::InvalidGameSeed
+            );
+
+            // Verify none of the recorded moves was the fail move, otherwise the
+            // session should be finalized as lost.
+            for position in 0..game_session.next_player_move_position {
+                let player_move = game_session
+                    .get_player_move(player_moves, usize::from(position))
+                    .ok_or(GameError::InvalidFailPosition)?;
+
+                require!(
+                    !ctx.accounts.game_config.parameters.is_losing_move(
+                        game_session,
+                        &private_config_seed,
+                        position,
+                        player_move
+                    ),
+                    GameError::InvalidFailPosition
+                );
+            }
+        }
+        None => {
+            let now = Clock::get()?.unix_timestamp;
+
+            require!(
+                game_session.is_reveal_window_expired(now),
+                GameError::RevealWindowNotExpired
+            );
+        }
+    }
+
+    Ok(args.private_config_seed)
+}
+
+pub fn settle_cash_out_handler(
+    ctx: Context<SettleCashOutAccounts>,
+    args: SettleCashOutArgs,
+) -> Result<()> {
+    let private_config_seed = checks(&ctx, &args)?;
+
+    let (round_count, deposit, payout, is_jackpot_hit) = {
+        let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+        let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+        let current_max_payout = ctx
+            .accounts
+            .global_state
+            .get_max_payout(ctx.accounts.vault.lamports())?;
+
+        let payout = get_payout_for_rounds(
+            &ctx.accounts.game_config.parameters,
+            game_session,
+            player_moves,
+            ctx.accounts.global_state.house_edge,
+            current_max_payout,
+        );
+
+        // Every recorded move survived, so the move threshold of the jackpot is checked
+        // whether or not the house revealed its seed.
+        let jackpot = &ctx.accounts.jackpot;
+
+        let is_jackpot_hit = jackpot.is_hit_by_moves(game_session.get_player_move_count())
+            || private_config_seed.is_some_and(|private_config_seed| {
+                jackpot.is_hit(&private_config_seed, &game_session.client_seed)
+            });
+
+        (
+            game_session.next_player_move_position,
+            game_session.deposit,
+            payout,
+            is_jackpot_hit,
+        )
+    };
+
+    // Transfer the deposit(with the vault's rent) and the payout to the player
+    let user_vault_balance = ctx.accounts.user_vault.lamports();
+
+    **ctx.accounts.vault.try_borrow_mut_lamports()? -= payout;
+    **ctx.accounts.user_vault.try_borrow_mut_lamports()? = 0;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += user_vault_balance
+        .checked_add(payout)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let global_state = &mut ctx.accounts.global_state;
+
+    global_state.record_paid_out(payout);
+    global_state.record_session_closed();
+
+    // The jackpot pays out of its own pool, apart from the global vault.
+    let jackpot_payout = if is_jackpot_hit {
+        pay_out_jackpot(
+            &mut ctx.accounts.jackpot,
+            &ctx.accounts.player.to_account_info(),
+            ctx.accounts.game_session.key(),
+        )?
+    } else {
+        0
+    };
+
+    ctx.accounts.player_profile.record_game_won(
+        deposit
+            .saturating_add(payout)
+            .saturating_add(jackpot_payout),
+    );
+
+    accrue_referral_reward(
+        &ctx.accounts.player_profile,
+        ctx.accounts.referrer_rewards.as_mut(),
+        &ctx.accounts.vault.to_account_info(),
+        &mut ctx.accounts.global_state,
+        ctx.accounts.game_session.key(),
+        deposit,
+    )?;
+
+    emit!(SettleCashOutEvent {
+        game_session: ctx.accounts.game_session.key(),
+        round_count,
+        payout
+    });
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{CrashBet, CrashRound, GameError, GlobalState, PlayerProfile, SettleCrashBetEvent};
+
+#[derive(Accounts)]
+pub struct SettleCrashBetAccounts<'info> {
+    pub crash_round: Account<'info, CrashRound>,
+
+    #[account(
+        mut,
+        close = player
+    )]
+    pub crash_bet: Account<'info, CrashBet>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<SettleCrashBetAccounts>) -> Result<()> {
+    let crash_bet = &ctx.accounts.crash_bet;
+
+    require!(
+        crash_bet.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        crash_bet.is_for_round(&ctx.accounts.crash_round.key()),
+        GameError::InvalidCrashRound
+    );
+
+    Ok(())
+}
+
+/// Settles a bet once its round is revealed, or once the reveal window expired, anyone
+/// can call it.
+pub fn settle_crash_bet_handler(ctx: Context<SettleCrashBetAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let won = ctx
+        .accounts
+        .crash_round
+        .is_winning_target(ctx.accounts.crash_bet.cash_out_target, now)
+        .ok_or(GameError::CrashRoundNotRevealed)?;
+
+    let payout = if won {
+        ctx.accounts.crash_bet.get_payout()?
+    } else {
+        0
+    };
+
+    // The deposit is already in the vault, so the whole payout comes from it.
+    **ctx.accounts.vault.try_borrow_mut_lamports()? -= payout;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += payout;
+
+    // The stake entered the vault outside of the ledger, so it is counted here, as swept
+    // when the bet is lost and against the payout when it is won.
+    let deposit = ctx.accounts.crash_bet.deposit;
+
+    if won {
+        ctx.accounts
+            .global_state
+            .record_paid_out(payout.saturating_sub(deposit));
+
+        ctx.accounts.player_profile.record_game_won(payout);
+    } else {
+        ctx.accounts.global_state.record_swept(deposit);
+
+        ctx.accounts.player_profile.record_game_lost();
+    }
+
+    ctx.accounts.global_state.record_session_closed();
+    ctx.accounts.global_state.record_crash_stake_closed(deposit);
+
+    emit!(SettleCrashBetEvent {
+        crash_round: ctx.accounts.crash_round.key(),
+        player: ctx.accounts.player.key(),
+        won,
+        payout
+    });
+
+    Ok(())
+}
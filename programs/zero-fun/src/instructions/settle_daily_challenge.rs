@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    get_payout_for_rounds, get_rounds_survived, DailyChallenge, GameConfig, GameError, GameSession,
+    GlobalState, PlayerProfile, SettleDailyChallengeEvent,
+};
+
+#[derive(Accounts)]
+pub struct SettleDailyChallengeAccounts<'info> {
+    #[account(mut)]
+    pub daily_challenge: Account<'info, DailyChallenge>,
+
+    #[account(
+        mut,
+        close = player
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// CHECK: This is the vault account where the player's deposit is stored.
+    #[account(mut)]
+    pub user_vault: UncheckedAccount<'info>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub game_config: Account<'info, GameConfig>,
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<SettleDailyChallengeAccounts>) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    require!(
+        game_session.is_daily_challenge()
+            && game_session
+                .public_config_seed
+                .eq(&ctx.accounts.daily_challenge.public_config_seed),
+        GameError::InvalidDailyChallenge
+    );
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        game_session.is_vault_for_game(ctx.accounts.user_vault.key),
+        GameError::InvalidVault
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
+    require!(
+        ctx.accounts.daily_challenge.is_revealed(),
+        GameError::InvalidDailyChallengeStatus
+    );
+
+    Ok(())
+}
+
+/// Settles a daily challenge session against the revealed seed and closes it, anyone can
+/// call it. A session that survived every move is paid out as if it cashed out and one
+/// that hit a losing move loses its deposit, both are ranked on the leaderboard. A
+/// session that made a move after the challenge ended could have played the revealed
+/// board, so it is only refunded its deposit and isn't ranked.
+pub fn settle_daily_challenge_handler(ctx: Context<SettleDailyChallengeAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let (rounds_survived, is_lost, deposit, payout) = {
+        let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+        let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+        let daily_challenge = &ctx.accounts.daily_challenge;
+
+        let rounds_survived = get_rounds_survived(
+            &ctx.accounts.game_config.parameters,
+            game_session,
+            player_moves,
+            &daily_challenge.private_config_seed,
+        );
+
+        let deposit = game_session.deposit;
+
+        if game_session.last_action_time >= daily_challenge.get_end_time() {
+            (None, false, deposit, 0)
+        } else if usize::from(rounds_survived) < game_session.get_player_move_count() {
+            (Some(rounds_survived), true, deposit, 0)
+        } else {
+            let current_max_payout = ctx
+                .accounts
+                .global_state
+                .get_max_payout(ctx.accounts.vault.lamports())?;
+
+            let payout = get_payout_for_rounds(
+                &ctx.accounts.game_config.parameters,
+                game_session,
+                player_moves,
+                ctx.accounts.global_state.house_edge,
+                current_max_payout,
+            );
+
+            (Some(rounds_survived), false, deposit, payout)
+        }
+    };
+
+    if let Some(rounds_survived) = rounds_survived {
+        ctx.accounts
+            .daily_challenge
+            .submit_score(ctx.accounts.player.key(), rounds_survived);
+    }
+
+    let user_vault_balance = ctx.accounts.user_vault.lamports();
+
+    **ctx.accounts.user_vault.try_borrow_mut_lamports()? = 0;
+
+    if is_lost {
+        // The deposit(with the vault's rent) goes to the house.
+        **ctx.accounts.vault.try_borrow_mut_lamports()? += user_vault_balance;
+
+        ctx.accounts.global_state.record_swept(user_vault_balance);
+    } else {
+        **ctx.accounts.vault.try_borrow_mut_lamports()? -= payout;
+
+        ctx.accounts.global_state.record_paid_out(payout);
+        **ctx.accounts.player.try_borrow_mut_lamports()? += user_vault_balance
+            .checked_add(payout)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    ctx.accounts.global_state.record_session_closed();
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    if rounds_survived.is_none() {
+        player_profile.record_game_defaulted(deposit);
+    } else if is_lost {
+        player_profile.record_game_lost();
+    } else {
+        player_profile.record_game_won(deposit.saturating_add(payout));
+    }
+
+    emit!(SettleDailyChallengeEvent {
+        daily_challenge: ctx.accounts.daily_challenge.key(),
+        player: ctx.accounts.player.key(),
+        game_session: ctx.accounts.game_session.key(),
+        rounds_survived,
+        is_lost,
+        payout
+    });
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{CrashRound, CrashState, GameError, GlobalState, StartCrashRoundEvent};
+
+#[derive(Accounts)]
+pub struct StartCrashRoundAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"crash-state"],
+        bump = crash_state.bump
+    )]
+    pub crash_state: Account<'info, CrashState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CrashRound::INIT_SPACE,
+        seeds = [b"crash-round", crash_state.next_round_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub crash_round: Account<'info, CrashRound>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<StartCrashRoundAccounts>) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    require!(
+        ctx.accounts.global_state.is_active(),
+        GameError::GameNotActive
+    );
+
+    // Rounds are revealed in the order of the chain, so only one is open at a time.
+    require!(
+        !ctx.accounts.crash_state.has_open_round,
+        GameError::CrashRoundOpen
+    );
+
+    Ok(())
+}
+
+pub fn start_crash_round_handler(ctx: Context<StartCrashRoundAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let crash_state = &mut ctx.accounts.crash_state;
+
+    let round_index = crash_state.next_round_index;
+
+    let betting_deadline = now
+        .checked_add(crash_state.betting_period)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    ctx.accounts.crash_round.set_inner(CrashRound::new(
+        round_index,
+        betting_deadline,
+        ctx.accounts.global_state.house_edge,
+        ctx.bumps.crash_round,
+    ));
+
+    crash_state.next_round_index = round_index
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    crash_state.has_open_round = true;
+
+    emit!(StartCrashRoundEvent {
+        crash_round: ctx.accounts.crash_round.key(),
+        round_index,
+        betting_deadline
+    });
+
+    Ok(())
+}
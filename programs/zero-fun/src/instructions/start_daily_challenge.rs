@@ -0,0 +1,171 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    DailyChallenge, GameConfig, GameError, GameSession, GlobalState, PlayerProfile,
+    StartDailyChallengeEvent,
+};
+
+/// Arguments for starting a daily challenge session.
+/// - deposit: The amount the player stakes on the session.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct StartDailyChallengeArgs {
+    pub deposit: u64,
+}
+
+#[derive(Accounts)]
+pub struct StartDailyChallengeAccounts<'info> {
+    #[account(mut)]
+    pub daily_challenge: Account<'info, DailyChallenge>,
+
+    /// Every player's session is keyed by the challenge instead of the shared public
+    /// config seed, so a player can only start one per challenge.
+    #[account(
+        init,
+        payer = player,
+        space = GameSession::INITIAL_SPACE,
+        seeds = [b"challenge-session".as_ref(), daily_challenge.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// CHECK: This is the vault account where the player's deposit will be stored.
+    #[account(
+        init,
+        space = 0,
+        payer = player,
+        seeds = [b"challenge-vault", daily_challenge.key().as_ref(), player.key.as_ref()],
+        bump
+    )]
+    pub user_vault: UncheckedAccount<'info>,
+
+    /// CHECK: This is the global vault account.
+    #[account(
+        seeds = [b"vault"],
+        bump = global_state.get_vault_bump()
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub global_state: Account<'info, GlobalState>,
+
+    pub game_config: Account<'info, GameConfig>,
+
+    pub system_program: Program<'info, System>,
+    /// The player's lifetime statistics, created with their first session.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[inline(always)]
+fn checks(
+    ctx: &Context<StartDailyChallengeAccounts>,
+    args: &StartDailyChallengeArgs,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // Verify the deposit is within the allowed deposit limits.
+    ctx.accounts
+        .global_state
+        .check_deposit(args.deposit, ctx.accounts.vault.lamports())?;
+
+    require!(
+        ctx.accounts.global_state.is_active(),
+        GameError::GameNotActive
+    );
+
+    require!(
+        ctx.accounts.daily_challenge.is_running(now),
+        GameError::DailyChallengeNotOpen
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(ctx.accounts.daily_challenge.variant.into()),
+        GameError::InvalidGameConfig
+    );
+
+    ctx.accounts
+        .player_profile
+        .check_limits(args.deposit, now)?;
+
+    Ok(())
+}
+
+pub fn start_daily_challenge_handler(
+    ctx: Context<StartDailyChallengeAccounts>,
+    args: StartDailyChallengeArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let daily_challenge = &mut ctx.accounts.daily_challenge;
+
+    {
+        let game_session = &mut ctx.accounts.game_session.load_init()?;
+
+        **game_session = GameSession::new(
+            ctx.accounts.player.key(),
+            args.deposit,
+            *ctx.accounts.user_vault.key,
+            daily_challenge.public_config_seed,
+            &[],
+            now,
+        );
+
+        game_session.set_variant(daily_challenge.variant);
+        game_session.set_default_windows(
+            ctx.accounts.global_state.default_offset,
+            ctx.accounts.global_state.max_session_lifetime,
+        );
+        game_session.enter_daily_challenge();
+    }
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: ctx.accounts.user_vault.to_account_info(),
+            },
+        ),
+        args.deposit,
+    )?;
+
+    daily_challenge.session_count = daily_challenge
+        .session_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let player_profile = &mut ctx.accounts.player_profile;
+
+    player_profile.initialize_if_needed(ctx.accounts.player.key(), ctx.bumps.player_profile);
+    player_profile.apply_pending_limits(now);
+    player_profile.record_game_started(args.deposit, now);
+
+    ctx.accounts
+        .global_state
+        .record_session_opened(args.deposit);
+
+    emit!(StartDailyChallengeEvent {
+        daily_challenge: daily_challenge.key(),
+        player: ctx.accounts.player.key(),
+        game_session: ctx.accounts.game_session.key(),
+        deposit: args.deposit
+    });
+
+    Ok(())
+}
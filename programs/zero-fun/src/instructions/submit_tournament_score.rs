@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    get_rounds_survived, GameConfig, GameError, GameSession, PlayerProfile,
+    SubmitTournamentScoreEvent, Tournament,
+};
+
+#[derive(Accounts)]
+pub struct SubmitTournamentScoreAccounts<'info> {
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        close = player
+    )]
+    pub game_session: AccountLoader<'info, GameSession>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    pub game_config: Account<'info, GameConfig>,
+
+    /// The player's lifetime statistics.
+    #[account(
+        mut,
+        seeds = [b"player-profile", player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<SubmitTournamentScoreAccounts>) -> Result<()> {
+    let game_session = ctx.accounts.game_session.load()?;
+
+    require!(
+        game_session.is_tournament()
+            && game_session.is_vault_for_game(&ctx.accounts.tournament.key()),
+        GameError::InvalidTournament
+    );
+
+    require!(
+        game_session.is_owned_by_player(ctx.accounts.player.key),
+        GameError::InvalidPlayer
+    );
+
+    require!(
+        ctx.accounts
+            .game_config
+            .is_for_variant(game_session.variant),
+        GameError::InvalidGameConfig
+    );
+
+    require!(
+        !ctx.accounts.tournament.is_open(),
+        GameError::InvalidTournamentStatus
+    );
+
+    Ok(())
+}
+
+/// Scores a tournament session against the revealed seed and closes it, anyone can call
+/// it. Sessions that made a move after the tournament ended, or that are submitted after
+/// the scoring window, are closed without being scored.
+pub fn submit_tournament_score_handler(ctx: Context<SubmitTournamentScoreAccounts>) -> Result<()> {
+    checks(&ctx)?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let rounds_survived = {
+        let game_session_data = ctx.accounts.game_session.as_ref().try_borrow_data()?;
+        let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+        let tournament = &ctx.accounts.tournament;
+
+        (tournament.is_revealed()
+            && !tournament.is_scoring_window_expired(now)
+            && game_session.last_action_time <= tournament.end_time)
+            .then(|| {
+                get_rounds_survived(
+                    &ctx.accounts.game_config.parameters,
+                    game_session,
+                    player_moves,
+                    &tournament.private_config_seed,
+                )
+            })
+    };
+
+    if let Some(rounds_survived) = rounds_survived {
+        ctx.accounts
+            .tournament
+            .submit_score(ctx.accounts.player.key(), rounds_survived);
+    }
+
+    // The prizes are only known once the scoring window expired.
+    ctx.accounts.player_profile.record_game_ended();
+
+    emit!(SubmitTournamentScoreEvent {
+        tournament: ctx.accounts.tournament.key(),
+        player: ctx.accounts.player.key(),
+        game_session: ctx.accounts.game_session.key(),
+        rounds_survived
+    });
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameConfig, GameError, GameState, GlobalState, UpdateGameConfigEvent};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct UpdateGameConfigArgs {
+    pub game_state: GameState,
+}
+
+#[derive(Accounts)]
+#[instruction(args: UpdateGameConfigArgs)]
+pub struct UpdateGameConfigAccounts<'info> {
+    #[account(mut)]
+    pub game_config: Account<'info, GameConfig>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<UpdateGameConfigAccounts>) -> Result<()> {
+    // Only the current admin can update a variant.
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    Ok(())
+}
+
+pub fn update_game_config_handler(
+    ctx: Context<UpdateGameConfigAccounts>,
+    args: UpdateGameConfigArgs,
+) -> Result<()> {
+    checks(&ctx)?;
+
+    let game_config = &mut ctx.accounts.game_config;
+
+    game_config.game_state = args.game_state;
+
+    emit!(UpdateGameConfigEvent {
+        admin_at_time_of_update: ctx.accounts.admin.key(),
+        variant: game_config.get_variant(),
+        game_state: args.game_state
+    });
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{GameError, GlobalState, GlobalStateUpdate, UpdateGlobalStateEvent};
+use crate::{
+    GameError, GameState, GlobalState, GlobalStateUpdate, UpdateGlobalStateEvent, MAX_BPS,
+    MAX_PROTOCOL_FEE,
+};
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct UpdateGlobalStateArgs {
@@ -42,14 +45,97 @@ pub fn update_global_state_handler(
             global_state.message_signer = new_signer;
         }
         GlobalStateUpdate::MaxDeposit(new_max_deposit) => {
+            require_gte!(
+                MAX_BPS,
+                u64::from(new_max_deposit),
+                GameError::InvalidLimits
+            );
+
             global_state.max_deposit = new_max_deposit;
         }
         GlobalStateUpdate::MaxPayout(new_max_payout) => {
+            require_gte!(MAX_BPS, u64::from(new_max_payout), GameError::InvalidLimits);
+
             global_state.max_payout = new_max_payout;
         }
         GlobalStateUpdate::GameState(new_game_state) => {
+            // Lifting the restriction acknowledges the circuit breaker, it is armed again
+            // with a clean window.
+            if global_state.is_restricted() {
+                global_state.reset_payout_window();
+            }
+
+            // The settlement window runs from when the game first started winding down.
+            if new_game_state == GameState::WindDown && !global_state.is_winding_down() {
+                global_state.wind_down_start = Clock::get()?.unix_timestamp;
+            }
+
             global_state.game_state = new_game_state;
         }
+        GlobalStateUpdate::HouseEdge(new_house_edge) => {
+            require_gte!(
+                MAX_BPS,
+                u64::from(new_house_edge),
+                GameError::InvalidHouseEdge
+            );
+
+            global_state.house_edge = new_house_edge;
+        }
+        GlobalStateUpdate::ReferralFee(new_referral_fee) => {
+            require_gte!(
+                MAX_BPS,
+                u64::from(new_referral_fee),
+                GameError::InvalidReferralFee
+            );
+
+            global_state.referral_fee = new_referral_fee;
+        }
+        GlobalStateUpdate::MaxReferralReward(new_max_referral_reward) => {
+            global_state.max_referral_reward = new_max_referral_reward;
+        }
+        GlobalStateUpdate::ProtocolFee(new_protocol_fee) => {
+            require_gte!(
+                MAX_PROTOCOL_FEE,
+                new_protocol_fee,
+                GameError::InvalidProtocolFee
+            );
+
+            global_state.protocol_fee = new_protocol_fee;
+        }
+        GlobalStateUpdate::MinReserve(new_min_reserve) => {
+            global_state.min_reserve = new_min_reserve;
+        }
+        GlobalStateUpdate::DailyWithdrawLimit(new_daily_withdraw_limit) => {
+            global_state.daily_withdraw_limit = new_daily_withdraw_limit;
+        }
+        GlobalStateUpdate::PayoutBreakerThreshold(new_payout_breaker_threshold) => {
+            global_state.payout_breaker_threshold = new_payout_breaker_threshold;
+        }
+        GlobalStateUpdate::MinDeposit(new_min_deposit) => {
+            global_state.min_deposit = new_min_deposit;
+        }
+        GlobalStateUpdate::MaxDepositLamports(new_max_deposit_lamports) => {
+            global_state.max_deposit_lamports = new_max_deposit_lamports;
+        }
+        GlobalStateUpdate::MaxPayoutLamports(new_max_payout_lamports) => {
+            global_state.max_payout_lamports = new_max_payout_lamports;
+        }
+        GlobalStateUpdate::DefaultOffset(new_default_offset) => {
+            GlobalState::check_default_windows(
+                new_default_offset,
+                global_state.max_session_lifetime,
+            )?;
+
+            global_state.default_offset = new_default_offset;
+        }
+        GlobalStateUpdate::MaxSessionLifetime(new_max_session_lifetime) => {
+            GlobalState::check_default_windows(
+                global_state.default_offset,
+                new_max_session_lifetime,
+            )?;
+
+            global_state.max_session_lifetime = new_max_session_lifetime;
+        }
     }
 
     emit!(UpdateGlobalStateEvent {
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, GlobalState, Jackpot, UpdateJackpotEvent};
+
+/// Arguments for updating the jackpot, the pool itself is left as is.
+/// - contribution: The share of each deposit added to the pool (in bps).
+/// - odds: A won session hits the jackpot with odds of `1 / odds`.
+/// - move_threshold: A won session that survived this many moves always hits the jackpot,
+///   0 is no threshold.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct UpdateJackpotArgs {
+    pub contribution: u16,
+    pub odds: u32,
+    pub move_threshold: u16,
+}
+
+#[derive(Accounts)]
+pub struct UpdateJackpotAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    pub global_state: Account<'info, GlobalState>,
+
+    pub admin: Signer<'info>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<UpdateJackpotAccounts>, args: &UpdateJackpotArgs) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    require!(
+        Jackpot::are_parameters_valid(args.contribution, args.odds),
+        GameError::InvalidJackpotParameters
+    );
+
+    Ok(())
+}
+
+pub fn update_jackpot_handler(
+    ctx: Context<UpdateJackpotAccounts>,
+    args: UpdateJackpotArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    let jackpot = &mut ctx.accounts.jackpot;
+
+    jackpot.contribution = args.contribution;
+    jackpot.odds = args.odds;
+    jackpot.move_threshold = args.move_threshold;
+
+    emit!(UpdateJackpotEvent {
+        admin_at_time_of_update: ctx.accounts.admin.key(),
+        contribution: args.contribution,
+        odds: args.odds,
+        move_threshold: args.move_threshold
+    });
+
+    Ok(())
+}
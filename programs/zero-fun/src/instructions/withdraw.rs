@@ -1,4 +1,4 @@
-use crate::{GlobalState, WithdrawEvent};
+use crate::{GameError, GlobalState, WithdrawEvent};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
@@ -8,6 +8,7 @@ pub struct WithdrawArgs {
 
 #[derive(Accounts)]
 pub struct WithdrawAccounts<'info> {
+    #[account(mut)]
     pub global_state: Account<'info, GlobalState>,
 
     #[account(
@@ -28,17 +29,35 @@ pub struct WithdrawAccounts<'info> {
 }
 
 #[inline(always)]
-fn checks(ctx: &Context<WithdrawAccounts>) -> Result<()> {
+fn checks(ctx: &Context<WithdrawAccounts>, args: &WithdrawArgs) -> Result<()> {
     require!(
         ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
-        crate::GameError::InvalidAdmin
+        GameError::InvalidAdmin
+    );
+
+    // The vault must stay rent exempt and keep the minimum reserve to back sessions.
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(ctx.accounts.vault.data_len());
+
+    let free_liquidity = ctx
+        .accounts
+        .global_state
+        .get_free_liquidity(ctx.accounts.vault.lamports(), rent_exempt_minimum);
+
+    require_gte!(
+        free_liquidity,
+        args.amount,
+        GameError::InsufficientFreeLiquidity
     );
 
     Ok(())
 }
 
 pub fn withdraw_handler(ctx: Context<WithdrawAccounts>, args: WithdrawArgs) -> Result<()> {
-    checks(&ctx)?;
+    checks(&ctx, &args)?;
+
+    ctx.accounts
+        .global_state
+        .record_withdrawal(args.amount, Clock::get()?.unix_timestamp)?;
 
     **ctx
         .accounts
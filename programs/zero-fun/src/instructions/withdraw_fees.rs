@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{FeeVault, GameError, GlobalState, WithdrawFeesEvent};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct WithdrawFeesArgs {
+    pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFeesAccounts<'info> {
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee-vault"],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    /// CHECK: Recipient account to receive the withdrawn fees
+    pub recipient: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[inline(always)]
+fn checks(ctx: &Context<WithdrawFeesAccounts>, args: &WithdrawFeesArgs) -> Result<()> {
+    require!(
+        ctx.accounts.global_state.is_admin(ctx.accounts.admin.key),
+        GameError::InvalidAdmin
+    );
+
+    // Only the fees can be taken, the account's rent stays with it.
+    require_gte!(
+        ctx.accounts.fee_vault.balance,
+        args.amount,
+        GameError::InsufficientFees
+    );
+
+    Ok(())
+}
+
+pub fn withdraw_fees_handler(
+    ctx: Context<WithdrawFeesAccounts>,
+    args: WithdrawFeesArgs,
+) -> Result<()> {
+    checks(&ctx, &args)?;
+
+    **ctx
+        .accounts
+        .fee_vault
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= args.amount;
+    **ctx
+        .accounts
+        .recipient
+        .to_account_info()
+        .try_borrow_mut_lamports()? += args.amount;
+
+    let fee_vault = &mut ctx.accounts.fee_vault;
+
+    fee_vault.balance -= args.amount;
+    fee_vault.total_withdrawn += args.amount;
+
+    emit!(WithdrawFeesEvent {
+        admin: ctx.accounts.admin.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount: args.amount
+    });
+
+    Ok(())
+}
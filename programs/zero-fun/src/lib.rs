@@ -11,6 +11,9 @@ pub use state::*;
 pub mod utils;
 pub use utils::*;
 
+pub mod rules;
+pub use rules::*;
+
 #[program]
 pub mod game_i {
     use super::*;
@@ -32,6 +35,56 @@ pub mod game_i {
         update_global_state_handler(ctx, args)
     }
 
+    /// Moves the global state off its legacy layout, with the deposit and payout limits
+    /// widened (admin only).
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalStateAccounts>) -> Result<()> {
+        migrate_global_state_handler(ctx)
+    }
+
+    /// Refunds the deposit of a session left in the layout from before sessions were
+    /// zero-copy and closes it (admin only).
+    pub fn refund_legacy_game_session(ctx: Context<RefundLegacyGameSessionAccounts>) -> Result<()> {
+        refund_legacy_game_session_handler(ctx)
+    }
+
+    /// Closes the global state and the vault once the game has wound down and no sessions,
+    /// crash bets or open crash rounds remain (admin only).
+    pub fn close_global_state(ctx: Context<CloseGlobalStateAccounts>) -> Result<()> {
+        close_global_state_handler(ctx)
+    }
+
+    /// Registers a game variant with its parameters (admin only).
+    pub fn initialize_game_config(
+        ctx: Context<InitializeGameConfigAccounts>,
+        args: InitializeGameConfigArgs,
+    ) -> Result<()> {
+        initialize_game_config_handler(ctx, args)
+    }
+
+    /// Updates the state of a game variant (admin only).
+    pub fn update_game_config(
+        ctx: Context<UpdateGameConfigAccounts>,
+        args: UpdateGameConfigArgs,
+    ) -> Result<()> {
+        update_game_config_handler(ctx, args)
+    }
+
+    /// Creates the progressive jackpot with its contribution and odds (admin only).
+    pub fn initialize_jackpot(
+        ctx: Context<InitializeJackpotAccounts>,
+        args: InitializeJackpotArgs,
+    ) -> Result<()> {
+        initialize_jackpot_handler(ctx, args)
+    }
+
+    /// Updates the contribution and odds of the jackpot (admin only).
+    pub fn update_jackpot(
+        ctx: Context<UpdateJackpotAccounts>,
+        args: UpdateJackpotArgs,
+    ) -> Result<()> {
+        update_jackpot_handler(ctx, args)
+    }
+
     /// Records a player action (move) on-chain during an active game session.
     pub fn record_action(ctx: Context<RecordActionAccounts>, args: RecordActionArgs) -> Result<()> {
         record_action_handler(ctx, args)
@@ -45,6 +98,43 @@ pub mod game_i {
         initialize_game_handler(ctx, args)
     }
 
+    /// Marks the outcome of a practice session against its revealed seed and closes it.
+    pub fn reveal_practice_game(
+        ctx: Context<RevealPracticeGameAccounts>,
+        args: RevealPracticeGameArgs,
+    ) -> Result<()> {
+        reveal_practice_game_handler(ctx, args)
+    }
+
+    /// Creates a player's profile, for players whose sessions predate profiles.
+    pub fn initialize_player_profile(ctx: Context<InitializePlayerProfileAccounts>) -> Result<()> {
+        initialize_player_profile_handler(ctx)
+    }
+
+    /// Sets a player's deposit and session limits, loosening a limit only takes effect
+    /// after a cooling-off period.
+    pub fn set_player_limits(
+        ctx: Context<SetPlayerLimitsAccounts>,
+        args: SetPlayerLimitsArgs,
+    ) -> Result<()> {
+        set_player_limits_handler(ctx, args)
+    }
+
+    /// Excludes a player from starting sessions for a period.
+    pub fn self_exclude(ctx: Context<SelfExcludeAccounts>, args: SelfExcludeArgs) -> Result<()> {
+        self_exclude_handler(ctx, args)
+    }
+
+    /// Binds a player to the referrer who brought them in, it can only be done once.
+    pub fn set_referrer(ctx: Context<SetReferrerAccounts>) -> Result<()> {
+        set_referrer_handler(ctx)
+    }
+
+    /// Pays a referrer the rewards accrued from their referred players' sessions.
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewardsAccounts>) -> Result<()> {
+        claim_referral_rewards_handler(ctx)
+    }
+
     /// Allows a player to default (cancel) their game if the session has expired.
     /// Used as a fallback to reclaim deposits in stalled games.
     pub fn default_game(ctx: Context<DefaultGameAccounts>) -> Result<()> {
@@ -78,14 +168,179 @@ pub mod game_i {
         finalize_game_as_lost_handler(ctx, args)
     }
 
+    /// Reveals the next link of a hash chain session's reverse hash chain, settling the
+    /// outcome of the round it belongs to and finalizing the game as lost if it failed.
+    pub fn reveal_round(ctx: Context<RevealRoundAccounts>, args: RevealRoundArgs) -> Result<()> {
+        reveal_round_handler(ctx, args)
+    }
+
+    /// Reveals the moves an auto-play session committed to, recording all of them and
+    /// cashing the session out in one instruction.
+    pub fn reveal_auto_play(
+        ctx: Context<RevealAutoPlayAccounts>,
+        args: RevealAutoPlayArgs,
+    ) -> Result<()> {
+        reveal_auto_play_handler(ctx, args)
+    }
+
+    /// Cashes out a player's game session, freezing its moves and starting the window the
+    /// house has to prove one of them was the fail move.
+    pub fn cash_out(ctx: Context<CashOutAccounts>) -> Result<()> {
+        cash_out_handler(ctx)
+    }
+
+    /// Settles a cashed out game session, paying the player the payout computed on-chain
+    /// for the rounds they survived. Anyone can settle once the reveal window expires,
+    /// or earlier by revealing the private config seed.
+    pub fn settle_cash_out(
+        ctx: Context<SettleCashOutAccounts>,
+        args: SettleCashOutArgs,
+    ) -> Result<()> {
+        settle_cash_out_handler(ctx, args)
+    }
+
+    /// Creates a tournament on a committed public config seed (admin only).
+    pub fn create_tournament(
+        ctx: Context<CreateTournamentAccounts>,
+        args: CreateTournamentArgs,
+    ) -> Result<()> {
+        create_tournament_handler(ctx, args)
+    }
+
+    /// Enters a tournament, paying the entry fee and creating the player's session on the
+    /// tournament's seed.
+    pub fn enter_tournament(ctx: Context<EnterTournamentAccounts>) -> Result<()> {
+        enter_tournament_handler(ctx)
+    }
+
+    /// Reveals a tournament's private config seed once it has ended.
+    pub fn reveal_tournament(
+        ctx: Context<RevealTournamentAccounts>,
+        args: RevealTournamentArgs,
+    ) -> Result<()> {
+        reveal_tournament_handler(ctx, args)
+    }
+
+    /// Scores a tournament session by the rounds it survived, placing it on the
+    /// leaderboard if it is good enough.
+    pub fn submit_tournament_score(ctx: Context<SubmitTournamentScoreAccounts>) -> Result<()> {
+        submit_tournament_score_handler(ctx)
+    }
+
+    /// Pays the top places of a tournament from its prize pool once the scoring window
+    /// has ended.
+    pub fn distribute_tournament_prizes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeTournamentPrizesAccounts<'info>>,
+    ) -> Result<()> {
+        distribute_tournament_prizes_handler(ctx)
+    }
+
+    /// Pays an entrant their entry fee back if the tournament's seed wasn't revealed in time.
+    pub fn refund_tournament_entry(ctx: Context<RefundTournamentEntryAccounts>) -> Result<()> {
+        refund_tournament_entry_handler(ctx)
+    }
+
+    /// Opens a daily challenge on a public config seed shared by every player (admin only).
+    pub fn open_daily_challenge(
+        ctx: Context<OpenDailyChallengeAccounts>,
+        args: OpenDailyChallengeArgs,
+    ) -> Result<()> {
+        open_daily_challenge_handler(ctx, args)
+    }
+
+    /// Starts a player's session on the daily challenge with a deposit.
+    pub fn start_daily_challenge(
+        ctx: Context<StartDailyChallengeAccounts>,
+        args: StartDailyChallengeArgs,
+    ) -> Result<()> {
+        start_daily_challenge_handler(ctx, args)
+    }
+
+    /// Reveals the daily challenge's private config seed once the day ends.
+    pub fn reveal_daily_challenge(
+        ctx: Context<RevealDailyChallengeAccounts>,
+        args: RevealDailyChallengeArgs,
+    ) -> Result<()> {
+        reveal_daily_challenge_handler(ctx, args)
+    }
+
+    /// Settles a daily challenge session against the revealed seed and ranks it.
+    pub fn settle_daily_challenge(ctx: Context<SettleDailyChallengeAccounts>) -> Result<()> {
+        settle_daily_challenge_handler(ctx)
+    }
+
+    /// Refunds a daily challenge session its deposit if the challenge's seed wasn't revealed
+    /// in time.
+    pub fn refund_daily_challenge_session(
+        ctx: Context<RefundDailyChallengeSessionAccounts>,
+    ) -> Result<()> {
+        refund_daily_challenge_session_handler(ctx)
+    }
+
+    /// Initializes the crash game, committing to the head of its reverse hash chain (admin only).
+    pub fn initialize_crash(
+        ctx: Context<InitializeCrashAccounts>,
+        args: InitializeCrashArgs,
+    ) -> Result<()> {
+        initialize_crash_handler(ctx, args)
+    }
+
+    /// Starts the next crash round, opening it for bets (admin only).
+    pub fn start_crash_round(ctx: Context<StartCrashRoundAccounts>) -> Result<()> {
+        start_crash_round_handler(ctx)
+    }
+
+    /// Joins a crash round with a deposit and a cash-out target.
+    pub fn join_crash_round(
+        ctx: Context<JoinCrashRoundAccounts>,
+        args: JoinCrashRoundArgs,
+    ) -> Result<()> {
+        join_crash_round_handler(ctx, args)
+    }
+
+    /// Cashes a crash bet out at the current multiplier while the round is running.
+    pub fn cash_out_crash_bet(ctx: Context<CashOutCrashBetAccounts>) -> Result<()> {
+        cash_out_crash_bet_handler(ctx)
+    }
+
+    /// Reveals the next link of the crash game's hash chain, fixing the crash point of
+    /// the open round.
+    pub fn reveal_crash_round(
+        ctx: Context<RevealCrashRoundAccounts>,
+        args: RevealCrashRoundArgs,
+    ) -> Result<()> {
+        reveal_crash_round_handler(ctx, args)
+    }
+
+    /// Settles a crash bet against the crash point of its round, paying it out from the
+    /// global vault if it won.
+    pub fn settle_crash_bet(ctx: Context<SettleCrashBetAccounts>) -> Result<()> {
+        settle_crash_bet_handler(ctx)
+    }
+
     /// Allows a player to mark the game as won if the admin is unresponsive.
     /// This broadcasts the player's claim on-chain, requiring the admin to later settle it.
     pub fn mark_game_as_won(ctx: Context<MarkGameAsWonAccounts>) -> Result<()> {
         mark_game_as_won_handler(ctx)
     }
 
-    /// Withdraws funds (admin-only). Used to withdraw accumulated fees from the global vault.
+    /// Withdraws funds (admin-only) from the bankroll in the global vault.
     pub fn withdraw(ctx: Context<WithdrawAccounts>, args: WithdrawArgs) -> Result<()> {
         withdraw_handler(ctx, args)
     }
+
+    /// Checks the global vault's balance against the bankroll ledger (anyone can call).
+    pub fn reconcile_bankroll(ctx: Context<ReconcileBankrollAccounts>) -> Result<()> {
+        reconcile_bankroll_handler(ctx)
+    }
+
+    /// Creates the fee vault the protocol fees are collected in (admin only).
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVaultAccounts>) -> Result<()> {
+        initialize_fee_vault_handler(ctx)
+    }
+
+    /// Withdraws collected protocol fees from the fee vault (admin only).
+    pub fn withdraw_fees(ctx: Context<WithdrawFeesAccounts>, args: WithdrawFeesArgs) -> Result<()> {
+        withdraw_fees_handler(ctx, args)
+    }
 }
@@ -0,0 +1,146 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{GameError, GameRules, GameSession, RoundConfig, HASH_LENGTH, MAX_DICE_ROLL_COUNT};
+
+/// The bit of a Dice move set when the player bets the roll lands over the target.
+const DICE_OVER_FLAG: u8 = 1 << 7;
+
+/// A single roll in `[0 .. roll_count)`, the player bets it lands over or under a
+/// target. The move holds the target in its low bits and sets `DICE_OVER_FLAG` for
+/// an over bet, the session is settled as soon as it is recorded.
+/// - roll_count: The number of outcomes of the roll.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DiceRules {
+    pub roll_count: u8,
+}
+
+impl DiceRules {
+    /// Encodes a bet as a move.
+    pub fn encode_move(target: u8, over: bool) -> u8 {
+        if over {
+            target | DICE_OVER_FLAG
+        } else {
+            target
+        }
+    }
+
+    /// Decodes a move into its target and whether it is an over bet.
+    pub fn decode_move(player_move: u8) -> (u8, bool) {
+        (
+            player_move & !DICE_OVER_FLAG,
+            player_move & DICE_OVER_FLAG != 0,
+        )
+    }
+
+    /// The roll of a session, `LE32( HASH(HRS + client_seed) ) % roll_count`.
+    pub fn get_roll(
+        &self,
+        private_config_seed: &[u8; HASH_LENGTH],
+        client_seed: &[u8; HASH_LENGTH],
+    ) -> u8 {
+        let roll_seed = hashv(&[private_config_seed.as_ref(), client_seed.as_ref()]).to_bytes();
+
+        let roll = u32::from_le_bytes([roll_seed[0], roll_seed[1], roll_seed[2], roll_seed[3]])
+            % u32::from(self.roll_count);
+
+        roll as u8
+    }
+
+    /// The number of rolls a bet wins on.
+    pub fn get_winning_roll_count(&self, player_move: u8) -> u8 {
+        let (target, over) = Self::decode_move(player_move);
+
+        if over {
+            // Rolls in `(target .. roll_count)`
+            self.roll_count.saturating_sub(target).saturating_sub(1)
+        } else {
+            // Rolls in `[0 .. target)`
+            target.min(self.roll_count)
+        }
+    }
+}
+
+impl GameRules for DiceRules {
+    fn validate_parameters(&self) -> Result<()> {
+        require!(
+            self.roll_count >= 2 && usize::from(self.roll_count) <= MAX_DICE_ROLL_COUNT,
+            GameError::InvalidGameParameters
+        );
+
+        Ok(())
+    }
+
+    // There is a single round, so there is nothing to reveal per round.
+    fn supports_hash_chain(&self) -> bool {
+        false
+    }
+
+    fn get_round_config(&self, _game_session: &GameSession, _round: u16) -> RoundConfig {
+        RoundConfig {
+            move_type_count: self.roll_count,
+        }
+    }
+
+    fn validate_move(
+        &self,
+        game_session: &GameSession,
+        _player_moves: &[u8],
+        player_move: u8,
+    ) -> Result<()> {
+        require!(
+            !self.is_complete(game_session.get_player_move_count()),
+            GameError::MaxMoveReached
+        );
+
+        let (target, _) = Self::decode_move(player_move);
+
+        // The bet must be able to both win and lose.
+        require!(
+            target < self.roll_count
+                && self.get_winning_roll_count(player_move) > 0
+                && self.get_winning_roll_count(player_move) < self.roll_count,
+            GameError::InvalidMove
+        );
+
+        Ok(())
+    }
+
+    fn is_losing_move(
+        &self,
+        game_session: &GameSession,
+        secret_seed: &[u8; HASH_LENGTH],
+        _round: u16,
+        player_move: u8,
+    ) -> bool {
+        let roll = self.get_roll(secret_seed, &game_session.client_seed);
+
+        let (target, over) = Self::decode_move(player_move);
+
+        if over {
+            roll <= target
+        } else {
+            roll >= target
+        }
+    }
+
+    fn apply_round_multiplier(
+        &self,
+        _game_session: &GameSession,
+        _round: u16,
+        player_move: u8,
+        amount: u128,
+    ) -> u128 {
+        // Winning has fair odds of `winning_roll_count / roll_count`.
+        let winning_roll_count = u128::from(self.get_winning_roll_count(player_move));
+
+        if winning_roll_count == 0 {
+            return amount;
+        }
+
+        amount * u128::from(self.roll_count) / winning_roll_count
+    }
+
+    fn is_complete(&self, move_count: usize) -> bool {
+        move_count >= 1
+    }
+}
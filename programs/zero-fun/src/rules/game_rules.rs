@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+
+use crate::{Difficulty, GameParameters, GameSession, HASH_LENGTH};
+
+/// The configuration of a single round, derived from the session's public config seed.
+/// - move_type_count: The number of moves the player can pick from in the round.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RoundConfig {
+    pub move_type_count: u8,
+}
+
+/// The rules of a game variant. The settlement, vault and signature logic is shared
+/// between variants, the instructions only go through these to decide what moves are
+/// allowed, which of them lose and what surviving pays.
+pub trait GameRules {
+    /// Checks the parameters are valid for the variant, it is done once when its
+    /// `GameConfig` is created.
+    fn validate_parameters(&self) -> Result<()>;
+
+    /// Checks if the variant can be played with `FairnessMode::HashChain`, where a
+    /// different secret is revealed for each round.
+    fn supports_hash_chain(&self) -> bool {
+        true
+    }
+
+    /// Checks if the variant can be played at `difficulty`.
+    fn supports_difficulty(&self, difficulty: Difficulty) -> bool {
+        difficulty == Difficulty::Normal
+    }
+
+    /// Derives the configuration of `round` from the session's public config seed and
+    /// difficulty.
+    fn get_round_config(&self, game_session: &GameSession, round: u16) -> RoundConfig;
+
+    /// Checks `player_move` can be recorded as the session's next move.
+    fn validate_move(
+        &self,
+        game_session: &GameSession,
+        player_moves: &[u8],
+        player_move: u8,
+    ) -> Result<()>;
+
+    /// Checks if `player_move` loses `round`. `secret_seed` is the revealed private
+    /// config seed, or the link revealed for the round in hash chain sessions.
+    fn is_losing_move(
+        &self,
+        game_session: &GameSession,
+        secret_seed: &[u8; HASH_LENGTH],
+        round: u16,
+        player_move: u8,
+    ) -> bool;
+
+    /// Multiplies `amount` by the fair odds of surviving `round` with `player_move`.
+    fn apply_round_multiplier(
+        &self,
+        game_session: &GameSession,
+        round: u16,
+        player_move: u8,
+        amount: u128,
+    ) -> u128;
+
+    /// Checks if a session with `move_count` moves has nothing left to play, it is then
+    /// cashed out as soon as the last move is recorded.
+    fn is_complete(&self, _move_count: usize) -> bool {
+        false
+    }
+}
+
+impl GameParameters {
+    /// The rules of the variant the parameters are for.
+    pub fn get_rules(&self) -> &dyn GameRules {
+        match self {
+            GameParameters::Tiles(rules) => rules,
+            GameParameters::Mines(rules) => rules,
+            GameParameters::Dice(rules) => rules,
+        }
+    }
+}
+
+impl GameRules for GameParameters {
+    fn validate_parameters(&self) -> Result<()> {
+        self.get_rules().validate_parameters()
+    }
+
+    fn supports_hash_chain(&self) -> bool {
+        self.get_rules().supports_hash_chain()
+    }
+
+    fn supports_difficulty(&self, difficulty: Difficulty) -> bool {
+        self.get_rules().supports_difficulty(difficulty)
+    }
+
+    fn get_round_config(&self, game_session: &GameSession, round: u16) -> RoundConfig {
+        self.get_rules().get_round_config(game_session, round)
+    }
+
+    fn validate_move(
+        &self,
+        game_session: &GameSession,
+        player_moves: &[u8],
+        player_move: u8,
+    ) -> Result<()> {
+        self.get_rules()
+            .validate_move(game_session, player_moves, player_move)
+    }
+
+    fn is_losing_move(
+        &self,
+        game_session: &GameSession,
+        secret_seed: &[u8; HASH_LENGTH],
+        round: u16,
+        player_move: u8,
+    ) -> bool {
+        self.get_rules()
+            .is_losing_move(game_session, secret_seed, round, player_move)
+    }
+
+    fn apply_round_multiplier(
+        &self,
+        game_session: &GameSession,
+        round: u16,
+        player_move: u8,
+        amount: u128,
+    ) -> u128 {
+        self.get_rules()
+            .apply_round_multiplier(game_session, round, player_move, amount)
+    }
+
+    fn is_complete(&self, move_count: usize) -> bool {
+        self.get_rules().is_complete(move_count)
+    }
+}
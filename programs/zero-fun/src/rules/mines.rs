@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    get_round_hash, GameError, GameRules, GameSession, RoundConfig, HASH_LENGTH,
+    MAX_MINES_TILE_COUNT,
+};
+
+/// A Mines grid, `mine_count` of the `tile_count` tiles are mines and the player
+/// reveals tiles one at a time until they cash out or hit a mine. Each move is the
+/// index of the tile revealed, a tile can only be revealed once.
+/// - tile_count: The number of tiles in the grid.
+/// - mine_count: The number of mines in the grid.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MinesRules {
+    pub tile_count: u8,
+    pub mine_count: u8,
+}
+
+impl MinesRules {
+    /// The mine positions as a bit mask, tile `i` is a mine if bit `i` is set.
+    /// They are the first `mine_count` tiles of a Fisher-Yates shuffle of the grid,
+    /// step `i` swaps tile `i` with tile `i + (LE32(HASH([i] + seed)) % (tile_count - i))`.
+    pub fn get_mine_positions(&self, private_config_seed: &[u8; HASH_LENGTH]) -> u32 {
+        let mut tiles: [u8; MAX_MINES_TILE_COUNT] = core::array::from_fn(|tile| tile as u8);
+
+        let mut mine_positions = 0u32;
+
+        for step in 0..self.mine_count {
+            let seed_for_step = get_round_hash(private_config_seed, u16::from(step));
+
+            let remaining_tile_count = u32::from(self.tile_count - step);
+
+            let offset = u32::from_le_bytes([
+                seed_for_step[0],
+                seed_for_step[1],
+                seed_for_step[2],
+                seed_for_step[3],
+            ]) % remaining_tile_count;
+
+            let swap_index = usize::from(step) + offset as usize;
+
+            tiles.swap(usize::from(step), swap_index);
+
+            mine_positions |= 1 << tiles[usize::from(step)];
+        }
+
+        mine_positions
+    }
+
+    /// The number of tiles the player can reveal before only mines are left.
+    pub fn get_safe_tile_count(&self) -> u8 {
+        self.tile_count - self.mine_count
+    }
+}
+
+impl GameRules for MinesRules {
+    fn validate_parameters(&self) -> Result<()> {
+        require!(
+            usize::from(self.tile_count) <= MAX_MINES_TILE_COUNT
+                && self.mine_count > 0
+                && self.mine_count < self.tile_count,
+            GameError::InvalidGameParameters
+        );
+
+        Ok(())
+    }
+
+    // The mines are laid out once for the whole grid, so a secret per round doesn't
+    // apply.
+    fn supports_hash_chain(&self) -> bool {
+        false
+    }
+
+    fn get_round_config(&self, _game_session: &GameSession, _round: u16) -> RoundConfig {
+        RoundConfig {
+            move_type_count: self.tile_count,
+        }
+    }
+
+    fn validate_move(
+        &self,
+        game_session: &GameSession,
+        player_moves: &[u8],
+        player_move: u8,
+    ) -> Result<()> {
+        let move_count = game_session.get_player_move_count();
+
+        require_gt!(
+            usize::from(self.get_safe_tile_count()),
+            move_count,
+            GameError::MaxMoveReached
+        );
+
+        require_gt!(self.tile_count, player_move, GameError::InvalidMove);
+
+        // The tile must not have been revealed already.
+        require!(
+            (0..move_count).all(|position| game_session
+                .get_player_move(player_moves, position)
+                .ne(&Some(player_move))),
+            GameError::InvalidMove
+        );
+
+        Ok(())
+    }
+
+    fn is_losing_move(
+        &self,
+        _game_session: &GameSession,
+        secret_seed: &[u8; HASH_LENGTH],
+        _round: u16,
+        player_move: u8,
+    ) -> bool {
+        self.get_mine_positions(secret_seed) & (1 << player_move) != 0
+    }
+
+    fn apply_round_multiplier(
+        &self,
+        _game_session: &GameSession,
+        round: u16,
+        _player_move: u8,
+        amount: u128,
+    ) -> u128 {
+        // Revealing a safe tile after `round` safe tiles has fair odds of
+        // `(tile_count - mine_count - round) / (tile_count - round)`.
+        let remaining_tile_count = u128::from(self.tile_count).saturating_sub(u128::from(round));
+
+        let remaining_safe_tile_count =
+            u128::from(self.get_safe_tile_count()).saturating_sub(u128::from(round));
+
+        if remaining_safe_tile_count == 0 {
+            return amount;
+        }
+
+        amount * remaining_tile_count / remaining_safe_tile_count
+    }
+}
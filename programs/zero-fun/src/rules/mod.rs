@@ -0,0 +1,11 @@
+pub mod game_rules;
+pub use game_rules::*;
+
+pub mod tiles;
+pub use tiles::*;
+
+pub mod mines;
+pub use mines::*;
+
+pub mod dice;
+pub use dice::*;
@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    get_difficulty_round_hash, Difficulty, FailMoveDerivation, GameError, GameRules, GameSession,
+    RoundConfig, HASH_LENGTH, MAX_MOVE_TYPE_COUNT,
+};
+
+/// The tile ladder, each round the player picks one of MTCFR tiles and one of them(or
+/// more at harder difficulties) is the fail move.
+/// - min_move_type_count: The least number of tiles in a round.
+/// - max_move_type_count: The most number of tiles in a round.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TileRules {
+    pub min_move_type_count: u8,
+    pub max_move_type_count: u8,
+}
+
+impl Default for TileRules {
+    fn default() -> Self {
+        Self {
+            min_move_type_count: 2,
+            max_move_type_count: MAX_MOVE_TYPE_COUNT as u8,
+        }
+    }
+}
+
+impl TileRules {
+    /// The inclusive range of MTCFR at `difficulty`, the difficulty's range narrowed to
+    /// the variant's. `None` if the two don't overlap or a round could be all fail tiles.
+    pub fn get_move_type_count_range(&self, difficulty: Difficulty) -> Option<(u8, u8)> {
+        let (min_move_type_count, max_move_type_count) = difficulty.get_move_type_count_range();
+
+        let min_move_type_count = min_move_type_count.max(self.min_move_type_count);
+        let max_move_type_count = max_move_type_count.min(self.max_move_type_count);
+
+        (min_move_type_count <= max_move_type_count
+            && min_move_type_count > difficulty.get_fail_move_count())
+        .then_some((min_move_type_count, max_move_type_count))
+    }
+
+    /// Move Type Count For Round (MTCFR), the number of allowed move types in `round`,
+    /// it is in the inclusive range given by `get_move_type_count_range`.
+    pub fn get_move_type_count_for_round(
+        &self,
+        public_config_seed: &[u8; HASH_LENGTH],
+        round: u16,
+        difficulty: Difficulty,
+    ) -> u8 {
+        let public_config_seed_for_move =
+            get_difficulty_round_hash(public_config_seed, round, difficulty);
+
+        // The difficulty is checked when the session is created.
+        let (min_move_type_count, max_move_type_count) = self
+            .get_move_type_count_range(difficulty)
+            .unwrap_or((self.min_move_type_count, self.max_move_type_count));
+
+        let range = max_move_type_count - min_move_type_count + 1;
+
+        (public_config_seed_for_move[0] % range) + min_move_type_count
+    }
+
+    /// Fail Move For Round (FMFR), the first move that causes a failure in `round`, it is
+    /// in the exclusive range `[0 .. move_type_count_for_round)`.
+    pub fn get_fail_move_for_round(
+        &self,
+        private_config_seed: &[u8; HASH_LENGTH],
+        round: u16,
+        move_type_count_for_round: u8,
+        difficulty: Difficulty,
+    ) -> u8 {
+        let private_config_seed_for_move =
+            get_difficulty_round_hash(private_config_seed, round, difficulty);
+
+        private_config_seed_for_move[0] % move_type_count_for_round
+    }
+
+    /// The fail tiles of `round` as a mask, bit `t` is set if tile `t` fails. There are
+    /// `K` of them at `difficulty`, derived with `fail_move_derivation`:
+    /// - Consecutive: `FMFR` and the `K - 1` tiles following it, wrapping around.
+    /// - PartialShuffle: The first `K` steps of a Fisher-Yates shuffle of the tiles,
+    ///   step `i` swaps tile `i` with tile `i + (B[i] % (MTCFR - i))` where `B` is the
+    ///   per-round hash of the secret seed, so the first fail tile is `FMFR`.
+    pub fn get_fail_moves_for_round(
+        &self,
+        private_config_seed: &[u8; HASH_LENGTH],
+        round: u16,
+        move_type_count_for_round: u8,
+        difficulty: Difficulty,
+        fail_move_derivation: FailMoveDerivation,
+    ) -> u8 {
+        let fail_move_count = difficulty.get_fail_move_count();
+
+        match fail_move_derivation {
+            FailMoveDerivation::Consecutive => {
+                let fail_move = self.get_fail_move_for_round(
+                    private_config_seed,
+                    round,
+                    move_type_count_for_round,
+                    difficulty,
+                );
+
+                (0..fail_move_count).fold(0, |fail_moves, offset| {
+                    fail_moves | 1 << ((fail_move + offset) % move_type_count_for_round)
+                })
+            }
+            FailMoveDerivation::PartialShuffle => {
+                let private_config_seed_for_move =
+                    get_difficulty_round_hash(private_config_seed, round, difficulty);
+
+                let mut tiles = [0u8; MAX_MOVE_TYPE_COUNT];
+
+                for (index, tile) in tiles.iter_mut().enumerate() {
+                    *tile = index as u8;
+                }
+
+                let mut fail_moves = 0;
+
+                // The difficulty leaves at least one safe tile, so `MTCFR - i` is never 0.
+                for index in 0..fail_move_count {
+                    let remaining = move_type_count_for_round - index;
+
+                    let swap_index =
+                        index + private_config_seed_for_move[usize::from(index)] % remaining;
+
+                    tiles.swap(usize::from(index), usize::from(swap_index));
+
+                    fail_moves |= 1 << tiles[usize::from(index)];
+                }
+
+                fail_moves
+            }
+        }
+    }
+}
+
+impl GameRules for TileRules {
+    fn validate_parameters(&self) -> Result<()> {
+        require!(
+            self.min_move_type_count >= 2
+                && self.min_move_type_count <= self.max_move_type_count
+                && usize::from(self.max_move_type_count) <= MAX_MOVE_TYPE_COUNT,
+            GameError::InvalidGameParameters
+        );
+
+        Ok(())
+    }
+
+    fn supports_difficulty(&self, difficulty: Difficulty) -> bool {
+        self.get_move_type_count_range(difficulty).is_some()
+    }
+
+    fn get_round_config(&self, game_session: &GameSession, round: u16) -> RoundConfig {
+        RoundConfig {
+            move_type_count: self.get_move_type_count_for_round(
+                &game_session.public_config_seed,
+                round,
+                game_session.get_difficulty(),
+            ),
+        }
+    }
+
+    fn validate_move(
+        &self,
+        game_session: &GameSession,
+        _player_moves: &[u8],
+        player_move: u8,
+    ) -> Result<()> {
+        let round_config =
+            self.get_round_config(game_session, game_session.next_player_move_position);
+
+        require_gt!(
+            round_config.move_type_count,
+            player_move,
+            GameError::InvalidMove
+        );
+
+        Ok(())
+    }
+
+    fn is_losing_move(
+        &self,
+        game_session: &GameSession,
+        secret_seed: &[u8; HASH_LENGTH],
+        round: u16,
+        player_move: u8,
+    ) -> bool {
+        let difficulty = game_session.get_difficulty();
+
+        let move_type_count = self.get_round_config(game_session, round).move_type_count;
+
+        if player_move >= move_type_count {
+            return false;
+        }
+
+        let fail_moves = self.get_fail_moves_for_round(
+            secret_seed,
+            round,
+            move_type_count,
+            difficulty,
+            game_session.get_fail_move_derivation(),
+        );
+
+        fail_moves & (1 << player_move) != 0
+    }
+
+    fn apply_round_multiplier(
+        &self,
+        game_session: &GameSession,
+        round: u16,
+        _player_move: u8,
+        amount: u128,
+    ) -> u128 {
+        // Surviving has fair odds of `(MTCFR - K) / MTCFR` with `K` fail tiles.
+        let move_type_count_for_round =
+            u128::from(self.get_round_config(game_session, round).move_type_count);
+
+        let fail_move_count = u128::from(game_session.get_difficulty().get_fail_move_count());
+
+        amount * move_type_count_for_round / (move_type_count_for_round - fail_move_count)
+    }
+}
@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    GameError, DEFAULT_OFFSET, MAX_BPS, MIN_DEFAULT_OFFSET, PAYOUT_BREAKER_BUCKET_COUNT,
+    PAYOUT_BREAKER_BUCKET_LENGTH, WIND_DOWN_SETTLEMENT_WINDOW, WITHDRAW_BUCKET_COUNT,
+    WITHDRAW_BUCKET_LENGTH,
+};
+
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct GlobalState {
     /// - Admin: They control the global state, they can also whitelist tokens
     /// or removed whitelisted tokens
@@ -9,28 +15,107 @@ pub struct GlobalState {
     /// - Message signer: They sign messages mark an action as approved by the admin
     /// and be executed by anyone
     pub message_signer: Pubkey,
-    pub max_deposit: u8, // In bps
-    pub max_payout: u8,  // In bps
+    pub max_deposit: u16, // In bps
+    pub max_payout: u16,  // In bps
+    /// The smallest deposit a staked session can be started with
+    pub min_deposit: u64,
+    /// The largest deposit allowed whatever the vault's balance, 0 is no cap
+    pub max_deposit_lamports: u64,
+    /// The largest payout allowed whatever the vault's balance, 0 is no cap
+    pub max_payout_lamports: u64,
     pub game_state: GameState,
     pub vault_bump: u8,
+    /// The share of the fair odds the house keeps on payouts computed on-chain
+    pub house_edge: u16, // In bps
+    /// The share of the house edge on a referred player's sessions paid to their referrer
+    pub referral_fee: u16, // In bps
+    /// The most a referrer can earn from a single session, 0 is no cap
+    pub max_referral_reward: u64,
+    /// The share of each deposit taken as the protocol fee, it is kept in the fee vault
+    /// apart from the bankroll
+    pub protocol_fee: u16, // In bps
+    /// The bankroll `withdraw` always leaves in the vault, on top of its rent
+    pub min_reserve: u64,
+    /// The most `withdraw` can take out of the vault within a withdrawal window, 0 is no cap
+    pub daily_withdraw_limit: u64,
+    /// The amount withdrawn in each bucket of the withdrawal window, a bucket is kept at its
+    /// index modulo the bucket count
+    pub withdraw_buckets: [u64; WITHDRAW_BUCKET_COUNT],
+    /// The index of the bucket the last withdrawal was recorded in
+    pub last_withdraw_bucket: i64,
+    /// The payouts of signed wins allowed within the payout window before settlement is
+    /// restricted, 0 disables the circuit breaker
+    pub payout_breaker_threshold: u64,
+    /// The payouts of signed wins in each bucket of the payout window, a bucket is kept at
+    /// its index modulo the bucket count
+    pub payout_buckets: [u64; PAYOUT_BREAKER_BUCKET_COUNT],
+    /// The index of the bucket the last payout was recorded in
+    pub last_payout_bucket: i64,
+    /// The deposits of staked sessions and crash bets
+    pub total_deposited: u64,
+    /// Everything paid out of the vault to players and referrers, crash wins are counted
+    /// net of their stake
+    pub total_paid_out: u64,
+    /// Everything swept into the vault from lost sessions and bets
+    pub total_swept: u64,
+    /// Everything taken out of the vault through `withdraw`
+    pub total_withdrawn: u64,
+    /// The staked sessions, daily challenge sessions included, and crash bets that are yet
+    /// to be settled
+    pub open_sessions: u64,
+    /// The stakes of the crash bets that are yet to be settled, they are held in the vault
+    /// and owed back to the players who win
+    pub open_crash_stakes: u64,
+    /// The time the game was set to wind down
+    pub wind_down_start: i64,
+    /// The time a session can be defaulted after its last action, new sessions keep the
+    /// value they were started with
+    pub default_offset: i64,
+    /// The time after its start a session can be defaulted regardless of its activity, new
+    /// sessions keep the value they were started with, 0 is no limit
+    pub max_session_lifetime: i64,
 }
 
 impl GlobalState {
     pub fn new(
         admin: Pubkey,
         message_signer: Pubkey,
-        max_deposit: u8,
-        max_payout: u8,
+        max_deposit: u16,
+        max_payout: u16,
         game_state: GameState,
         vault_bump: u8,
+        house_edge: u16,
     ) -> Self {
         Self {
             admin,
             message_signer,
             max_deposit,
             max_payout,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
             game_state,
             vault_bump,
+            house_edge,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve: 0,
+            daily_withdraw_limit: 0,
+            withdraw_buckets: [0; WITHDRAW_BUCKET_COUNT],
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: [0; PAYOUT_BREAKER_BUCKET_COUNT],
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes: 0,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
         }
     }
 
@@ -42,22 +127,341 @@ impl GlobalState {
         self.game_state.eq(&crate::GameState::Active)
     }
 
+    pub fn is_restricted(&self) -> bool {
+        self.game_state.eq(&GameState::Restricted)
+    }
+
+    /// Whether sessions already started can be played, the circuit breaker only holds back
+    /// new sessions and signed wins.
+    pub fn is_playable(&self) -> bool {
+        self.is_active() || self.is_restricted()
+    }
+
+    pub fn is_winding_down(&self) -> bool {
+        self.game_state.eq(&GameState::WindDown)
+    }
+
+    /// Whether the window the admin has to settle marked wins after the game was set to
+    /// wind down has passed.
+    pub fn is_wind_down_settlement_over(&self, now: i64) -> bool {
+        self.is_winding_down()
+            && now
+                > self
+                    .wind_down_start
+                    .saturating_add(WIND_DOWN_SETTLEMENT_WINDOW)
+    }
+
+    /// Clears the buckets that rolled out of a bucketed window since `last_bucket`, the
+    /// window then ends with `bucket`.
+    fn roll_window<const N: usize>(buckets: &mut [u64; N], last_bucket: &mut i64, bucket: i64) {
+        let elapsed = bucket.saturating_sub(*last_bucket);
+
+        if elapsed >= N as i64 {
+            *buckets = [0; N];
+        } else {
+            for stale_bucket in (*last_bucket + 1)..=bucket {
+                buckets[Self::get_bucket_index::<N>(stale_bucket)] = 0;
+            }
+        }
+
+        *last_bucket = (*last_bucket).max(bucket);
+    }
+
+    #[inline(always)]
+    fn get_bucket_index<const N: usize>(bucket: i64) -> usize {
+        bucket.rem_euclid(N as i64) as usize
+    }
+
+    /// The amounts in a bucketed window ending with `bucket`.
+    fn get_window_total<const N: usize>(buckets: &[u64; N], last_bucket: i64, bucket: i64) -> u64 {
+        let elapsed = bucket.saturating_sub(last_bucket);
+
+        // The buckets that rolled out of the window are still in place until the next
+        // amount recorded clears them, so only the ones still in it are counted.
+        (0..N as i64)
+            .filter(|age| age + elapsed < N as i64)
+            .map(|age| buckets[Self::get_bucket_index::<N>(last_bucket - age)])
+            .fold(0u64, u64::saturating_add)
+    }
+
+    /// The payouts of signed wins in the payout window ending at `now`.
+    pub fn get_payouts_in_window(&self, now: i64) -> u64 {
+        Self::get_window_total(
+            &self.payout_buckets,
+            self.last_payout_bucket,
+            now.div_euclid(PAYOUT_BREAKER_BUCKET_LENGTH),
+        )
+    }
+
+    /// Records the payout of a signed win at `now`, the payout leaves out the deposit as it
+    /// is refunded from the session's own vault. The payouts are counted over the last
+    /// `PAYOUT_BREAKER_WINDOW`.
+    /// Returns whether the payouts in the window crossed the threshold, settlement is then
+    /// restricted until the admin sets the game state again.
+    pub fn record_payout(&mut self, payout: u64, now: i64) -> bool {
+        Self::roll_window(
+            &mut self.payout_buckets,
+            &mut self.last_payout_bucket,
+            now.div_euclid(PAYOUT_BREAKER_BUCKET_LENGTH),
+        );
+
+        let bucket = &mut self.payout_buckets
+            [Self::get_bucket_index::<PAYOUT_BREAKER_BUCKET_COUNT>(self.last_payout_bucket)];
+
+        *bucket = bucket.saturating_add(payout);
+
+        // Winding down already holds back everything but settlement, so it is left as is.
+        let is_tripped = self.payout_breaker_threshold > 0
+            && self.get_payouts_in_window(now) > self.payout_breaker_threshold
+            && !self.is_winding_down();
+
+        if is_tripped {
+            self.game_state = GameState::Restricted;
+        }
+
+        is_tripped
+    }
+
+    /// Verifies a session can't be defaulted before the house had `MIN_DEFAULT_OFFSET` to
+    /// settle it, either after its last action or after its start.
+    pub fn check_default_windows(default_offset: i64, max_session_lifetime: i64) -> Result<()> {
+        require!(
+            default_offset >= MIN_DEFAULT_OFFSET
+                && (max_session_lifetime == 0 || max_session_lifetime >= MIN_DEFAULT_OFFSET),
+            GameError::InvalidDefaultWindows
+        );
+
+        Ok(())
+    }
+
+    /// Clears the payouts tracked by the circuit breaker.
+    pub fn reset_payout_window(&mut self) {
+        self.payout_buckets = [0; PAYOUT_BREAKER_BUCKET_COUNT];
+    }
+
     pub fn get_vault_bump(&self) -> u8 {
         self.vault_bump
     }
+
+    /// The lamports that can be withdrawn from the vault, keeping its rent, the stakes of
+    /// the open crash bets and the minimum reserve.
+    pub fn get_free_liquidity(&self, vault_lamports: u64, rent_exempt_minimum: u64) -> u64 {
+        vault_lamports
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(self.open_crash_stakes)
+            .saturating_sub(self.min_reserve)
+    }
+
+    /// The amount withdrawn in the withdrawal window ending at `now`.
+    pub fn get_withdrawn_in_window(&self, now: i64) -> u64 {
+        Self::get_window_total(
+            &self.withdraw_buckets,
+            self.last_withdraw_bucket,
+            now.div_euclid(WITHDRAW_BUCKET_LENGTH),
+        )
+    }
+
+    /// Records a withdrawal of `amount` at `now` against the daily cap, the withdrawals are
+    /// counted over the last `WITHDRAW_WINDOW`.
+    pub fn record_withdrawal(&mut self, amount: u64, now: i64) -> Result<()> {
+        let withdrawn_in_window = self
+            .get_withdrawn_in_window(now)
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        require!(
+            self.daily_withdraw_limit == 0 || withdrawn_in_window <= self.daily_withdraw_limit,
+            GameError::DailyWithdrawLimitExceeded
+        );
+
+        Self::roll_window(
+            &mut self.withdraw_buckets,
+            &mut self.last_withdraw_bucket,
+            now.div_euclid(WITHDRAW_BUCKET_LENGTH),
+        );
+
+        let bucket = &mut self.withdraw_buckets
+            [Self::get_bucket_index::<WITHDRAW_BUCKET_COUNT>(self.last_withdraw_bucket)];
+
+        *bucket = bucket.saturating_add(amount);
+
+        self.total_withdrawn = self.total_withdrawn.saturating_add(amount);
+
+        Ok(())
+    }
+
+    /// Records a staked session or crash bet started with `deposit`.
+    pub fn record_session_opened(&mut self, deposit: u64) {
+        self.total_deposited = self.total_deposited.saturating_add(deposit);
+        self.open_sessions = self.open_sessions.saturating_add(1);
+    }
+
+    /// Records a staked session or crash bet settled, sessions opened before the ledger was
+    /// kept are not counted.
+    pub fn record_session_closed(&mut self) {
+        self.open_sessions = self.open_sessions.saturating_sub(1);
+    }
+
+    /// Records the stake of a crash bet placed, it is held in the vault until the bet is
+    /// settled.
+    pub fn record_crash_stake_opened(&mut self, deposit: u64) {
+        self.open_crash_stakes = self.open_crash_stakes.saturating_add(deposit);
+    }
+
+    /// Records the stake of a crash bet settled, bets placed before the stakes were kept
+    /// are not counted.
+    pub fn record_crash_stake_closed(&mut self, deposit: u64) {
+        self.open_crash_stakes = self.open_crash_stakes.saturating_sub(deposit);
+    }
+
+    /// Records `amount` paid out of the vault.
+    pub fn record_paid_out(&mut self, amount: u64) {
+        self.total_paid_out = self.total_paid_out.saturating_add(amount);
+    }
+
+    /// Records `amount` swept into the vault from a loss.
+    pub fn record_swept(&mut self, amount: u64) {
+        self.total_swept = self.total_swept.saturating_add(amount);
+    }
+
+    /// Reconciles the vault's balance with the ledger, the vault must hold at least what
+    /// was swept into it less what was paid out and withdrawn.
+    /// Returns the lamports held on top of that, the bankroll it was funded with and the
+    /// inflows the ledger does not count, e.g. the house's share of tournament prize pools.
+    pub fn reconcile(&self, vault_lamports: u64, rent_exempt_minimum: u64) -> Result<u64> {
+        let inflows = u128::from(vault_lamports)
+            + u128::from(self.total_paid_out)
+            + u128::from(self.total_withdrawn);
+
+        let ledger = u128::from(rent_exempt_minimum) + u128::from(self.total_swept);
+
+        require_gte!(inflows, ledger, GameError::BankrollMismatch);
+
+        Ok(u64::try_from(inflows - ledger).unwrap_or(u64::MAX))
+    }
+
+    /// The protocol fee taken out of `deposit`.
+    pub fn get_protocol_fee(&self, deposit: u64) -> u64 {
+        (u128::from(deposit) * u128::from(self.protocol_fee) / u128::from(MAX_BPS)) as u64
+    }
+
+    /// The reward due to a referrer for a session with `deposit`, a share of the house edge
+    /// realized on it.
+    pub fn get_referral_reward(&self, deposit: u64) -> u64 {
+        let house_edge = u128::from(deposit) * u128::from(self.house_edge) / u128::from(MAX_BPS);
+
+        let referral_reward =
+            (house_edge * u128::from(self.referral_fee) / u128::from(MAX_BPS)) as u64;
+
+        if self.max_referral_reward == 0 {
+            referral_reward
+        } else {
+            referral_reward.min(self.max_referral_reward)
+        }
+    }
+
+    /// The maximum deposit allowed for a single game given the vault's balance.
+    pub fn get_max_deposit(&self, vault_lamports: u64) -> Result<u64> {
+        let max_deposit = vault_lamports
+            .checked_mul(u64::from(self.max_deposit))
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            / MAX_BPS;
+
+        if self.max_deposit_lamports == 0 {
+            Ok(max_deposit)
+        } else {
+            Ok(max_deposit.min(self.max_deposit_lamports))
+        }
+    }
+
+    /// Verifies `deposit` is within the deposit limits given the vault's balance.
+    pub fn check_deposit(&self, deposit: u64, vault_lamports: u64) -> Result<()> {
+        require_gte!(deposit, self.min_deposit, GameError::DepositBelowMinimum);
+
+        require_gte!(
+            self.get_max_deposit(vault_lamports)?,
+            deposit,
+            GameError::DepositExceedsMaximum
+        );
+
+        Ok(())
+    }
+
+    /// The maximum payout allowed for a single game given the vault's balance.
+    pub fn get_max_payout(&self, vault_lamports: u64) -> Result<u64> {
+        let max_payout = vault_lamports
+            .checked_mul(u64::from(self.max_payout))
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            / MAX_BPS;
+
+        if self.max_payout_lamports == 0 {
+            Ok(max_payout)
+        } else {
+            Ok(max_payout.min(self.max_payout_lamports))
+        }
+    }
+}
+
+/// The layout of the global state before the deposit and payout limits were widened, the
+/// global state is moved off it through `migrate_global_state`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct LegacyGlobalState {
+    pub admin: Pubkey,
+    pub message_signer: Pubkey,
+    pub max_deposit: u8, // In bps
+    pub max_payout: u8,  // In bps
+    pub game_state: GameState,
+    pub vault_bump: u8,
+}
+
+impl LegacyGlobalState {
+    pub const SPACE: usize = 32 + 32 + 1 + 1 + 1 + 1;
+
+    /// The global state in the current layout, every field added since starts unset.
+    pub fn migrate(&self) -> GlobalState {
+        GlobalState::new(
+            self.admin,
+            self.message_signer,
+            u16::from(self.max_deposit),
+            u16::from(self.max_payout),
+            self.game_state,
+            self.vault_bump,
+            0,
+        )
+    }
 }
 
-#[derive(InitSpace, Clone, Copy, AnchorDeserialize, AnchorSerialize, PartialEq)]
+#[derive(InitSpace, Clone, Copy, AnchorDeserialize, AnchorSerialize, PartialEq, Default)]
 pub enum GameState {
+    #[default]
     Active,
     Locked,
+    /// Set by the payout circuit breaker, sessions can't be started or settled as won
+    /// through a signed message, every other settlement goes on.
+    Restricted,
+    /// Set by the admin to shut the game down, sessions can't be started and every open
+    /// one can be defaulted at once, marked wins are left `WIND_DOWN_SETTLEMENT_WINDOW` to
+    /// be settled first.
+    WindDown,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub enum GlobalStateUpdate {
     Admin(Pubkey),
     MessageSigner(Pubkey),
-    MaxDeposit(u8),
-    MaxPayout(u8),
+    MaxDeposit(u16),
+    MaxPayout(u16),
     GameState(GameState),
+    HouseEdge(u16),
+    ReferralFee(u16),
+    MaxReferralReward(u64),
+    ProtocolFee(u16),
+    MinReserve(u64),
+    DailyWithdrawLimit(u64),
+    PayoutBreakerThreshold(u64),
+    MinDeposit(u64),
+    MaxDepositLamports(u64),
+    MaxPayoutLamports(u64),
+    DefaultOffset(i64),
+    MaxSessionLifetime(i64),
 }
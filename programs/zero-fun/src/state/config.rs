@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    DiceRules, GameError, GameState, MinesRules, TileRules, DICE_MOVE_BIT_WIDTH,
+    MINES_MOVE_BIT_WIDTH, MOVE_BIT_WIDTH,
+};
+
+/// The game variants the program supports, each one has its own `GameConfig`.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameVariant {
+    Tiles,
+    Mines,
+    Dice,
+}
+
+impl From<GameVariant> for u8 {
+    fn from(variant: GameVariant) -> Self {
+        variant as u8
+    }
+}
+
+impl TryFrom<u8> for GameVariant {
+    type Error = GameError;
+
+    fn try_from(variant: u8) -> std::result::Result<Self, Self::Error> {
+        match variant {
+            0 => Ok(GameVariant::Tiles),
+            1 => Ok(GameVariant::Mines),
+            2 => Ok(GameVariant::Dice),
+            _ => Err(GameError::InvalidGameVariant),
+        }
+    }
+}
+
+impl GameVariant {
+    /// The number of bits a move of the variant takes in the move storage.
+    pub fn get_move_bit_width(&self) -> usize {
+        match self {
+            GameVariant::Tiles => MOVE_BIT_WIDTH,
+            GameVariant::Mines => MINES_MOVE_BIT_WIDTH,
+            GameVariant::Dice => DICE_MOVE_BIT_WIDTH,
+        }
+    }
+}
+
+/// The parameters of a game variant, they are what the variant's `GameRules` are
+/// implemented on.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameParameters {
+    Tiles(TileRules),
+    Mines(MinesRules),
+    Dice(DiceRules),
+}
+
+impl GameParameters {
+    pub fn get_variant(&self) -> GameVariant {
+        match self {
+            GameParameters::Tiles(_) => GameVariant::Tiles,
+            GameParameters::Mines(_) => GameVariant::Mines,
+            GameParameters::Dice(_) => GameVariant::Dice,
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+/// The registry entry for a game variant, there is one per variant at
+/// `[b"game-config", [variant]]`.
+/// The parameters are fixed once the config is created so sessions in flight always
+/// settle with the rules they were started with, only the state can be updated.
+pub struct GameConfig {
+    pub game_state: GameState,
+    pub parameters: GameParameters,
+    pub bump: u8,
+}
+
+impl GameConfig {
+    pub fn new(game_state: GameState, parameters: GameParameters, bump: u8) -> Self {
+        Self {
+            game_state,
+            parameters,
+            bump,
+        }
+    }
+
+    pub fn get_variant(&self) -> GameVariant {
+        self.parameters.get_variant()
+    }
+
+    /// Checks if this is the config for the variant stored in a session.
+    pub fn is_for_variant(&self, variant: u8) -> bool {
+        u8::from(self.get_variant()) == variant
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.game_state.eq(&GameState::Active)
+    }
+}
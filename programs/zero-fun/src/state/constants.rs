@@ -4,14 +4,118 @@ pub const HASH_LENGTH: usize = 32;
 
 // type HASHTYPE = [u8;HASH_LENGTH];
 
-pub const MAX_MOVE_COUNT: usize = 64;
+/// The number of moves held by a single page of move storage, a session starts
+/// with one page and grows by a page whenever it runs out of space.
+pub const MOVE_PAGE_SIZE: usize = 64;
+
+pub const MAX_MOVE_COUNT: usize = u16::MAX as usize;
 
 pub const MAX_MOVE_TYPE_COUNT: usize = 8;
 
+/// The number of bits needed to store a single move, moves are in the range
+/// `[0 .. MAX_MOVE_TYPE_COUNT)`.
+pub const MOVE_BIT_WIDTH: usize =
+    (usize::BITS - (MAX_MOVE_TYPE_COUNT - 1).leading_zeros()) as usize;
+
+/// The most number of tiles a Mines grid can have.
+pub const MAX_MINES_TILE_COUNT: usize = 32;
+
+/// The number of bits needed to store a Mines move, moves are tile indices in the
+/// range `[0 .. MAX_MINES_TILE_COUNT)`.
+pub const MINES_MOVE_BIT_WIDTH: usize =
+    (usize::BITS - (MAX_MINES_TILE_COUNT - 1).leading_zeros()) as usize;
+
+/// The number of bits needed to store a Dice move, the target and the direction of
+/// the bet.
+pub const DICE_MOVE_BIT_WIDTH: usize = 8;
+
+/// The most number of outcomes a Dice roll can have, the target has to fit in the
+/// bits of a move left after the direction.
+pub const MAX_DICE_ROLL_COUNT: usize = 1 << (DICE_MOVE_BIT_WIDTH - 1);
+
+/// The number of bytes needed to store a page of bit-packed moves.
+pub const PACKED_MOVE_PAGE_LENGTH: usize = (MOVE_PAGE_SIZE * MOVE_BIT_WIDTH).div_ceil(8);
+
 pub const MAX_METADATA_LENGTH: usize = 64;
 
+/// The default offset a new global state starts with.
 pub const DEFAULT_OFFSET: i64 = 60 * 60 * 36; // 36 hours
 
+/// The shortest default offset the admin can set, the house needs the time to settle a
+/// session before the player can take their deposit back.
+pub const MIN_DEFAULT_OFFSET: i64 = 60 * 60; // 1 hour
+
+pub const CASH_OUT_REVEAL_WINDOW: i64 = 60 * 60; // 1 hour
+
+pub const CRASH_REVEAL_WINDOW: i64 = 60 * 60; // 1 hour
+
+/// The highest crash point a round can have and so the highest cash-out target.
+pub const MAX_CRASH_POINT: u64 = 1_000 * MAX_BPS; // 1000x
+
+/// The most number of moves an auto-play session can reveal.
+pub const MAX_AUTO_PLAY_MOVE_COUNT: usize = MOVE_PAGE_SIZE * 4;
+
+/// The most number of places a tournament pays prizes to.
+pub const MAX_TOURNAMENT_WINNERS: usize = 10;
+
+/// The time the house has after a tournament ends to reveal its private config seed,
+/// entrants can get their entry fee back after it.
+pub const TOURNAMENT_REVEAL_WINDOW: i64 = 60 * 60 * 24; // 24 hours
+
+/// The time entrants have after the seed is revealed to submit their scores.
+pub const TOURNAMENT_SCORING_WINDOW: i64 = 60 * 60 * 24; // 24 hours
+
+/// The length of a daily challenge, challenge `day` runs from `day * DAILY_CHALLENGE_DURATION`.
+pub const DAILY_CHALLENGE_DURATION: i64 = 60 * 60 * 24; // 24 hours
+
+/// The time the house has after a daily challenge ends to reveal its private config seed,
+/// players can get their deposit back after it.
+pub const DAILY_CHALLENGE_REVEAL_WINDOW: i64 = 60 * 60 * 24; // 24 hours
+
+/// The most number of places kept on a daily challenge's leaderboard.
+pub const MAX_DAILY_CHALLENGE_LEADERBOARD_LENGTH: usize = 10;
+
+/// The largest share of each deposit the jackpot can take.
+pub const MAX_JACKPOT_CONTRIBUTION: u16 = 1_000; // 10%
+
+/// The length of the window the payout circuit breaker counts payouts over.
+pub const PAYOUT_BREAKER_WINDOW: i64 = 60 * 60; // 1 hour
+
+/// The number of buckets the payout window is split into, it rolls forward a bucket at a
+/// time.
+pub const PAYOUT_BREAKER_BUCKET_COUNT: usize = 6;
+
+/// The length of each bucket of the payout window.
+pub const PAYOUT_BREAKER_BUCKET_LENGTH: i64 =
+    PAYOUT_BREAKER_WINDOW / PAYOUT_BREAKER_BUCKET_COUNT as i64; // 10 minutes
+
+/// The time the admin has to settle marked wins once the game is set to wind down.
+pub const WIND_DOWN_SETTLEMENT_WINDOW: i64 = 60 * 60 * 6; // 6 hours
+
+/// The length of the window the daily withdrawal cap is counted over.
+pub const WITHDRAW_WINDOW: i64 = 60 * 60 * 24; // 24 hours
+
+/// The number of buckets the withdrawal window is split into, it rolls forward a bucket at
+/// a time.
+pub const WITHDRAW_BUCKET_COUNT: usize = 24;
+
+/// The length of each bucket of the withdrawal window.
+pub const WITHDRAW_BUCKET_LENGTH: i64 = WITHDRAW_WINDOW / WITHDRAW_BUCKET_COUNT as i64; // 1 hour
+
+/// The largest share of each deposit the protocol fee can take.
+pub const MAX_PROTOCOL_FEE: u16 = 1_000; // 10%
+
+/// The periods player deposit limits are counted over, from the unix epoch.
+pub const DEPOSIT_LIMIT_DAY: i64 = 60 * 60 * 24; // 24 hours
+pub const DEPOSIT_LIMIT_WEEK: i64 = 7 * DEPOSIT_LIMIT_DAY;
+pub const DEPOSIT_LIMIT_MONTH: i64 = 30 * DEPOSIT_LIMIT_DAY;
+
+/// The time a player waits for looser limits to take effect.
+pub const LIMIT_COOLING_OFF_PERIOD: i64 = 60 * 60 * 24; // 24 hours
+
+/// The longest a player can exclude themselves for in one go.
+pub const MAX_SELF_EXCLUSION_PERIOD: i64 = 60 * 60 * 24 * 365 * 5; // 5 years
+
 pub const INITIALIZE_GAME_ACTION: &str = "initialize-game";
 
 pub const FINALIZE_WIN_ACTION: &str = "finalize-win";
@@ -22,4 +126,8 @@ pub const PRIVATE_SEED: &str = "private";
 
 pub const PUBLIC_SEED: &str = "public";
 
+pub const AUTO_PLAY_SEED: &str = "auto-play";
+
+pub const JACKPOT_SEED: &str = "jackpot";
+
 pub const MOVE_TYPE_COUNT_SEED: &str = "move-type-count";
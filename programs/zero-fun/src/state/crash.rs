@@ -0,0 +1,173 @@
+use std::ops::Add;
+
+use anchor_lang::prelude::*;
+
+use crate::{CRASH_REVEAL_WINDOW, HASH_LENGTH, MAX_BPS};
+
+#[account]
+#[derive(InitSpace)]
+/// The state of the crash game, there is a single one at `[b"crash-state"]`.
+/// The house commits to the head of a reverse hash chain when it is created, each
+/// round then reveals the next link of the chain and derives its crash point from it.
+pub struct CrashState {
+    /// The last link of the hash chain that was revealed, it starts out as the
+    /// committed head of the chain.
+    pub chain_link: [u8; HASH_LENGTH],
+    /// The index of the next round to be started.
+    pub next_round_index: u64,
+    /// How long players can join a round after it is started.
+    pub betting_period: i64, // In seconds
+    /// How fast the multiplier grows once betting closes.
+    pub multiplier_growth: u64, // In bps per second
+    pub has_open_round: bool,
+    pub bump: u8,
+}
+
+impl CrashState {
+    pub fn new(
+        chain_head: [u8; HASH_LENGTH],
+        betting_period: i64,
+        multiplier_growth: u64,
+        bump: u8,
+    ) -> Self {
+        Self {
+            chain_link: chain_head,
+            next_round_index: 0,
+            betting_period,
+            multiplier_growth,
+            has_open_round: false,
+            bump,
+        }
+    }
+
+    /// The multiplier `elapsed` seconds after betting closed.
+    pub fn get_multiplier(&self, elapsed: i64) -> u64 {
+        let elapsed = u64::try_from(elapsed).unwrap_or(0);
+
+        MAX_BPS.saturating_add(self.multiplier_growth.saturating_mul(elapsed))
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CrashRoundStatus {
+    Open,
+    Revealed,
+}
+
+#[account]
+#[derive(InitSpace)]
+/// A round of the crash game at `[b"crash-round", round_index]`.
+pub struct CrashRound {
+    pub round_index: u64,
+    /// Players can join until this time, the multiplier starts growing after it.
+    pub betting_deadline: i64,
+    /// The house edge at the time the round was started, the crash point is derived
+    /// with it.
+    pub house_edge: u16, // In bps
+    pub status: CrashRoundStatus,
+    /// Set if the round was revealed after its reveal window expired, the bets are then
+    /// paid their target regardless of the crash point.
+    pub revealed_late: bool,
+    /// The link of the hash chain revealed for the round.
+    pub chain_link: [u8; HASH_LENGTH],
+    pub crash_point: u64, // In bps
+    /// What the round's bets win at their targets on top of their stakes, it is kept
+    /// within the maximum payout so a round can't be joined past what the vault covers.
+    pub potential_payout: u64,
+    pub bump: u8,
+}
+
+impl CrashRound {
+    pub fn new(round_index: u64, betting_deadline: i64, house_edge: u16, bump: u8) -> Self {
+        Self {
+            round_index,
+            betting_deadline,
+            house_edge,
+            status: CrashRoundStatus::Open,
+            revealed_late: false,
+            chain_link: [0; HASH_LENGTH],
+            crash_point: 0,
+            potential_payout: 0,
+            bump,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status.eq(&CrashRoundStatus::Open)
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.status.eq(&CrashRoundStatus::Revealed)
+    }
+
+    pub fn is_betting_open(&self, now: i64) -> bool {
+        now.le(&self.betting_deadline)
+    }
+
+    pub fn get_reveal_deadline(&self) -> i64 {
+        self.betting_deadline.add(CRASH_REVEAL_WINDOW)
+    }
+
+    pub fn is_reveal_window_expired(&self, now: i64) -> bool {
+        now.gt(&self.get_reveal_deadline())
+    }
+
+    /// Checks if a bet with `cash_out_target` won, `None` if the round can't be settled
+    /// yet. If the house did not reveal the round in time every bet wins.
+    pub fn is_winning_target(&self, cash_out_target: u64, now: i64) -> Option<bool> {
+        if self.is_revealed() && !self.revealed_late {
+            Some(cash_out_target < self.crash_point)
+        } else if self.revealed_late || self.is_reveal_window_expired(now) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+/// A player's bet on a crash round at `[b"crash-bet", crash_round, player]`.
+pub struct CrashBet {
+    pub crash_round: Pubkey,
+    pub player: Pubkey,
+    pub deposit: u64,
+    /// The bet is cashed out once the multiplier reaches this, it can be lowered while
+    /// the round is running.
+    pub cash_out_target: u64, // In bps
+    pub bump: u8,
+}
+
+impl CrashBet {
+    pub fn new(
+        crash_round: Pubkey,
+        player: Pubkey,
+        deposit: u64,
+        cash_out_target: u64,
+        bump: u8,
+    ) -> Self {
+        Self {
+            crash_round,
+            player,
+            deposit,
+            cash_out_target,
+            bump,
+        }
+    }
+
+    pub fn is_for_round(&self, crash_round: &Pubkey) -> bool {
+        self.crash_round.eq(crash_round)
+    }
+
+    pub fn is_owned_by_player(&self, player: &Pubkey) -> bool {
+        self.player.eq(player)
+    }
+
+    /// The amount paid out if the bet wins, the deposit included.
+    pub fn get_payout(&self) -> Result<u64> {
+        let payout =
+            u128::from(self.deposit) * u128::from(self.cash_out_target) / u128::from(MAX_BPS);
+
+        Ok(u64::try_from(payout).map_err(|_| ProgramError::ArithmeticOverflow)?)
+    }
+}
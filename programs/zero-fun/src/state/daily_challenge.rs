@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    GameVariant, LeaderboardEntry, DAILY_CHALLENGE_DURATION, DAILY_CHALLENGE_REVEAL_WINDOW,
+    HASH_LENGTH, MAX_DAILY_CHALLENGE_LEADERBOARD_LENGTH,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DailyChallengeStatus {
+    Open,
+    Revealed,
+}
+
+#[account]
+#[derive(InitSpace)]
+/// A daily challenge at `[b"daily-challenge", day]`.
+/// The house commits to a single public config seed shared by every player of the day,
+/// each player's session is at `[b"challenge-session", daily_challenge, player]` so it
+/// doesn't collide with the sessions keyed by their own seed. Once the day ends the
+/// house reveals the private config seed once and every session is settled and ranked
+/// against the same board. If it isn't revealed within `DAILY_CHALLENGE_REVEAL_WINDOW`
+/// every session is refunded its deposit instead.
+pub struct DailyChallenge {
+    /// The number of days since the unix epoch the challenge runs on.
+    pub day: u64,
+    pub variant: GameVariant,
+    pub public_config_seed: [u8; HASH_LENGTH],
+    /// Only meaningful once the challenge is revealed.
+    pub private_config_seed: [u8; HASH_LENGTH],
+    pub status: DailyChallengeStatus,
+    pub session_count: u32,
+    /// The best scores settled so far, sorted from the best.
+    #[max_len(MAX_DAILY_CHALLENGE_LEADERBOARD_LENGTH)]
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+impl DailyChallenge {
+    pub fn new(
+        day: u64,
+        variant: GameVariant,
+        public_config_seed: [u8; HASH_LENGTH],
+        bump: u8,
+    ) -> Self {
+        Self {
+            day,
+            variant,
+            public_config_seed,
+            private_config_seed: [0; HASH_LENGTH],
+            status: DailyChallengeStatus::Open,
+            session_count: 0,
+            leaderboard: Vec::new(),
+            bump,
+        }
+    }
+
+    /// The day `now` falls on.
+    pub fn get_day(now: i64) -> u64 {
+        u64::try_from(now.div_euclid(DAILY_CHALLENGE_DURATION)).unwrap_or(0)
+    }
+
+    pub fn get_start_time(&self) -> i64 {
+        i64::try_from(self.day)
+            .unwrap_or(i64::MAX)
+            .saturating_mul(DAILY_CHALLENGE_DURATION)
+    }
+
+    pub fn get_end_time(&self) -> i64 {
+        self.get_start_time()
+            .saturating_add(DAILY_CHALLENGE_DURATION)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status.eq(&DailyChallengeStatus::Open)
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.status.eq(&DailyChallengeStatus::Revealed)
+    }
+
+    pub fn is_running(&self, now: i64) -> bool {
+        self.is_open() && now.ge(&self.get_start_time()) && now.lt(&self.get_end_time())
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        now.ge(&self.get_end_time())
+    }
+
+    pub fn is_reveal_window_expired(&self, now: i64) -> bool {
+        now.gt(&self
+            .get_end_time()
+            .saturating_add(DAILY_CHALLENGE_REVEAL_WINDOW))
+    }
+
+    /// Places `player` on the leaderboard if `rounds_survived` beats one of its scores,
+    /// ties keep the score that was settled first.
+    pub fn submit_score(&mut self, player: Pubkey, rounds_survived: u16) {
+        let position = self
+            .leaderboard
+            .iter()
+            .position(|entry| entry.rounds_survived < rounds_survived)
+            .unwrap_or(self.leaderboard.len());
+
+        if position >= MAX_DAILY_CHALLENGE_LEADERBOARD_LENGTH {
+            return;
+        }
+
+        self.leaderboard.insert(
+            position,
+            LeaderboardEntry {
+                player,
+                rounds_survived,
+            },
+        );
+
+        self.leaderboard
+            .truncate(MAX_DAILY_CHALLENGE_LEADERBOARD_LENGTH);
+    }
+}
@@ -44,4 +44,140 @@ pub enum GameError {
     InvalidGameSeed,
     #[msg("Invalid fail position")]
     InvalidFailPosition,
+    #[msg("Invalid move")]
+    InvalidMove,
+    #[msg("Invalid game session status")]
+    InvalidGameSessionStatus,
+    #[msg("The move storage is full")]
+    MoveStorageFull,
+    #[msg("Invalid fairness mode")]
+    InvalidFairnessMode,
+    #[msg("The previous round has not been revealed yet")]
+    RoundNotRevealed,
+    #[msg("There is no round to reveal")]
+    NoRoundToReveal,
+    #[msg("Invalid hash chain link")]
+    InvalidHashChainLink,
+    #[msg("Invalid house edge")]
+    InvalidHouseEdge,
+    #[msg("The game session has not been cashed out")]
+    GameSessionNotCashedOut,
+    #[msg("The reveal window has not expired yet")]
+    RevealWindowNotExpired,
+    #[msg("The reveal window has expired")]
+    RevealWindowExpired,
+    #[msg("Invalid game variant")]
+    InvalidGameVariant,
+    #[msg("Invalid game parameters")]
+    InvalidGameParameters,
+    #[msg("Invalid game config")]
+    InvalidGameConfig,
+    #[msg("The game variant is not active")]
+    GameVariantNotActive,
+    #[msg("Invalid crash parameters")]
+    InvalidCrashParameters,
+    #[msg("A crash round is still open")]
+    CrashRoundOpen,
+    #[msg("The crash round is not open")]
+    CrashRoundNotOpen,
+    #[msg("Betting for the crash round has closed")]
+    BettingClosed,
+    #[msg("Betting for the crash round has not closed yet")]
+    BettingNotClosed,
+    #[msg("Invalid cash out target")]
+    InvalidCashOutTarget,
+    #[msg("The crash round has not been revealed yet")]
+    CrashRoundNotRevealed,
+    #[msg("Invalid crash round")]
+    InvalidCrashRound,
+    #[msg("Invalid difficulty")]
+    InvalidDifficulty,
+    #[msg("Invalid fail move derivation")]
+    InvalidFailMoveDerivation,
+    #[msg("The game session is played through auto-play")]
+    AutoPlaySession,
+    #[msg("The game session is not played through auto-play")]
+    NotAutoPlaySession,
+    #[msg("Invalid auto-play moves")]
+    InvalidAutoPlayMoves,
+    #[msg("The game session is a tournament entry")]
+    TournamentSession,
+    #[msg("Invalid tournament parameters")]
+    InvalidTournamentParameters,
+    #[msg("The tournament is not open for entries")]
+    TournamentNotOpen,
+    #[msg("The tournament has not ended yet")]
+    TournamentNotEnded,
+    #[msg("Invalid tournament status")]
+    InvalidTournamentStatus,
+    #[msg("Invalid tournament")]
+    InvalidTournament,
+    #[msg("The scoring window of the tournament has not ended yet")]
+    ScoringWindowNotExpired,
+    #[msg("Invalid prize recipient")]
+    InvalidPrizeRecipient,
+    #[msg("Invalid jackpot parameters")]
+    InvalidJackpotParameters,
+    #[msg("The game session is a daily challenge session")]
+    DailyChallengeSession,
+    #[msg("Invalid daily challenge parameters")]
+    InvalidDailyChallengeParameters,
+    #[msg("The daily challenge is not open")]
+    DailyChallengeNotOpen,
+    #[msg("The daily challenge has not ended")]
+    DailyChallengeNotEnded,
+    #[msg("Invalid daily challenge status")]
+    InvalidDailyChallengeStatus,
+    #[msg("The game session is not for the daily challenge")]
+    InvalidDailyChallenge,
+    #[msg("Practice sessions have no deposit and no vault")]
+    InvalidPracticeGame,
+    #[msg("The game session is a practice session")]
+    PracticeSession,
+    #[msg("The game session is not a practice session")]
+    NotPracticeSession,
+    #[msg("The player has excluded themselves from playing")]
+    PlayerSelfExcluded,
+    #[msg("The player has reached their maximum number of active sessions")]
+    TooManyActiveSessions,
+    #[msg("The deposit exceeds the player's deposit limit")]
+    DepositLimitExceeded,
+    #[msg("Invalid self-exclusion period")]
+    InvalidSelfExclusionPeriod,
+    #[msg("Invalid referral fee")]
+    InvalidReferralFee,
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+    #[msg("The player already has a referrer")]
+    ReferrerAlreadySet,
+    #[msg("Invalid referrer rewards account")]
+    InvalidReferrerRewards,
+    #[msg("There are no referral rewards to claim")]
+    NoReferralRewards,
+    #[msg("Invalid protocol fee")]
+    InvalidProtocolFee,
+    #[msg("The fee vault does not hold enough fees")]
+    InsufficientFees,
+    #[msg("The vault does not have enough free liquidity")]
+    InsufficientFreeLiquidity,
+    #[msg("The withdrawal exceeds the daily withdrawal limit")]
+    DailyWithdrawLimitExceeded,
+    #[msg("Settlement is restricted by the payout circuit breaker")]
+    SettlementRestricted,
+    #[msg("The deposit is below the minimum deposit")]
+    DepositBelowMinimum,
+    #[msg("Invalid deposit or payout limits")]
+    InvalidLimits,
+    #[msg("The global state has already been migrated")]
+    GlobalStateAlreadyMigrated,
+    #[msg("The game session is not in the legacy layout")]
+    NotLegacyGameSession,
+    #[msg("The vault's balance does not reconcile with the bankroll ledger")]
+    BankrollMismatch,
+    #[msg("The game is not winding down")]
+    GameNotWindingDown,
+    #[msg("There are sessions still open")]
+    SessionsStillOpen,
+    #[msg("Invalid default offset or session lifetime")]
+    InvalidDefaultWindows,
 }
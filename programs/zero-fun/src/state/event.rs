@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{GameSession, GlobalStateUpdate, HASH_LENGTH};
+use crate::{
+    Difficulty, GameState, GameVariant, GlobalStateUpdate, LeaderboardEntry, PlayerLimits,
+    HASH_LENGTH,
+};
 
 #[event]
 pub struct MarkGameAsWonEvent {
@@ -10,7 +13,17 @@ pub struct MarkGameAsWonEvent {
 #[event]
 pub struct InitializeGameEvent {
     pub game_session: Pubkey,
-    pub game_session_account: GameSession,
+    pub player: Pubkey,
+    pub deposit: u64,
+    pub vault: Pubkey,
+    pub public_config_seed: [u8; HASH_LENGTH],
+    pub game_metadata: String,
+    pub hash_chain_head: Option<[u8; HASH_LENGTH]>,
+    pub client_seed: Option<[u8; HASH_LENGTH]>,
+    pub variant: GameVariant,
+    pub difficulty: Difficulty,
+    pub auto_play_commitment: Option<[u8; HASH_LENGTH]>,
+    pub practice: bool,
 }
 
 #[event]
@@ -37,15 +50,297 @@ pub struct FinalizeGameAsLostEvent {
     pub private_config_seed: [u8; HASH_LENGTH],
 }
 
+#[event]
+pub struct RevealRoundEvent {
+    pub game_session: Pubkey,
+    pub round: u16,
+    pub hash_chain_link: [u8; HASH_LENGTH],
+    pub survived: bool,
+}
+
+#[event]
+pub struct CashOutEvent {
+    pub game_session: Pubkey,
+    pub round_count: u16,
+    pub reveal_deadline: i64,
+}
+
+#[event]
+pub struct RevealAutoPlayEvent {
+    pub game_session: Pubkey,
+    pub moves: Vec<u8>,
+    pub stop_after: u16,
+    pub salt: [u8; HASH_LENGTH],
+}
+
+#[event]
+pub struct SettleCashOutEvent {
+    pub game_session: Pubkey,
+    pub round_count: u16,
+    pub payout: u64,
+}
+
 #[event]
 pub struct UpdateGlobalStateEvent {
     pub admin_at_time_of_update: Pubkey,
     pub update: GlobalStateUpdate,
 }
 
+#[event]
+pub struct UpdateGameConfigEvent {
+    pub admin_at_time_of_update: Pubkey,
+    pub variant: GameVariant,
+    pub game_state: GameState,
+}
+
 #[event]
 pub struct WithdrawEvent {
     pub admin: Pubkey,
     pub recipient: Pubkey,
     pub amount: u64,
 }
+
+#[event]
+pub struct StartCrashRoundEvent {
+    pub crash_round: Pubkey,
+    pub round_index: u64,
+    pub betting_deadline: i64,
+}
+
+#[event]
+pub struct JoinCrashRoundEvent {
+    pub crash_round: Pubkey,
+    pub player: Pubkey,
+    pub deposit: u64,
+    pub cash_out_target: u64,
+}
+
+#[event]
+pub struct CashOutCrashBetEvent {
+    pub crash_round: Pubkey,
+    pub player: Pubkey,
+    pub cash_out_target: u64,
+}
+
+#[event]
+pub struct RevealCrashRoundEvent {
+    pub crash_round: Pubkey,
+    pub round_index: u64,
+    pub chain_link: [u8; HASH_LENGTH],
+    pub house_edge: u16,
+    pub crash_point: u64,
+    pub revealed_late: bool,
+}
+
+#[event]
+pub struct SettleCrashBetEvent {
+    pub crash_round: Pubkey,
+    pub player: Pubkey,
+    pub won: bool,
+    pub payout: u64,
+}
+
+#[event]
+pub struct CreateTournamentEvent {
+    pub tournament: Pubkey,
+    pub tournament_id: u64,
+    pub variant: GameVariant,
+    pub entry_fee: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub public_config_seed: [u8; HASH_LENGTH],
+    pub prize_shares: Vec<u16>,
+}
+
+#[event]
+pub struct EnterTournamentEvent {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub game_session: Pubkey,
+}
+
+#[event]
+pub struct RevealTournamentEvent {
+    pub tournament: Pubkey,
+    pub private_config_seed: [u8; HASH_LENGTH],
+}
+
+#[event]
+pub struct SubmitTournamentScoreEvent {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub game_session: Pubkey,
+    pub rounds_survived: Option<u16>,
+}
+
+#[event]
+pub struct DistributeTournamentPrizesEvent {
+    pub tournament: Pubkey,
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub prizes: Vec<u64>,
+    pub house_share: u64,
+}
+
+#[event]
+pub struct RefundTournamentEntryEvent {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct JackpotContributionEvent {
+    pub game_session: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct JackpotPayoutEvent {
+    pub game_session: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct UpdateJackpotEvent {
+    pub admin_at_time_of_update: Pubkey,
+    pub contribution: u16,
+    pub odds: u32,
+    pub move_threshold: u16,
+}
+
+#[event]
+pub struct OpenDailyChallengeEvent {
+    pub daily_challenge: Pubkey,
+    pub day: u64,
+    pub variant: GameVariant,
+    pub public_config_seed: [u8; HASH_LENGTH],
+}
+
+#[event]
+pub struct StartDailyChallengeEvent {
+    pub daily_challenge: Pubkey,
+    pub player: Pubkey,
+    pub game_session: Pubkey,
+    pub deposit: u64,
+}
+
+#[event]
+pub struct RevealDailyChallengeEvent {
+    pub daily_challenge: Pubkey,
+    pub private_config_seed: [u8; HASH_LENGTH],
+}
+
+#[event]
+pub struct SettleDailyChallengeEvent {
+    pub daily_challenge: Pubkey,
+    pub player: Pubkey,
+    pub game_session: Pubkey,
+    /// `None` if the session made a move after the challenge ended, it is then only
+    /// refunded its deposit.
+    pub rounds_survived: Option<u16>,
+    pub is_lost: bool,
+    pub payout: u64,
+}
+
+#[event]
+pub struct RefundDailyChallengeSessionEvent {
+    pub daily_challenge: Pubkey,
+    pub player: Pubkey,
+    pub game_session: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RevealPracticeGameEvent {
+    pub game_session: Pubkey,
+    pub player: Pubkey,
+    /// `None` if the session was closed without the seed being revealed.
+    pub private_config_seed: Option<[u8; HASH_LENGTH]>,
+    pub rounds_survived: Option<u16>,
+    pub is_lost: bool,
+}
+
+#[event]
+pub struct SetPlayerLimitsEvent {
+    pub player: Pubkey,
+    pub limits: PlayerLimits,
+    /// `Some` with the time the limits take effect if they loosen a limit in force.
+    pub pending_limits_time: Option<i64>,
+}
+
+#[event]
+pub struct SelfExcludeEvent {
+    pub player: Pubkey,
+    pub excluded_until: i64,
+}
+
+#[event]
+pub struct SetReferrerEvent {
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct ReferralRewardEvent {
+    pub referrer: Pubkey,
+    pub player: Pubkey,
+    pub game_session: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimReferralRewardsEvent {
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProtocolFeeEvent {
+    pub game_session: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WithdrawFeesEvent {
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PayoutCircuitBreakerEvent {
+    pub game_session: Pubkey,
+    pub payouts_in_window: u64,
+    pub threshold: u64,
+}
+
+#[event]
+pub struct MigrateGlobalStateEvent {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RefundLegacyGameSessionEvent {
+    pub game_session: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReconcileBankrollEvent {
+    pub vault_balance: u64,
+    pub total_deposited: u64,
+    pub total_paid_out: u64,
+    pub total_swept: u64,
+    pub total_withdrawn: u64,
+    pub open_sessions: u64,
+    pub unaccounted: u64,
+}
+
+#[event]
+pub struct CloseGlobalStateEvent {
+    pub admin: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+/// The fee vault at `[b"fee-vault"]`, it holds the protocol fees itself so they are kept
+/// apart from the bankroll in the global vault and never count towards its limits.
+pub struct FeeVault {
+    /// The fees held, on top of the account's rent.
+    pub balance: u64,
+    pub total_collected: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl FeeVault {
+    pub fn new(bump: u8) -> Self {
+        Self {
+            balance: 0,
+            total_collected: 0,
+            total_withdrawn: 0,
+            bump,
+        }
+    }
+}
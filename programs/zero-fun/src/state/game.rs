@@ -2,38 +2,225 @@ use std::ops::Add;
 
 use anchor_lang::prelude::*;
 
-use crate::{state::error::GameError, DEFAULT_OFFSET, HASH_LENGTH, MAX_MOVE_COUNT};
+use crate::{
+    read_packed_move, state::error::GameError, write_packed_move, GameVariant,
+    CASH_OUT_REVEAL_WINDOW, DEFAULT_OFFSET, HASH_LENGTH, MAX_METADATA_LENGTH, MAX_MOVE_COUNT,
+    MAX_MOVE_TYPE_COUNT, MOVE_BIT_WIDTH, PACKED_MOVE_PAGE_LENGTH,
+};
 
 #[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq)]
 pub enum GameSessionStatus {
     Active,
     Won,
     Lost,
+    CashedOut,
 }
 
-#[account]
+impl From<GameSessionStatus> for u8 {
+    fn from(status: GameSessionStatus) -> Self {
+        status as u8
+    }
+}
+
+impl TryFrom<u8> for GameSessionStatus {
+    type Error = GameError;
+
+    fn try_from(status: u8) -> std::result::Result<Self, Self::Error> {
+        match status {
+            0 => Ok(GameSessionStatus::Active),
+            1 => Ok(GameSessionStatus::Won),
+            2 => Ok(GameSessionStatus::Lost),
+            3 => Ok(GameSessionStatus::CashedOut),
+            _ => Err(GameError::InvalidGameSessionStatus),
+        }
+    }
+}
+
+/// How the fail moves of a session are revealed.
+/// - Seed: The private config seed is revealed once the game is over.
+/// - HashChain: The house commits to the head of a reverse hash chain and reveals
+///   a link per round, so each round is settled as soon as it is played.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq)]
+pub enum FairnessMode {
+    Seed,
+    HashChain,
+}
+
+impl From<FairnessMode> for u8 {
+    fn from(fairness_mode: FairnessMode) -> Self {
+        fairness_mode as u8
+    }
+}
+
+impl TryFrom<u8> for FairnessMode {
+    type Error = GameError;
+
+    fn try_from(fairness_mode: u8) -> std::result::Result<Self, Self::Error> {
+        match fairness_mode {
+            0 => Ok(FairnessMode::Seed),
+            1 => Ok(FairnessMode::HashChain),
+            _ => Err(GameError::InvalidFairnessMode),
+        }
+    }
+}
+
+/// How risky each round of the tile ladder is, picked by the player when the session is
+/// created. Each difficulty narrows the variant's MTCFR range and sets how many of the
+/// tiles in a round fail.
+/// - Normal: The variant's range with a single fail tile.
+/// - Easy: `[6 .. 8]` tiles with a single fail tile.
+/// - Hard: `[4 .. 8]` tiles with two fail tiles.
+/// - Expert: `[4 .. 6]` tiles with three fail tiles.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug, Default,
+)]
+pub enum Difficulty {
+    #[default]
+    Normal,
+    Easy,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    /// The inclusive range of tiles in a round, before it is narrowed to the variant's.
+    pub fn get_move_type_count_range(&self) -> (u8, u8) {
+        match self {
+            Difficulty::Normal => (2, MAX_MOVE_TYPE_COUNT as u8),
+            Difficulty::Easy => (6, 8),
+            Difficulty::Hard => (4, 8),
+            Difficulty::Expert => (4, 6),
+        }
+    }
+
+    /// The number of tiles that fail in each round.
+    pub fn get_fail_move_count(&self) -> u8 {
+        match self {
+            Difficulty::Normal | Difficulty::Easy => 1,
+            Difficulty::Hard => 2,
+            Difficulty::Expert => 3,
+        }
+    }
+}
+
+impl From<Difficulty> for u8 {
+    fn from(difficulty: Difficulty) -> Self {
+        difficulty as u8
+    }
+}
+
+impl TryFrom<u8> for Difficulty {
+    type Error = GameError;
+
+    fn try_from(difficulty: u8) -> std::result::Result<Self, Self::Error> {
+        match difficulty {
+            0 => Ok(Difficulty::Normal),
+            1 => Ok(Difficulty::Easy),
+            2 => Ok(Difficulty::Hard),
+            3 => Ok(Difficulty::Expert),
+            _ => Err(GameError::InvalidDifficulty),
+        }
+    }
+}
+
+/// How the fail tiles of a round are derived when there is more than one.
+/// - Consecutive: The tiles following `FMFR`, wrapping around. Sessions created before
+///   the derivation was versioned read as this.
+/// - PartialShuffle: The first tiles of a shuffle of the round's tiles seeded by the
+///   per-round hash of the secret seed, the first of them is `FMFR`.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FailMoveDerivation {
+    Consecutive,
+    PartialShuffle,
+}
+
+impl From<FailMoveDerivation> for u8 {
+    fn from(fail_move_derivation: FailMoveDerivation) -> Self {
+        fail_move_derivation as u8
+    }
+}
+
+impl TryFrom<u8> for FailMoveDerivation {
+    type Error = GameError;
+
+    fn try_from(fail_move_derivation: u8) -> std::result::Result<Self, Self::Error> {
+        match fail_move_derivation {
+            0 => Ok(FailMoveDerivation::Consecutive),
+            1 => Ok(FailMoveDerivation::PartialShuffle),
+            _ => Err(GameError::InvalidFailMoveDerivation),
+        }
+    }
+}
+
+#[account(zero_copy)]
 #[derive(InitSpace)]
 /// Represents a game session for a player.
+/// The layout is fixed-size so the account can be read without deserializing it,
+/// fields are ordered to avoid any implicit padding.
+/// The moves the player made are bit-packed, each taking the bit width of the session's
+/// variant(`MOVE_BIT_WIDTH` for the tile ladder), and
+/// stored right after the session in the account data, that storage starts with a
+/// single page and is grown by a page at a time as the player makes moves.
 pub struct GameSession {
     pub last_action_time: i64,
-    pub player: Pubkey,
     pub deposit: u64,
-    pub status: GameSessionStatus,
+    /// The time the player cashed out, the house has `CASH_OUT_REVEAL_WINDOW` from
+    /// then to prove one of the recorded moves failed.
+    pub cash_out_time: i64,
+    /// The time the session was started.
+    pub start_time: i64,
+    /// The time the session can be defaulted after its last action, taken from the
+    /// global state when the session was started.
+    pub default_offset: i64,
+    /// The time after its start the session can be defaulted regardless of its activity,
+    /// taken from the global state when the session was started, 0 is no limit.
+    pub max_lifetime: i64,
+    pub player: Pubkey,
+    pub vault: Pubkey,
     /// A SHA-256 hash seed used to derive the public configuration of the game,
     /// which is known to both the player and the game system.
     pub public_config_seed: [u8; HASH_LENGTH],
+    /// In `FairnessMode::HashChain` this is the last link of the hash chain that was
+    /// revealed, it starts out as the committed head of the chain.
+    pub hash_chain_link: [u8; HASH_LENGTH],
+    /// A seed picked by the player once the house committed to its seed, variants that
+    /// use it mix it into their outcome so neither side decides it alone.
+    pub client_seed: [u8; HASH_LENGTH],
+    /// In auto-play sessions this is the player's commitment to their planned moves,
+    /// they are revealed all at once through `reveal_auto_play`.
+    pub auto_play_commitment: [u8; HASH_LENGTH],
     /// Arbitrary metadata about the game, such as the algorithm version,
-    /// configuration parameters, etc. Limited to 64 bytes.
-    #[max_len(64)]
-    pub game_metadata: String,
-    /// Stores the set of moves the user made while it was active, it is filled in
-    /// when the user makes a move
-    pub player_moves: [u8; MAX_MOVE_COUNT],
-
-    pub vault: Pubkey,
-
+    /// configuration parameters, etc. Only the first `game_metadata_length`
+    /// bytes are meaningful.
+    pub game_metadata: [u8; MAX_METADATA_LENGTH],
+    pub game_metadata_length: u8,
+    /// Stores a `GameSessionStatus`, use `get_status` and `set_status` to access it.
+    pub status: u8,
     /// Stores the next position for the player move
-    pub next_player_move_position: u8,
+    pub next_player_move_position: u16,
+    /// The number of rounds whose outcome has been revealed in `FairnessMode::HashChain`.
+    pub revealed_round_count: u16,
+    /// Stores a `FairnessMode`, use `get_fairness_mode` to access it.
+    pub fairness_mode: u8,
+    /// Stores a `GameVariant`, use `get_variant` to access it. It was padding before
+    /// variants were added so older sessions read as `GameVariant::Tiles`.
+    pub variant: u8,
+    /// Stores a `Difficulty`, use `get_difficulty` to access it.
+    pub difficulty: u8,
+    /// Stores a `FailMoveDerivation`, use `get_fail_move_derivation` to access it.
+    pub fail_move_derivation: u8,
+    /// Set if the session is played through `reveal_auto_play` instead of `record_action`.
+    pub auto_play: u8,
+    /// Set if the session is a tournament entry, its `vault` is then the tournament and
+    /// it is scored through `submit_tournament_score` instead of being settled.
+    pub tournament: u8,
+    /// Set if the session is played on a daily challenge's shared seed, it is settled
+    /// through `settle_daily_challenge` once the challenge is revealed.
+    pub daily_challenge: u8,
+    /// Set if the session is a practice session, it has no deposit and no vault and is
+    /// closed through `reveal_practice_game` instead of being settled.
+    pub practice: u8,
+    pub padding: [u8; 2],
 }
 
 impl GameSession {
@@ -42,22 +229,146 @@ impl GameSession {
         deposit: u64,
         vault: Pubkey,
         public_config_seed: [u8; HASH_LENGTH],
-        game_metadata: String,
+        game_metadata: &[u8],
         now: i64,
     ) -> Self {
+        // The metadata length is expected to have been checked by the caller.
+        let game_metadata_length = game_metadata.len().min(MAX_METADATA_LENGTH);
+
+        let mut metadata = [0; MAX_METADATA_LENGTH];
+        metadata[..game_metadata_length].copy_from_slice(&game_metadata[..game_metadata_length]);
+
         Self {
             last_action_time: now,
-            player,
             deposit,
+            cash_out_time: 0,
+            start_time: now,
+            default_offset: DEFAULT_OFFSET,
+            max_lifetime: 0,
+            player,
             vault,
-            status: GameSessionStatus::Active,
-            public_config_seed: public_config_seed,
-            game_metadata,
-            player_moves: [0; MAX_MOVE_COUNT],
+            public_config_seed,
+            hash_chain_link: [0; HASH_LENGTH],
+            client_seed: [0; HASH_LENGTH],
+            auto_play_commitment: [0; HASH_LENGTH],
+            game_metadata: metadata,
+            game_metadata_length: game_metadata_length as u8,
+            status: GameSessionStatus::Active.into(),
             next_player_move_position: 0,
+            revealed_round_count: 0,
+            fairness_mode: FairnessMode::Seed.into(),
+            variant: GameVariant::Tiles.into(),
+            difficulty: Difficulty::Normal.into(),
+            fail_move_derivation: FailMoveDerivation::PartialShuffle.into(),
+            auto_play: 0,
+            tournament: 0,
+            daily_challenge: 0,
+            practice: 0,
+            padding: [0; 2],
         }
     }
 
+    /// Switches the session to `FairnessMode::HashChain` with `hash_chain_head` as the
+    /// committed head of the chain.
+    pub fn enable_hash_chain(&mut self, hash_chain_head: [u8; HASH_LENGTH]) {
+        self.fairness_mode = FairnessMode::HashChain.into();
+        self.hash_chain_link = hash_chain_head;
+    }
+
+    /// Commits the session to the moves it will reveal through `reveal_auto_play`.
+    pub fn enable_auto_play(&mut self, auto_play_commitment: [u8; HASH_LENGTH]) {
+        self.auto_play = 1;
+        self.auto_play_commitment = auto_play_commitment;
+    }
+
+    pub fn is_auto_play(&self) -> bool {
+        self.auto_play != 0
+    }
+
+    /// Links the session to `tournament`, which holds the entry fee instead of a vault.
+    pub fn enter_tournament(&mut self, tournament: Pubkey) {
+        self.tournament = 1;
+        self.vault = tournament;
+    }
+
+    pub fn is_tournament(&self) -> bool {
+        self.tournament != 0
+    }
+
+    pub fn enter_daily_challenge(&mut self) {
+        self.daily_challenge = 1;
+    }
+
+    pub fn is_daily_challenge(&self) -> bool {
+        self.daily_challenge != 0
+    }
+
+    /// Makes the session a practice session, it has nothing at stake so it has no vault.
+    pub fn enable_practice(&mut self) {
+        self.practice = 1;
+        self.vault = Pubkey::default();
+    }
+
+    pub fn is_practice(&self) -> bool {
+        self.practice != 0
+    }
+
+    pub fn set_variant(&mut self, variant: GameVariant) {
+        self.variant = variant.into();
+    }
+
+    pub fn get_variant(&self) -> Result<GameVariant> {
+        Ok(GameVariant::try_from(self.variant)?)
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty.into();
+    }
+
+    /// The difficulty the session is played at, unknown values read as
+    /// `Difficulty::Normal`.
+    pub fn get_difficulty(&self) -> Difficulty {
+        Difficulty::try_from(self.difficulty).unwrap_or_default()
+    }
+
+    /// How the session's fail tiles are derived, unknown values read as
+    /// `FailMoveDerivation::Consecutive`.
+    pub fn get_fail_move_derivation(&self) -> FailMoveDerivation {
+        FailMoveDerivation::try_from(self.fail_move_derivation)
+            .unwrap_or(FailMoveDerivation::Consecutive)
+    }
+
+    /// The number of bits each of the session's moves takes in the move storage.
+    pub fn get_move_bit_width(&self) -> usize {
+        GameVariant::try_from(self.variant)
+            .map_or(MOVE_BIT_WIDTH, |variant| variant.get_move_bit_width())
+    }
+
+    /// The offset of the bit-packed moves within the account data.
+    pub const PLAYER_MOVES_OFFSET: usize = 8 + core::mem::size_of::<GameSession>();
+
+    /// The space for a newly created session, it holds a single page of moves.
+    pub const INITIAL_SPACE: usize = GameSession::PLAYER_MOVES_OFFSET + PACKED_MOVE_PAGE_LENGTH;
+
+    /// Splits the data of a game session account into the session and its moves,
+    /// the discriminator is expected to have been checked by the caller.
+    pub fn split_account_data(data: &[u8]) -> (&GameSession, &[u8]) {
+        let (game_session, player_moves) = data.split_at(GameSession::PLAYER_MOVES_OFFSET);
+
+        (bytemuck::from_bytes(&game_session[8..]), player_moves)
+    }
+
+    /// Splits the data of a game session account into the session and its moves,
+    /// the discriminator is expected to have been checked by the caller.
+    pub fn split_account_data_mut(data: &mut [u8]) -> (&mut GameSession, &mut [u8]) {
+        let (game_session, player_moves) = data.split_at_mut(GameSession::PLAYER_MOVES_OFFSET);
+
+        (
+            bytemuck::from_bytes_mut(&mut game_session[8..]),
+            player_moves,
+        )
+    }
+
     pub fn is_vault_for_game(&self, vault: &Pubkey) -> bool {
         self.vault.eq(vault)
     }
@@ -66,28 +377,147 @@ impl GameSession {
         self.player.eq(player)
     }
 
+    /// Sets the windows the session can be defaulted after, they are kept for the life of
+    /// the session so later changes to the global state don't affect it.
+    pub fn set_default_windows(&mut self, default_offset: i64, max_lifetime: i64) {
+        self.default_offset = default_offset;
+        self.max_lifetime = max_lifetime;
+    }
+
     pub fn can_default(&self, now: i64) -> bool {
-        now.gt(&self.last_action_time.add(DEFAULT_OFFSET))
+        now.gt(&self.last_action_time.saturating_add(self.default_offset))
+            || (self.max_lifetime > 0 && now.gt(&self.start_time.saturating_add(self.max_lifetime)))
+    }
+
+    pub fn get_status(&self) -> Result<GameSessionStatus> {
+        Ok(GameSessionStatus::try_from(self.status)?)
+    }
+
+    pub fn set_status(&mut self, status: GameSessionStatus) {
+        self.status = status.into();
     }
 
     pub fn is_active(&self) -> bool {
-        self.status == crate::GameSessionStatus::Active
+        self.status == u8::from(GameSessionStatus::Active)
     }
 
     pub fn is_won(&self) -> bool {
-        self.status == crate::GameSessionStatus::Won
+        self.status == u8::from(GameSessionStatus::Won)
     }
 
-    pub fn set_next_player_move(&mut self, player_move: u8) -> Result<()> {
-        let player_move_position = usize::from(self.next_player_move_position);
+    pub fn is_cashed_out(&self) -> bool {
+        self.status == u8::from(GameSessionStatus::CashedOut)
+    }
+
+    /// Freezes the move history, starting the window the house has to reveal the
+    /// private config seed in.
+    pub fn cash_out(&mut self, now: i64) {
+        self.set_status(GameSessionStatus::CashedOut);
+        self.cash_out_time = now;
+        self.last_action_time = now;
+    }
+
+    pub fn get_reveal_deadline(&self) -> i64 {
+        self.cash_out_time.add(CASH_OUT_REVEAL_WINDOW)
+    }
+
+    pub fn is_reveal_window_expired(&self, now: i64) -> bool {
+        now.gt(&self.get_reveal_deadline())
+    }
+
+    pub fn get_fairness_mode(&self) -> Result<FairnessMode> {
+        Ok(FairnessMode::try_from(self.fairness_mode)?)
+    }
+
+    pub fn is_hash_chain(&self) -> bool {
+        self.fairness_mode == u8::from(FairnessMode::HashChain)
+    }
+
+    /// Checks if a move has been recorded whose outcome has not been revealed yet,
+    /// only meaningful in `FairnessMode::HashChain`.
+    pub fn has_unrevealed_round(&self) -> bool {
+        self.revealed_round_count < self.next_player_move_position
+    }
+
+    pub fn get_game_metadata(&self) -> &[u8] {
+        &self.game_metadata[..usize::from(self.game_metadata_length).min(MAX_METADATA_LENGTH)]
+    }
+
+    pub fn get_player_move_count(&self) -> usize {
+        usize::from(self.next_player_move_position)
+    }
+
+    /// Checks if there is no room left in `player_moves` for the next move.
+    pub fn is_move_storage_full(&self, player_moves: &[u8]) -> bool {
+        (self.get_player_move_count() + 1) * self.get_move_bit_width() > player_moves.len() * 8
+    }
+
+    /// Returns the move recorded at `position` in `player_moves`, or `None` if no
+    /// move has been recorded there yet.
+    pub fn get_player_move(&self, player_moves: &[u8], position: usize) -> Option<u8> {
+        if position.ge(&self.get_player_move_count()) {
+            return None;
+        }
+
+        read_packed_move(player_moves, position, self.get_move_bit_width())
+    }
+
+    /// Records the next move in `player_moves`, the storage is expected to have been
+    /// grown by the caller if it was full.
+    pub fn set_next_player_move(&mut self, player_moves: &mut [u8], player_move: u8) -> Result<()> {
+        let player_move_position = self.get_player_move_count();
 
         if player_move_position.ge(&MAX_MOVE_COUNT) {
             return Err(GameError::MaxMoveReached.into());
         }
 
-        self.player_moves[player_move_position] = player_move;
+        let move_bit_width = self.get_move_bit_width();
+
+        require_gt!(
+            1usize << move_bit_width,
+            usize::from(player_move),
+            GameError::InvalidMove
+        );
+
+        write_packed_move(
+            player_moves,
+            player_move_position,
+            player_move,
+            move_bit_width,
+        )
+        .ok_or(GameError::MoveStorageFull)?;
+
         // Overflow not possible it is bounded
         self.next_player_move_position += 1;
         Ok(())
     }
 }
+
+/// The layout of a game session before it was made zero-copy, sessions left in it can't
+/// be played or settled and are refunded through `refund_legacy_game_session`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct LegacyGameSession {
+    pub last_action_time: i64,
+    pub player: Pubkey,
+    pub deposit: u64,
+    pub status: GameSessionStatus,
+    pub public_config_seed: [u8; HASH_LENGTH],
+    pub game_metadata: String,
+    pub player_moves: [u8; LegacyGameSession::MAX_MOVE_COUNT],
+    pub vault: Pubkey,
+    pub next_player_move_position: u8,
+}
+
+impl LegacyGameSession {
+    pub const MAX_MOVE_COUNT: usize = 64;
+
+    pub const SPACE: usize = 8
+        + 32
+        + 8
+        + 1
+        + HASH_LENGTH
+        + (4 + MAX_METADATA_LENGTH)
+        + LegacyGameSession::MAX_MOVE_COUNT
+        + 32
+        + 1;
+}
@@ -0,0 +1,99 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::{JackpotPayoutEvent, HASH_LENGTH, JACKPOT_SEED, MAX_BPS, MAX_JACKPOT_CONTRIBUTION};
+
+#[account]
+#[derive(InitSpace)]
+/// The progressive jackpot at `[b"jackpot"]`, it holds its pool itself so the pool is kept
+/// apart from the global vault and its `max_payout`.
+/// A share of every deposit is added to the pool, a won session whose private config seed
+/// is revealed hits it with odds of `1 / odds`, and one that survived `move_threshold`
+/// moves always hits it.
+pub struct Jackpot {
+    pub contribution: u16, // In bps
+    pub odds: u32,
+    /// 0 is no move threshold.
+    pub move_threshold: u16,
+    /// The lamports in the pool, on top of the account's rent.
+    pub balance: u64,
+    pub total_contributed: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl Jackpot {
+    pub fn new(contribution: u16, odds: u32, move_threshold: u16, bump: u8) -> Self {
+        Self {
+            contribution,
+            odds,
+            move_threshold,
+            balance: 0,
+            total_contributed: 0,
+            total_paid: 0,
+            bump,
+        }
+    }
+
+    pub fn are_parameters_valid(contribution: u16, odds: u32) -> bool {
+        contribution <= MAX_JACKPOT_CONTRIBUTION && odds > 0
+    }
+
+    /// The share of `deposit` added to the pool.
+    pub fn get_contribution(&self, deposit: u64) -> u64 {
+        (u128::from(deposit) * u128::from(self.contribution) / u128::from(MAX_BPS)) as u64
+    }
+
+    /// Checks if a session with `private_config_seed` hits the jackpot, that is if
+    /// `LE64( HASH("jackpot" + HRS + client_seed) ) % odds == 0`. The client seed is picked
+    /// once the house committed to `HRS`, so the house can't pick a seed that never hits.
+    pub fn is_hit(
+        &self,
+        private_config_seed: &[u8; HASH_LENGTH],
+        client_seed: &[u8; HASH_LENGTH],
+    ) -> bool {
+        let jackpot_hash = hashv(&[
+            JACKPOT_SEED.as_ref(),
+            private_config_seed.as_ref(),
+            client_seed.as_ref(),
+        ])
+        .to_bytes();
+
+        let mut sample = [0u8; 8];
+        sample.copy_from_slice(&jackpot_hash[..8]);
+
+        u64::from_le_bytes(sample) % u64::from(self.odds.max(1)) == 0
+    }
+
+    /// Checks if a session that survived `move_count` moves hits the jackpot.
+    pub fn is_hit_by_moves(&self, move_count: usize) -> bool {
+        self.move_threshold > 0 && move_count >= usize::from(self.move_threshold)
+    }
+}
+
+/// Pays the whole pool of the jackpot to the player of a session that hit it, out of the
+/// jackpot's own account, and returns the amount paid.
+pub fn pay_out_jackpot<'info>(
+    jackpot: &mut Account<'info, Jackpot>,
+    player: &AccountInfo<'info>,
+    game_session: Pubkey,
+) -> Result<u64> {
+    let amount = jackpot.balance;
+
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    **jackpot.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **player.try_borrow_mut_lamports()? += amount;
+
+    jackpot.balance = 0;
+    jackpot.total_paid += amount;
+
+    emit!(JackpotPayoutEvent {
+        game_session,
+        player: player.key(),
+        amount
+    });
+
+    Ok(amount)
+}
@@ -12,3 +12,27 @@ pub use constants::*;
 
 pub mod event;
 pub use event::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod crash;
+pub use crash::*;
+
+pub mod tournament;
+pub use tournament::*;
+
+pub mod jackpot;
+pub use jackpot::*;
+
+pub mod daily_challenge;
+pub use daily_challenge::*;
+
+pub mod player_profile;
+pub use player_profile::*;
+
+pub mod referral;
+pub use referral::*;
+
+pub mod fee_vault;
+pub use fee_vault::*;
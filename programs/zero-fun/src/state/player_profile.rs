@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, DEPOSIT_LIMIT_DAY, DEPOSIT_LIMIT_MONTH, DEPOSIT_LIMIT_WEEK};
+
+/// Limits a player puts on their own play, a limit of 0 is no limit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct PlayerLimits {
+    pub daily_deposit_limit: u64,
+    pub weekly_deposit_limit: u64,
+    pub monthly_deposit_limit: u64,
+    pub max_active_sessions: u32,
+}
+
+impl PlayerLimits {
+    /// Whether `limit` is at most as permissive as `current`.
+    #[inline(always)]
+    fn is_at_least_as_tight<T: PartialOrd + Default>(limit: T, current: T) -> bool {
+        let none = T::default();
+
+        current.eq(&none) || (limit.ne(&none) && limit <= current)
+    }
+
+    /// Whether every limit in `self` is at most as permissive as in `current`.
+    pub fn is_at_least_as_tight_as(&self, current: &PlayerLimits) -> bool {
+        Self::is_at_least_as_tight(self.daily_deposit_limit, current.daily_deposit_limit)
+            && Self::is_at_least_as_tight(self.weekly_deposit_limit, current.weekly_deposit_limit)
+            && Self::is_at_least_as_tight(self.monthly_deposit_limit, current.monthly_deposit_limit)
+            && Self::is_at_least_as_tight(self.max_active_sessions, current.max_active_sessions)
+    }
+
+    /// The tightest of each limit in `self` and `other`.
+    pub fn tightest(&self, other: &PlayerLimits) -> PlayerLimits {
+        #[inline(always)]
+        fn tightest<T: Ord + Default + Copy>(a: T, b: T) -> T {
+            if a.eq(&T::default()) {
+                b
+            } else if b.eq(&T::default()) {
+                a
+            } else {
+                a.min(b)
+            }
+        }
+
+        PlayerLimits {
+            daily_deposit_limit: tightest(self.daily_deposit_limit, other.daily_deposit_limit),
+            weekly_deposit_limit: tightest(self.weekly_deposit_limit, other.weekly_deposit_limit),
+            monthly_deposit_limit: tightest(
+                self.monthly_deposit_limit,
+                other.monthly_deposit_limit,
+            ),
+            max_active_sessions: tightest(self.max_active_sessions, other.max_active_sessions),
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+/// A player's lifetime statistics at `[b"player-profile", player]`.
+/// It is created with the player's first session and updated by every instruction that
+/// settles one, so the stats can be read without going through the events.
+pub struct PlayerProfile {
+    pub player: Pubkey,
+    pub games_played: u64,
+    pub games_won: u64,
+    pub games_lost: u64,
+    /// Sessions closed without an outcome, the deposit was refunded.
+    pub games_defaulted: u64,
+    /// Every deposit the player made.
+    pub total_wagered: u64,
+    /// Every lamport paid back to the player, deposits included, so the player's net
+    /// result is `total_paid_out - total_wagered`.
+    pub total_paid_out: u64,
+    /// The most games won in a row.
+    pub longest_streak: u32,
+    pub current_streak: u32,
+    /// The sessions started that are yet to be settled.
+    pub active_sessions: u32,
+    /// The limits in force.
+    pub limits: PlayerLimits,
+    /// Looser limits waiting out the cooling-off period, they replace `limits` from
+    /// `pending_limits_time` on.
+    pub pending_limits: Option<PlayerLimits>,
+    pub pending_limits_time: i64,
+    /// No session can be started before this time.
+    pub excluded_until: i64,
+    /// The deposits made in the current day, week and month, the periods are counted from
+    /// the unix epoch and the deposits are reset as a new one starts.
+    pub daily_deposited: u64,
+    pub weekly_deposited: u64,
+    pub monthly_deposited: u64,
+    pub last_deposit_time: i64,
+    /// The player who referred this player, `Pubkey::default()` if none, it can only be
+    /// set once.
+    pub referrer: Pubkey,
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    pub fn new(player: Pubkey, bump: u8) -> Self {
+        Self {
+            player,
+            games_played: 0,
+            games_won: 0,
+            games_lost: 0,
+            games_defaulted: 0,
+            total_wagered: 0,
+            total_paid_out: 0,
+            longest_streak: 0,
+            current_streak: 0,
+            active_sessions: 0,
+            limits: PlayerLimits::default(),
+            pending_limits: None,
+            pending_limits_time: 0,
+            excluded_until: 0,
+            daily_deposited: 0,
+            weekly_deposited: 0,
+            monthly_deposited: 0,
+            last_deposit_time: 0,
+            referrer: Pubkey::default(),
+            bump,
+        }
+    }
+
+    /// Sets up a profile created by `init_if_needed`, existing profiles are left as is.
+    pub fn initialize_if_needed(&mut self, player: Pubkey, bump: u8) {
+        if self.player.eq(&Pubkey::default()) {
+            *self = Self::new(player, bump);
+        }
+    }
+
+    pub fn has_referrer(&self) -> bool {
+        self.referrer.ne(&Pubkey::default())
+    }
+
+    /// The limits in force at `now`, pending limits count once their cooling-off period
+    /// has passed.
+    pub fn get_limits(&self, now: i64) -> PlayerLimits {
+        match self.pending_limits {
+            Some(pending_limits) if now >= self.pending_limits_time => pending_limits,
+            _ => self.limits,
+        }
+    }
+
+    /// Moves the pending limits in force once their cooling-off period has passed.
+    pub fn apply_pending_limits(&mut self, now: i64) {
+        self.limits = self.get_limits(now);
+
+        if now >= self.pending_limits_time {
+            self.pending_limits = None;
+        }
+    }
+
+    /// Tighter limits take effect immediately, while any limit being loosened waits out
+    /// `cooling_off_period`, until then the tightest of the old and new limits hold.
+    /// Returns whether the limits are pending.
+    pub fn set_limits(&mut self, limits: PlayerLimits, now: i64, cooling_off_period: i64) -> bool {
+        self.apply_pending_limits(now);
+
+        if limits.is_at_least_as_tight_as(&self.limits) {
+            self.limits = limits;
+            self.pending_limits = None;
+
+            false
+        } else {
+            self.limits = self.limits.tightest(&limits);
+            self.pending_limits = Some(limits);
+            self.pending_limits_time = now.saturating_add(cooling_off_period);
+
+            true
+        }
+    }
+
+    /// Exclusions can only be extended, never shortened.
+    pub fn self_exclude(&mut self, now: i64, period: i64) {
+        self.excluded_until = self.excluded_until.max(now.saturating_add(period));
+    }
+
+    pub fn is_excluded(&self, now: i64) -> bool {
+        now < self.excluded_until
+    }
+
+    /// The deposits made in the current (day, week, month) at `now`.
+    pub fn get_deposited(&self, now: i64) -> (u64, u64, u64) {
+        let deposited_in = |deposited: u64, period: i64| {
+            if self.last_deposit_time.div_euclid(period) == now.div_euclid(period) {
+                deposited
+            } else {
+                0
+            }
+        };
+
+        (
+            deposited_in(self.daily_deposited, DEPOSIT_LIMIT_DAY),
+            deposited_in(self.weekly_deposited, DEPOSIT_LIMIT_WEEK),
+            deposited_in(self.monthly_deposited, DEPOSIT_LIMIT_MONTH),
+        )
+    }
+
+    /// Checks a session with `deposit` can be started at `now` within the player's limits.
+    pub fn check_limits(&self, deposit: u64, now: i64) -> Result<()> {
+        require!(!self.is_excluded(now), GameError::PlayerSelfExcluded);
+
+        let limits = self.get_limits(now);
+
+        require!(
+            limits.max_active_sessions == 0 || self.active_sessions < limits.max_active_sessions,
+            GameError::TooManyActiveSessions
+        );
+
+        let (daily_deposited, weekly_deposited, monthly_deposited) = self.get_deposited(now);
+
+        let is_within =
+            |deposited: u64, limit: u64| limit == 0 || deposited.saturating_add(deposit) <= limit;
+
+        require!(
+            is_within(daily_deposited, limits.daily_deposit_limit)
+                && is_within(weekly_deposited, limits.weekly_deposit_limit)
+                && is_within(monthly_deposited, limits.monthly_deposit_limit),
+            GameError::DepositLimitExceeded
+        );
+
+        Ok(())
+    }
+
+    pub fn record_game_started(&mut self, deposit: u64, now: i64) {
+        let (daily_deposited, weekly_deposited, monthly_deposited) = self.get_deposited(now);
+
+        self.daily_deposited = daily_deposited.saturating_add(deposit);
+        self.weekly_deposited = weekly_deposited.saturating_add(deposit);
+        self.monthly_deposited = monthly_deposited.saturating_add(deposit);
+        self.last_deposit_time = now;
+
+        self.games_played = self.games_played.saturating_add(1);
+        self.total_wagered = self.total_wagered.saturating_add(deposit);
+        self.active_sessions = self.active_sessions.saturating_add(1);
+    }
+
+    pub fn record_game_won(&mut self, paid_out: u64) {
+        self.games_won = self.games_won.saturating_add(1);
+        self.total_paid_out = self.total_paid_out.saturating_add(paid_out);
+        self.current_streak = self.current_streak.saturating_add(1);
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+        self.active_sessions = self.active_sessions.saturating_sub(1);
+    }
+
+    pub fn record_game_lost(&mut self) {
+        self.games_lost = self.games_lost.saturating_add(1);
+        self.current_streak = 0;
+        self.active_sessions = self.active_sessions.saturating_sub(1);
+    }
+
+    /// Sessions closed with no outcome of their own, such as scored tournament entries.
+    pub fn record_game_ended(&mut self) {
+        self.active_sessions = self.active_sessions.saturating_sub(1);
+    }
+
+    pub fn record_game_defaulted(&mut self, refunded: u64) {
+        self.games_defaulted = self.games_defaulted.saturating_add(1);
+        self.total_paid_out = self.total_paid_out.saturating_add(refunded);
+        self.active_sessions = self.active_sessions.saturating_sub(1);
+    }
+}
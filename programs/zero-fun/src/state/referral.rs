@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameError, GlobalState, PlayerProfile, ReferralRewardEvent};
+
+#[account]
+#[derive(InitSpace)]
+/// The rewards earned by a referrer at `[b"referrer-rewards", referrer]`, the unclaimed
+/// rewards are held as lamports by the account itself.
+pub struct ReferrerRewards {
+    pub referrer: Pubkey,
+    pub referral_count: u32,
+    pub unclaimed: u64,
+    pub total_earned: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl ReferrerRewards {
+    pub fn new(referrer: Pubkey, bump: u8) -> Self {
+        Self {
+            referrer,
+            referral_count: 0,
+            unclaimed: 0,
+            total_earned: 0,
+            total_claimed: 0,
+            bump,
+        }
+    }
+
+    /// Sets up an account created by `init_if_needed`, existing accounts are left as is.
+    pub fn initialize_if_needed(&mut self, referrer: Pubkey, bump: u8) {
+        if self.referrer.eq(&Pubkey::default()) {
+            *self = Self::new(referrer, bump);
+        }
+    }
+}
+
+/// Moves the referral reward for a settled session with `deposit` out of the global vault
+/// into the referrer's rewards account, if the player was referred.
+pub fn accrue_referral_reward<'info>(
+    player_profile: &PlayerProfile,
+    referrer_rewards: Option<&mut Account<'info, ReferrerRewards>>,
+    vault: &AccountInfo<'info>,
+    global_state: &mut GlobalState,
+    game_session: Pubkey,
+    deposit: u64,
+) -> Result<()> {
+    if !player_profile.has_referrer() {
+        return Ok(());
+    }
+
+    let referrer_rewards = referrer_rewards.ok_or(GameError::InvalidReferrerRewards)?;
+
+    require_keys_eq!(
+        referrer_rewards.referrer,
+        player_profile.referrer,
+        GameError::InvalidReferrerRewards
+    );
+
+    let amount = global_state.get_referral_reward(deposit);
+
+    if amount == 0 {
+        return Ok(());
+    }
+
+    **vault.try_borrow_mut_lamports()? -= amount;
+
+    global_state.record_paid_out(amount);
+    **referrer_rewards
+        .to_account_info()
+        .try_borrow_mut_lamports()? += amount;
+
+    referrer_rewards.unclaimed = referrer_rewards.unclaimed.saturating_add(amount);
+    referrer_rewards.total_earned = referrer_rewards.total_earned.saturating_add(amount);
+
+    emit!(ReferralRewardEvent {
+        referrer: referrer_rewards.referrer,
+        player: player_profile.player,
+        game_session,
+        amount
+    });
+
+    Ok(())
+}
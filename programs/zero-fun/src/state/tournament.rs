@@ -0,0 +1,149 @@
+use std::ops::Add;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    GameVariant, HASH_LENGTH, MAX_BPS, MAX_TOURNAMENT_WINNERS, TOURNAMENT_REVEAL_WINDOW,
+    TOURNAMENT_SCORING_WINDOW,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TournamentStatus {
+    Open,
+    Revealed,
+    Distributed,
+}
+
+/// A place on a tournament's leaderboard.
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub rounds_survived: u16,
+}
+
+#[account]
+#[derive(InitSpace)]
+/// A tournament at `[b"tournament", tournament_id]`.
+/// Every entrant plays a session on the same committed public config seed, so they all
+/// face the same board. Once the house reveals the private config seed the sessions are
+/// scored by the rounds they survived and the entry fees are paid out to the top places.
+pub struct Tournament {
+    pub tournament_id: u64,
+    pub variant: GameVariant,
+    pub entry_fee: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// The time the private config seed was revealed, the scoring window starts then.
+    pub reveal_time: i64,
+    pub public_config_seed: [u8; HASH_LENGTH],
+    /// Only meaningful once the tournament is revealed.
+    pub private_config_seed: [u8; HASH_LENGTH],
+    /// The share of the prize pool each place is paid(in bps), the rest is kept by the
+    /// house.
+    #[max_len(MAX_TOURNAMENT_WINNERS)]
+    pub prize_shares: Vec<u16>,
+    /// The best scores submitted so far, sorted from the best, at most one per place.
+    #[max_len(MAX_TOURNAMENT_WINNERS)]
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub prize_pool: u64,
+    pub entry_count: u32,
+    pub status: TournamentStatus,
+    pub bump: u8,
+}
+
+impl Tournament {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        tournament_id: u64,
+        variant: GameVariant,
+        entry_fee: u64,
+        start_time: i64,
+        end_time: i64,
+        public_config_seed: [u8; HASH_LENGTH],
+        prize_shares: Vec<u16>,
+        bump: u8,
+    ) -> Self {
+        Self {
+            tournament_id,
+            variant,
+            entry_fee,
+            start_time,
+            end_time,
+            reveal_time: 0,
+            public_config_seed,
+            private_config_seed: [0; HASH_LENGTH],
+            prize_shares,
+            leaderboard: Vec::new(),
+            prize_pool: 0,
+            entry_count: 0,
+            status: TournamentStatus::Open,
+            bump,
+        }
+    }
+
+    /// Checks the prize shares pay at least one place and at most the whole pool.
+    pub fn are_prize_shares_valid(prize_shares: &[u16]) -> bool {
+        !prize_shares.is_empty()
+            && prize_shares.len() <= MAX_TOURNAMENT_WINNERS
+            && prize_shares
+                .iter()
+                .map(|prize_share| u64::from(*prize_share))
+                .sum::<u64>()
+                <= MAX_BPS
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status.eq(&TournamentStatus::Open)
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.status.eq(&TournamentStatus::Revealed)
+    }
+
+    pub fn is_running(&self, now: i64) -> bool {
+        self.is_open() && now.ge(&self.start_time) && now.le(&self.end_time)
+    }
+
+    pub fn has_ended(&self, now: i64) -> bool {
+        now.gt(&self.end_time)
+    }
+
+    pub fn is_reveal_window_expired(&self, now: i64) -> bool {
+        now.gt(&self.end_time.add(TOURNAMENT_REVEAL_WINDOW))
+    }
+
+    pub fn is_scoring_window_expired(&self, now: i64) -> bool {
+        now.gt(&self.reveal_time.add(TOURNAMENT_SCORING_WINDOW))
+    }
+
+    /// Places `player` on the leaderboard if `rounds_survived` beats one of its scores,
+    /// ties keep the score that was submitted first.
+    pub fn submit_score(&mut self, player: Pubkey, rounds_survived: u16) {
+        let position = self
+            .leaderboard
+            .iter()
+            .position(|entry| entry.rounds_survived < rounds_survived)
+            .unwrap_or(self.leaderboard.len());
+
+        if position >= self.prize_shares.len() {
+            return;
+        }
+
+        self.leaderboard.insert(
+            position,
+            LeaderboardEntry {
+                player,
+                rounds_survived,
+            },
+        );
+
+        self.leaderboard.truncate(self.prize_shares.len());
+    }
+
+    /// The prize for `place`, a share of the prize pool.
+    pub fn get_prize(&self, place: usize) -> u64 {
+        let prize_share = self.prize_shares.get(place).copied().unwrap_or(0);
+
+        (u128::from(self.prize_pool) * u128::from(prize_share) / u128::from(MAX_BPS)) as u64
+    }
+}
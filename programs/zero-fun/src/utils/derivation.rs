@@ -0,0 +1,137 @@
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::{Difficulty, GameRules, GameSession, HASH_LENGTH, MAX_BPS, MAX_CRASH_POINT};
+
+/// Returns the bytes a round index contributes to the per-round hashes, rounds below
+/// 256 keep using a single byte so earlier sessions still verify, later rounds use two
+/// little-endian bytes. The two never collide as the hashed inputs differ in length.
+pub fn get_round_seed(round_bytes: &[u8; 2]) -> &[u8] {
+    if round_bytes[1] == 0 {
+        &round_bytes[..1]
+    } else {
+        &round_bytes[..]
+    }
+}
+
+/// Hashes `seed` for `round`, i.e `HASH([r] + seed)`.
+pub fn get_round_hash(seed: &[u8; HASH_LENGTH], round: u16) -> [u8; HASH_LENGTH] {
+    let round_bytes = round.to_le_bytes();
+
+    hashv(&[get_round_seed(&round_bytes), seed.as_ref()]).to_bytes()
+}
+
+/// Hashes `seed` for `round` at `difficulty`, i.e `HASH([r] + [difficulty] + seed)`. The
+/// difficulty is left out for `Difficulty::Normal` so sessions from before difficulties
+/// were added still verify.
+pub fn get_difficulty_round_hash(
+    seed: &[u8; HASH_LENGTH],
+    round: u16,
+    difficulty: Difficulty,
+) -> [u8; HASH_LENGTH] {
+    if difficulty == Difficulty::Normal {
+        return get_round_hash(seed, round);
+    }
+
+    let round_bytes = round.to_le_bytes();
+
+    hashv(&[
+        get_round_seed(&round_bytes),
+        &[u8::from(difficulty)],
+        seed.as_ref(),
+    ])
+    .to_bytes()
+}
+
+/// Counts the rounds the session survived before its first losing move.
+pub fn get_rounds_survived(
+    rules: &impl GameRules,
+    game_session: &GameSession,
+    player_moves: &[u8],
+    private_config_seed: &[u8; HASH_LENGTH],
+) -> u16 {
+    let mut rounds_survived = 0;
+
+    while let Some(player_move) =
+        game_session.get_player_move(player_moves, usize::from(rounds_survived))
+    {
+        if rules.is_losing_move(
+            game_session,
+            private_config_seed,
+            rounds_survived,
+            player_move,
+        ) {
+            break;
+        }
+
+        rounds_survived += 1;
+    }
+
+    rounds_survived
+}
+
+/// Computes the payout(excluding the deposit) for surviving every round the session
+/// recorded a move for. The deposit is multiplied by the fair odds of each round the
+/// rules give and the house edge is taken off the total, the result is capped at
+/// `max_payout`.
+pub fn get_payout_for_rounds(
+    rules: &impl GameRules,
+    game_session: &GameSession,
+    player_moves: &[u8],
+    house_edge: u16,
+    max_payout: u64,
+) -> u64 {
+    let deposit = game_session.deposit;
+
+    let retained_bps = u128::from(MAX_BPS.saturating_sub(u64::from(house_edge)));
+
+    if retained_bps == 0 {
+        return 0;
+    }
+
+    // Once the total is past this value the payout is capped anyway, so there is no
+    // need to keep multiplying.
+    let max_total =
+        (u128::from(deposit) + u128::from(max_payout)) * u128::from(MAX_BPS) / retained_bps;
+
+    let mut total = u128::from(deposit);
+
+    for round in 0..game_session.next_player_move_position {
+        if total >= max_total {
+            break;
+        }
+
+        let Some(player_move) = game_session.get_player_move(player_moves, usize::from(round))
+        else {
+            break;
+        };
+
+        total = rules.apply_round_multiplier(game_session, round, player_move, total);
+    }
+
+    let total = total * retained_bps / u128::from(MAX_BPS);
+
+    u64::try_from(total.saturating_sub(u128::from(deposit)))
+        .unwrap_or(u64::MAX)
+        .min(max_payout)
+}
+
+/// The crash point of a round, in bps, derived from the link of the hash chain revealed
+/// for it. With `U = (LE64(link) >> 12) / 2^52` uniform in `[0 .. 1)` the crash point is
+/// `(MAX_BPS - house_edge) / (1 - U)`, so a target `x` survives with odds
+/// `(1 - house_edge) / x`. It is kept in `[MAX_BPS .. MAX_CRASH_POINT]`.
+pub fn get_crash_point(chain_link: &[u8; HASH_LENGTH], house_edge: u16) -> u64 {
+    const SCALE: u128 = 1 << 52;
+
+    let mut link_bytes = [0u8; 8];
+    link_bytes.copy_from_slice(&chain_link[..8]);
+
+    let sample = u128::from(u64::from_le_bytes(link_bytes) >> 12);
+
+    let retained_bps = u128::from(MAX_BPS.saturating_sub(u64::from(house_edge)));
+
+    let crash_point = retained_bps * SCALE / (SCALE - sample);
+
+    u64::try_from(crash_point)
+        .unwrap_or(u64::MAX)
+        .clamp(MAX_BPS, MAX_CRASH_POINT)
+}
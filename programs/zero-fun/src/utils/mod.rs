@@ -1,2 +1,11 @@
 pub mod signature;
 pub use signature::*;
+
+pub mod packing;
+pub use packing::*;
+
+pub mod derivation;
+pub use derivation::*;
+
+pub mod storage;
+pub use storage::*;
@@ -0,0 +1,51 @@
+/// Reads the move stored at `position` from a buffer of bit-packed moves,
+/// each move occupies `bit_width` bits and may straddle a byte boundary.
+/// Returns `None` if the position lies outside the buffer.
+pub fn read_packed_move(packed_moves: &[u8], position: usize, bit_width: usize) -> Option<u8> {
+    let bit_offset = position.checked_mul(bit_width)?;
+    let byte_index = bit_offset / 8;
+    let bit_index = bit_offset % 8;
+
+    let low = u16::from(*packed_moves.get(byte_index)?);
+    let high = if bit_index + bit_width > 8 {
+        u16::from(*packed_moves.get(byte_index + 1)?)
+    } else {
+        0
+    };
+
+    let mask = (1u16 << bit_width) - 1;
+
+    Some((((high << 8 | low) >> bit_index) & mask) as u8)
+}
+
+/// Writes `player_move` at `position` into a buffer of bit-packed moves,
+/// the caller is expected to have checked that the move fits in `bit_width` bits.
+/// Returns `None` if the position lies outside the buffer.
+pub fn write_packed_move(
+    packed_moves: &mut [u8],
+    position: usize,
+    player_move: u8,
+    bit_width: usize,
+) -> Option<()> {
+    let bit_offset = position.checked_mul(bit_width)?;
+    let byte_index = bit_offset / 8;
+    let bit_index = bit_offset % 8;
+
+    let straddles = bit_index + bit_width > 8;
+
+    if byte_index >= packed_moves.len() || (straddles && byte_index + 1 >= packed_moves.len()) {
+        return None;
+    }
+
+    let mask = ((1u16 << bit_width) - 1) << bit_index;
+    let value = (u16::from(player_move) << bit_index) & mask;
+
+    packed_moves[byte_index] = (u16::from(packed_moves[byte_index]) & !mask | value) as u8;
+
+    if straddles {
+        packed_moves[byte_index + 1] =
+            (u16::from(packed_moves[byte_index + 1]) & !(mask >> 8) | value >> 8) as u8;
+    }
+
+    Some(())
+}
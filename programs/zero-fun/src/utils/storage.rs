@@ -0,0 +1,33 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+/// Grows a session's account to `new_length`, `payer` covers the extra rent.
+pub fn grow_move_storage<'info>(
+    game_session: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_length: usize,
+) -> Result<()> {
+    let rent_top_up = Rent::get()?
+        .minimum_balance(new_length)
+        .saturating_sub(game_session.lamports());
+
+    if rent_top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: game_session.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+
+    game_session.realloc(new_length, true)?;
+
+    Ok(())
+}
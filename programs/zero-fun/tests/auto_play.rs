@@ -0,0 +1,264 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_game_config_account, create_game_session_account, create_global_state_account,
+};
+
+use zero_fun::{
+    instruction::{RecordAction, RevealAutoPlay},
+    instructions::RecordActionArgs,
+    GameError, GameSession, GameState, GlobalState, RevealAutoPlayArgs, AUTO_PLAY_SEED,
+    HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID, MOVE_PAGE_SIZE,
+};
+
+// Every round of the tile ladder has at least two tiles, so these are always valid moves
+const MOVES: [u8; 2] = [0, 1];
+
+// Here what is relevant is that the revealed moves have to match the commitment and
+// that an auto-play session can't be played move by move.
+struct TestSetup {
+    player: Keypair,
+    salt: [u8; HASH_LENGTH],
+    game_session: Pubkey,
+    global_state: Pubkey,
+    game_config: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn get_commitment(stop_after: u16, salt: &[u8; HASH_LENGTH]) -> [u8; HASH_LENGTH] {
+        hashv(&[
+            AUTO_PLAY_SEED.as_ref(),
+            stop_after.to_le_bytes().as_ref(),
+            salt.as_ref(),
+            MOVES.as_ref(),
+        ])
+        .to_bytes()
+    }
+
+    /// Creates a session committed to playing `MOVES` for `stop_after` moves, or a regular
+    /// session if `stop_after` is `None`.
+    fn new(svm: &mut LiteSVM, stop_after: Option<u16>) -> Result<Self> {
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        let salt = Pubkey::new_unique().to_bytes();
+
+        let public_config_seed = Pubkey::new_unique().to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut game_session_account = GameSession::new(
+            player.pubkey(),
+            1_000_000u64,
+            Pubkey::new_unique(),
+            public_config_seed,
+            b"metadata",
+            0,
+        );
+
+        if let Some(stop_after) = stop_after {
+            game_session_account.enable_auto_play(Self::get_commitment(stop_after, &salt));
+        }
+
+        create_game_session_account(svm, game_session, &game_session_account);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            255,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        Ok(Self {
+            player,
+            salt,
+            game_session,
+            global_state,
+            game_config,
+        })
+    }
+
+    fn accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.player.pubkey(), true),
+            AccountMeta::new_readonly(self.global_state, false),
+            AccountMeta::new(self.game_session, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(self.game_config, false),
+        ]
+    }
+
+    fn reveal_auto_play(&self, stop_after: u16, salt: [u8; HASH_LENGTH]) -> Instruction {
+        let args = RevealAutoPlayArgs {
+            moves: MOVES.to_vec(),
+            stop_after,
+            salt,
+        };
+
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: self.accounts(),
+            data: RevealAutoPlay { args }.data(),
+        }
+    }
+
+    fn record_action(&self, action: u8) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: self.accounts(),
+            data: RecordAction {
+                args: RecordActionArgs { action },
+            }
+            .data(),
+        }
+    }
+
+    fn send(
+        &self,
+        svm: &mut LiteSVM,
+        instruction: Instruction,
+    ) -> litesvm::types::TransactionResult {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.player.pubkey()),
+            &[&self.player],
+            svm.latest_blockhash(),
+        );
+
+        svm.send_transaction(transaction)
+    }
+}
+
+#[test]
+fn test_reveal_auto_play_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm, Some(3)).unwrap();
+
+    let instruction = setup.reveal_auto_play(3, setup.salt);
+
+    assert_transaction_success(setup.send(&mut svm, instruction));
+
+    let game_session_data = svm.get_account(&setup.game_session).unwrap().data;
+
+    let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+    assert!(game_session.is_cashed_out());
+
+    // The moves are repeated until the session stops
+    let recorded_moves: Vec<u8> = (0..3)
+        .filter_map(|position| game_session.get_player_move(player_moves, position))
+        .collect();
+
+    assert_eq!(recorded_moves, vec![0, 1, 0]);
+}
+
+#[test]
+fn test_reveal_auto_play_success_across_pages() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let stop_after = u16::try_from(MOVE_PAGE_SIZE * 2 + 1).unwrap();
+
+    let setup = TestSetup::new(&mut svm, Some(stop_after)).unwrap();
+
+    let instruction = setup.reveal_auto_play(stop_after, setup.salt);
+
+    assert_transaction_success(setup.send(&mut svm, instruction));
+
+    let game_session_data = svm.get_account(&setup.game_session).unwrap().data;
+
+    let (game_session, _) = GameSession::split_account_data(&game_session_data);
+
+    assert_eq!(
+        game_session.get_player_move_count(),
+        usize::from(stop_after)
+    );
+}
+
+#[test]
+fn test_reveal_auto_play_fails_with_invalid_commitment() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm, Some(3)).unwrap();
+
+    // Stopping later than committed to
+    let instruction = setup.reveal_auto_play(4, setup.salt);
+
+    assert_custom_transaction_error_at(
+        setup.send(&mut svm, instruction),
+        0,
+        GameError::InvalidCommitment,
+    );
+}
+
+#[test]
+fn test_reveal_auto_play_fails_without_commitment() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm, None).unwrap();
+
+    let instruction = setup.reveal_auto_play(3, setup.salt);
+
+    assert_custom_transaction_error_at(
+        setup.send(&mut svm, instruction),
+        0,
+        GameError::NotAutoPlaySession,
+    );
+}
+
+#[test]
+fn test_record_action_fails_for_auto_play_session() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm, Some(3)).unwrap();
+
+    let instruction = setup.record_action(0);
+
+    assert_custom_transaction_error_at(
+        setup.send(&mut svm, instruction),
+        0,
+        GameError::AutoPlaySession,
+    );
+}
@@ -0,0 +1,269 @@
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_game_session_account_with_moves, set_current_time,
+};
+
+use zero_fun::{
+    instruction::CashOut, GameSession, GameSessionStatus, CASH_OUT_REVEAL_WINDOW, HASH_LENGTH,
+    ID as ZERO_FUN_PROGRAM_ID, PACKED_MOVE_PAGE_LENGTH,
+};
+
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn builder(
+        svm: &mut LiteSVM,
+        state_player: Pubkey,
+        instruction_player: Keypair,
+        game_session_status: GameSessionStatus,
+        recorded_moves: usize,
+        hash_chain: bool,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        svm.airdrop(&instruction_player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        let (game_session_pubkey, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                [0u8; HASH_LENGTH].as_ref(),
+                state_player.as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut game_session_account = GameSession::new(
+            state_player,
+            1_000_000u64,
+            Pubkey::new_unique(),
+            [0u8; HASH_LENGTH],
+            b"metadata",
+            0,
+        );
+
+        game_session_account.set_status(game_session_status);
+
+        if hash_chain {
+            game_session_account.enable_hash_chain(Pubkey::new_unique().to_bytes());
+        }
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        for _ in 0..recorded_moves {
+            game_session_account.set_next_player_move(&mut player_moves, 1)?;
+        }
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session_pubkey,
+            &game_session_account,
+            &player_moves,
+        );
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new_readonly(instruction_player.pubkey(), true),
+            AccountMeta::new(game_session_pubkey, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: CashOut {}.data(),
+        };
+
+        Ok(([instruction], vec![instruction_player]))
+    }
+
+    pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameSessionStatus::Active,
+            3,
+            false,
+        )
+    }
+
+    pub fn with_invalid_player(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let state_player = Pubkey::new_unique();
+        let instruction_player = Keypair::new();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameSessionStatus::Active,
+            3,
+            false,
+        )
+    }
+
+    pub fn with_inactive_game_session(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameSessionStatus::Won,
+            3,
+            false,
+        )
+    }
+
+    pub fn with_unrevealed_round(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameSessionStatus::Active,
+            1,    // The last move has not been revealed
+            true, // Hash chain session
+        )
+    }
+}
+
+#[test]
+fn test_cash_out_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[1].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let now = 1_000i64;
+
+    set_current_time(&mut svm, now);
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let game_session_account = svm
+        .get_account(&game_session)
+        .expect("Game session should still be open");
+
+    let (game_session, _) = GameSession::split_account_data(&game_session_account.data);
+
+    assert!(game_session.is_cashed_out());
+    assert_eq!(game_session.cash_out_time, now);
+    assert_eq!(
+        game_session.get_reveal_deadline(),
+        now + CASH_OUT_REVEAL_WINDOW
+    );
+}
+
+#[test]
+fn test_cash_out_fails_with_invalid_player() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_player(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidPlayer,
+    );
+}
+
+#[test]
+fn test_cash_out_fails_with_inactive_game_session() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_inactive_game_session(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::GameSessionNotActive,
+    );
+}
+
+#[test]
+fn test_cash_out_fails_with_unrevealed_round() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_unrevealed_round(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::RoundNotRevealed,
+    );
+}
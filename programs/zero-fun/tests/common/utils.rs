@@ -1,4 +1,4 @@
-use anchor_lang::{error::Error as AnchorError, AccountSerialize, Space};
+use anchor_lang::{error::Error as AnchorError, AccountSerialize, Discriminator, Space};
 use litesvm::types::{TransactionMetadata, TransactionResult};
 use litesvm::LiteSVM;
 use solana_sdk::{
@@ -11,7 +11,10 @@ use solana_sdk::{
     signer::{keypair::Keypair, Signer},
     transaction::TransactionError,
 };
-use zero_fun::{GameError, GameSession, GlobalState, ID as ZERO_FUN_PROGRAM_ID};
+use zero_fun::{
+    FeeVault, GameConfig, GameError, GameParameters, GameSession, GameState, GlobalState, Jackpot,
+    PlayerProfile, ReferrerRewards, TileRules, ID as ZERO_FUN_PROGRAM_ID, PACKED_MOVE_PAGE_LENGTH,
+};
 
 pub fn create_global_state_account(
     svm: &mut LiteSVM,
@@ -46,6 +49,190 @@ pub fn create_global_state_account(
     }
 }
 
+/// Creates an active global state at its PDA and returns its address.
+pub fn create_default_global_state_account(svm: &mut LiteSVM) -> Pubkey {
+    let (global_state_pubkey, _) =
+        Pubkey::find_program_address(&[b"global-state"], &ZERO_FUN_PROGRAM_ID);
+
+    let (_, vault_bump) = Pubkey::find_program_address(&[b"vault"], &ZERO_FUN_PROGRAM_ID);
+
+    create_global_state_account(
+        svm,
+        global_state_pubkey,
+        GlobalState::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            0,
+        ),
+    );
+
+    global_state_pubkey
+}
+
+/// Creates the config for the variant of `game_config` at its PDA and returns its address.
+pub fn create_game_config_account(svm: &mut LiteSVM, game_config: GameConfig) -> Pubkey {
+    let (game_config_pubkey, _) = Pubkey::find_program_address(
+        &[b"game-config", &[u8::from(game_config.get_variant())]],
+        &ZERO_FUN_PROGRAM_ID,
+    );
+
+    let mut data = Vec::with_capacity(8 + GameConfig::INIT_SPACE);
+
+    game_config
+        .try_serialize(&mut data)
+        .expect("Could not serialize GameConfig");
+
+    let rent = svm.minimum_balance_for_rent_exemption(data.len());
+
+    let account = SolanaAccount {
+        lamports: rent,
+        data,
+        owner: ZERO_FUN_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    svm.set_account(game_config_pubkey, account)
+        .expect("Could not insert GameConfig account into SVM");
+
+    game_config_pubkey
+}
+
+/// Creates the config for the tile ladder with its default parameters.
+pub fn create_default_game_config_account(svm: &mut LiteSVM, game_state: GameState) -> Pubkey {
+    create_game_config_account(
+        svm,
+        GameConfig::new(game_state, GameParameters::Tiles(TileRules::default()), 255),
+    )
+}
+
+/// Creates a program owned account holding the serialized `account`, sized for its
+/// `INIT_SPACE` like the program creates it.
+pub fn create_program_account<T: AccountSerialize + Space>(
+    svm: &mut LiteSVM,
+    pubkey: Pubkey,
+    account: &T,
+) {
+    let mut data = Vec::with_capacity(8 + T::INIT_SPACE);
+
+    account
+        .try_serialize(&mut data)
+        .expect("Could not serialize account");
+
+    data.resize(8 + T::INIT_SPACE, 0);
+
+    let rent = svm.minimum_balance_for_rent_exemption(data.len());
+
+    let account = SolanaAccount {
+        lamports: rent,
+        data,
+        owner: ZERO_FUN_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    svm.set_account(pubkey, account)
+        .expect("Could not insert account into SVM");
+}
+
+/// Creates the jackpot at its PDA, holding `jackpot.balance` on top of its rent, and
+/// returns its address.
+pub fn create_jackpot_account(svm: &mut LiteSVM, mut jackpot: Jackpot) -> Pubkey {
+    let (jackpot_pubkey, jackpot_bump) =
+        Pubkey::find_program_address(&[b"jackpot"], &ZERO_FUN_PROGRAM_ID);
+
+    jackpot.bump = jackpot_bump;
+
+    create_program_account(svm, jackpot_pubkey, &jackpot);
+
+    let mut account = svm.get_account(&jackpot_pubkey).unwrap();
+    account.lamports += jackpot.balance;
+
+    svm.set_account(jackpot_pubkey, account)
+        .expect("Could not insert Jackpot account into SVM");
+
+    jackpot_pubkey
+}
+
+/// Creates a jackpot that takes no contribution and is never hit.
+pub fn create_default_jackpot_account(svm: &mut LiteSVM) -> Pubkey {
+    create_jackpot_account(svm, Jackpot::new(0, u32::MAX, 0, 0))
+}
+
+/// Creates the fee vault at its PDA, holding `fee_vault.balance` on top of its rent, and
+/// returns its address.
+pub fn create_fee_vault_account(svm: &mut LiteSVM, mut fee_vault: FeeVault) -> Pubkey {
+    let (fee_vault_pubkey, fee_vault_bump) =
+        Pubkey::find_program_address(&[b"fee-vault"], &ZERO_FUN_PROGRAM_ID);
+
+    fee_vault.bump = fee_vault_bump;
+
+    create_program_account(svm, fee_vault_pubkey, &fee_vault);
+
+    let mut account = svm.get_account(&fee_vault_pubkey).unwrap();
+    account.lamports += fee_vault.balance;
+
+    svm.set_account(fee_vault_pubkey, account)
+        .expect("Could not insert FeeVault account into SVM");
+
+    fee_vault_pubkey
+}
+
+/// Creates an empty fee vault.
+pub fn create_default_fee_vault_account(svm: &mut LiteSVM) -> Pubkey {
+    create_fee_vault_account(svm, FeeVault::new(0))
+}
+
+/// The address of `player`'s profile.
+pub fn get_player_profile_address(player: &Pubkey) -> Pubkey {
+    let (player_profile, _) =
+        Pubkey::find_program_address(&[b"player-profile", player.as_ref()], &ZERO_FUN_PROGRAM_ID);
+
+    player_profile
+}
+
+/// Creates an empty profile for `player` and returns its address.
+pub fn create_player_profile_account(svm: &mut LiteSVM, player: Pubkey) -> Pubkey {
+    let (player_profile, player_profile_bump) =
+        Pubkey::find_program_address(&[b"player-profile", player.as_ref()], &ZERO_FUN_PROGRAM_ID);
+
+    create_program_account(
+        svm,
+        player_profile,
+        &PlayerProfile::new(player, player_profile_bump),
+    );
+
+    player_profile
+}
+
+/// Creates `referrer_rewards` at its PDA, holding its unclaimed rewards on top of its rent,
+/// and returns its address.
+pub fn create_referrer_rewards_account(
+    svm: &mut LiteSVM,
+    mut referrer_rewards: ReferrerRewards,
+) -> Pubkey {
+    let (referrer_rewards_pubkey, referrer_rewards_bump) = Pubkey::find_program_address(
+        &[b"referrer-rewards", referrer_rewards.referrer.as_ref()],
+        &ZERO_FUN_PROGRAM_ID,
+    );
+
+    referrer_rewards.bump = referrer_rewards_bump;
+
+    create_program_account(svm, referrer_rewards_pubkey, &referrer_rewards);
+
+    let mut account = svm.get_account(&referrer_rewards_pubkey).unwrap();
+    account.lamports += referrer_rewards.unclaimed;
+
+    svm.set_account(referrer_rewards_pubkey, account)
+        .expect("Could not insert ReferrerRewards account into SVM");
+
+    referrer_rewards_pubkey
+}
+
 pub fn get_initializer_keypair() -> Keypair {
     Keypair::from_base58_string(
         "3JzA5QAwszDUeHVoK8jZwNNmCvKKDQjz6u47TuW1cVBvsCmvt9Fhpb1WvxyHi8xkrv66NGw8GSsKfiko7NnYbuCW",
@@ -127,12 +314,27 @@ pub fn create_game_session_account(
     game_session_pubkey: Pubkey,
     game_session: &GameSession,
 ) {
-    let mut data = Vec::with_capacity(8 + GameSession::INIT_SPACE);
+    create_game_session_account_with_moves(
+        svm,
+        game_session_pubkey,
+        game_session,
+        &[0u8; PACKED_MOVE_PAGE_LENGTH],
+    );
+}
 
-    // Anchor writes the discriminator + fields
-    game_session
-        .try_serialize(&mut data)
-        .expect("Could not serialize GameSession");
+pub fn create_game_session_account_with_moves(
+    svm: &mut LiteSVM,
+    game_session_pubkey: Pubkey,
+    game_session: &GameSession,
+    player_moves: &[u8],
+) {
+    let mut data = Vec::with_capacity(GameSession::PLAYER_MOVES_OFFSET + player_moves.len());
+
+    // Zero-copy accounts are the discriminator followed by the raw struct bytes,
+    // the bit-packed moves follow the session
+    data.extend_from_slice(GameSession::DISCRIMINATOR);
+    data.extend_from_slice(bytemuck::bytes_of(game_session));
+    data.extend_from_slice(player_moves);
 
     let rent = svm.minimum_balance_for_rent_exemption(data.len());
 
@@ -0,0 +1,635 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::{AccountDeserialize, InstructionData};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_global_state_account, create_program_account, create_vault_account, set_current_time,
+};
+
+use zero_fun::{
+    instruction::{
+        CashOutCrashBet, JoinCrashRound, RevealCrashRound, SettleCrashBet, StartCrashRound,
+    },
+    CrashBet, CrashRound, CrashRoundStatus, CrashState, GameError, GameState, GlobalState,
+    JoinCrashRoundArgs, PlayerProfile, RevealCrashRoundArgs, CRASH_REVEAL_WINDOW, HASH_LENGTH,
+    ID as ZERO_FUN_PROGRAM_ID, MAX_BPS,
+};
+
+const BETTING_DEADLINE: i64 = 1_000;
+
+const BETTING_PERIOD: i64 = 30;
+
+const MULTIPLIER_GROWTH: u64 = 1_000; // 0.1x per second
+
+const DEPOSIT: u64 = 1_000_000;
+
+const CASH_OUT_TARGET: u64 = 2 * MAX_BPS;
+
+// Every test starts with an open round(the first one) and the crash state committed to the
+// head of a chain whose next link is `chain_link`
+struct TestSetup {
+    pub admin: Keypair,
+    pub player: Keypair,
+    pub chain_link: [u8; HASH_LENGTH],
+    pub crash_state: Pubkey,
+    pub crash_round: Pubkey,
+    pub crash_bet: Pubkey,
+    pub vault: Pubkey,
+    pub global_state: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn new(svm: &mut LiteSVM) -> Self {
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to admin");
+
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            admin.pubkey(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        // Create the crash state, its head is the hash of the next link
+        let chain_link = hashv(&[Pubkey::new_unique().as_ref()]).to_bytes();
+
+        let (crash_state, crash_state_bump) =
+            Pubkey::find_program_address(&[b"crash-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let mut crash_state_account = CrashState::new(
+            hashv(&[chain_link.as_ref()]).to_bytes(),
+            BETTING_PERIOD,
+            MULTIPLIER_GROWTH,
+            crash_state_bump,
+        );
+
+        crash_state_account.next_round_index = 1;
+        crash_state_account.has_open_round = true;
+
+        create_program_account(svm, crash_state, &crash_state_account);
+
+        let (crash_round, crash_round_bump) = Pubkey::find_program_address(
+            &[b"crash-round", 0u64.to_le_bytes().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        create_program_account(
+            svm,
+            crash_round,
+            &CrashRound::new(0, BETTING_DEADLINE, 100, crash_round_bump),
+        );
+
+        let (crash_bet, _) = Pubkey::find_program_address(
+            &[b"crash-bet", crash_round.as_ref(), player.pubkey().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        set_current_time(svm, BETTING_DEADLINE - 1);
+
+        Self {
+            admin,
+            player,
+            chain_link,
+            crash_state,
+            crash_round,
+            crash_bet,
+            vault,
+            global_state,
+        }
+    }
+
+    /// Adds the player's bet on the open round.
+    pub fn with_bet(self, svm: &mut LiteSVM) -> Self {
+        let (_, crash_bet_bump) = Pubkey::find_program_address(
+            &[
+                b"crash-bet",
+                self.crash_round.as_ref(),
+                self.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        create_program_account(
+            svm,
+            self.crash_bet,
+            &CrashBet::new(
+                self.crash_round,
+                self.player.pubkey(),
+                DEPOSIT,
+                CASH_OUT_TARGET,
+                crash_bet_bump,
+            ),
+        );
+
+        // The bet was counted in the ledger when it was placed
+        let mut global_state = self.get_global_state(svm);
+
+        global_state.record_session_opened(DEPOSIT);
+        global_state.record_crash_stake_opened(DEPOSIT);
+
+        create_global_state_account(svm, self.global_state, global_state);
+
+        self.with_player_profile(svm, |player_profile| {
+            player_profile.record_game_started(DEPOSIT, 0)
+        })
+    }
+
+    /// Sets what the bets already on the open round win at their targets.
+    pub fn with_potential_payout(self, svm: &mut LiteSVM, potential_payout: u64) -> Self {
+        let mut crash_round = self.get_crash_round(svm);
+
+        crash_round.potential_payout = potential_payout;
+
+        create_program_account(svm, self.crash_round, &crash_round);
+
+        self
+    }
+
+    /// Marks the open round as revealed with `crash_point`.
+    pub fn with_revealed_round(self, svm: &mut LiteSVM, crash_point: u64) -> Self {
+        let mut crash_round = self.get_crash_round(svm);
+
+        crash_round.status = CrashRoundStatus::Revealed;
+        crash_round.chain_link = self.chain_link;
+        crash_round.crash_point = crash_point;
+
+        create_program_account(svm, self.crash_round, &crash_round);
+
+        self
+    }
+
+    /// Creates the player's profile, with `update` applied to it.
+    pub fn with_player_profile(
+        self,
+        svm: &mut LiteSVM,
+        update: impl FnOnce(&mut PlayerProfile),
+    ) -> Self {
+        let (player_profile, player_profile_bump) = self.get_player_profile_address();
+
+        let mut player_profile_account =
+            PlayerProfile::new(self.player.pubkey(), player_profile_bump);
+
+        update(&mut player_profile_account);
+
+        create_program_account(svm, player_profile, &player_profile_account);
+
+        self
+    }
+
+    pub fn get_player_profile_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"player-profile", self.player.pubkey().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        )
+    }
+
+    pub fn get_player_profile(&self, svm: &LiteSVM) -> PlayerProfile {
+        let account = svm
+            .get_account(&self.get_player_profile_address().0)
+            .unwrap();
+
+        PlayerProfile::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn get_global_state(&self, svm: &LiteSVM) -> GlobalState {
+        let account = svm.get_account(&self.global_state).unwrap();
+
+        GlobalState::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn get_crash_round(&self, svm: &LiteSVM) -> CrashRound {
+        let account = svm.get_account(&self.crash_round).unwrap();
+
+        CrashRound::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn get_crash_bet(&self, svm: &LiteSVM) -> CrashBet {
+        let account = svm.get_account(&self.crash_bet).unwrap();
+
+        CrashBet::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub fn start_crash_round(&self) -> Instruction {
+        let (next_crash_round, _) = Pubkey::find_program_address(
+            &[b"crash-round", 1u64.to_le_bytes().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.crash_state, false),
+                AccountMeta::new(next_crash_round, false),
+                AccountMeta::new(self.admin.pubkey(), true),
+                AccountMeta::new_readonly(self.global_state, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: StartCrashRound {}.data(),
+        }
+    }
+
+    pub fn join_crash_round(&self, cash_out_target: u64) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.crash_round, false),
+                AccountMeta::new(self.crash_bet, false),
+                AccountMeta::new(self.player.pubkey(), true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.global_state, false),
+                AccountMeta::new(self.get_player_profile_address().0, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: JoinCrashRound {
+                args: JoinCrashRoundArgs {
+                    deposit: DEPOSIT,
+                    cash_out_target,
+                },
+            }
+            .data(),
+        }
+    }
+
+    pub fn cash_out_crash_bet(&self) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.player.pubkey(), true),
+                AccountMeta::new_readonly(self.crash_state, false),
+                AccountMeta::new_readonly(self.crash_round, false),
+                AccountMeta::new(self.crash_bet, false),
+            ],
+            data: CashOutCrashBet {}.data(),
+        }
+    }
+
+    pub fn reveal_crash_round(&self, chain_link: [u8; HASH_LENGTH]) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.crash_state, false),
+                AccountMeta::new(self.crash_round, false),
+            ],
+            data: RevealCrashRound {
+                args: RevealCrashRoundArgs { chain_link },
+            }
+            .data(),
+        }
+    }
+
+    pub fn settle_crash_bet(&self) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.crash_round, false),
+                AccountMeta::new(self.crash_bet, false),
+                AccountMeta::new(self.player.pubkey(), false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.global_state, false),
+                AccountMeta::new(self.get_player_profile_address().0, false),
+            ],
+            data: SettleCrashBet {}.data(),
+        }
+    }
+}
+
+fn send(
+    svm: &mut LiteSVM,
+    instruction: Instruction,
+    signer: &Keypair,
+) -> litesvm::types::TransactionResult {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signer.pubkey()),
+        &[signer],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(transaction)
+}
+
+#[test]
+fn test_join_crash_round_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    let vault_balance_before = svm.get_balance(&setup.vault).unwrap();
+
+    let instruction = setup.join_crash_round(CASH_OUT_TARGET);
+
+    assert_transaction_success(send(&mut svm, instruction, &setup.player));
+
+    let crash_bet = setup.get_crash_bet(&svm);
+
+    assert_eq!(crash_bet.deposit, DEPOSIT);
+    assert_eq!(crash_bet.cash_out_target, CASH_OUT_TARGET);
+
+    // The bet wins its deposit again at 2x
+    assert_eq!(setup.get_crash_round(&svm).potential_payout, DEPOSIT);
+
+    assert_eq!(
+        svm.get_balance(&setup.vault).unwrap(),
+        vault_balance_before + DEPOSIT
+    );
+
+    let global_state = setup.get_global_state(&svm);
+
+    assert_eq!(global_state.total_deposited, DEPOSIT);
+    assert_eq!(global_state.open_sessions, 1);
+    assert_eq!(global_state.open_crash_stakes, DEPOSIT);
+
+    // The bet is wagered like a session's deposit
+    let player_profile = setup.get_player_profile(&svm);
+
+    assert_eq!(player_profile.games_played, 1);
+    assert_eq!(player_profile.total_wagered, DEPOSIT);
+    assert_eq!(player_profile.active_sessions, 1);
+}
+
+#[test]
+fn test_join_crash_round_fails_with_deposit_over_limit() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_player_profile(&mut svm, |player_profile| {
+        player_profile.limits.daily_deposit_limit = DEPOSIT - 1
+    });
+
+    let instruction = setup.join_crash_round(CASH_OUT_TARGET);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::DepositLimitExceeded,
+    );
+}
+
+#[test]
+fn test_join_crash_round_fails_after_betting_closed() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    set_current_time(&mut svm, BETTING_DEADLINE + 1);
+
+    let instruction = setup.join_crash_round(CASH_OUT_TARGET);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::BettingClosed,
+    );
+}
+
+#[test]
+fn test_join_crash_round_fails_with_invalid_cash_out_target() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    // A target of 1x can't win anything
+    let instruction = setup.join_crash_round(MAX_BPS);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::InvalidCashOutTarget,
+    );
+}
+
+#[test]
+fn test_join_crash_round_fails_past_round_maximum_payout() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    // The maximum payout is 1% of the vault, just over a million lamports, which the bets
+    // already on the round nearly use up
+    let setup = TestSetup::new(&mut svm).with_potential_payout(&mut svm, 500_000);
+
+    let instruction = setup.join_crash_round(CASH_OUT_TARGET);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::PayoutExceedsMaximum,
+    );
+}
+
+#[test]
+fn test_start_crash_round_fails_with_open_round() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    let instruction = setup.start_crash_round();
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instruction, &setup.admin),
+        0,
+        GameError::CrashRoundOpen,
+    );
+}
+
+#[test]
+fn test_cash_out_crash_bet_lowers_target() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_bet(&mut svm);
+
+    // 5 seconds in the multiplier is at 1.5x
+    set_current_time(&mut svm, BETTING_DEADLINE + 5);
+
+    let instruction = setup.cash_out_crash_bet();
+
+    assert_transaction_success(send(&mut svm, instruction, &setup.player));
+
+    assert_eq!(
+        setup.get_crash_bet(&svm).cash_out_target,
+        MAX_BPS + 5 * MULTIPLIER_GROWTH
+    );
+}
+
+#[test]
+fn test_reveal_crash_round_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    set_current_time(&mut svm, BETTING_DEADLINE + 1);
+
+    let instruction = setup.reveal_crash_round(setup.chain_link);
+
+    assert_transaction_success(send(&mut svm, instruction, &setup.player));
+
+    let crash_round = setup.get_crash_round(&svm);
+
+    assert!(crash_round.is_revealed());
+    assert!(!crash_round.revealed_late);
+    assert!(crash_round.crash_point >= MAX_BPS);
+    assert_eq!(crash_round.chain_link, setup.chain_link);
+}
+
+#[test]
+fn test_reveal_crash_round_fails_with_invalid_link() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    set_current_time(&mut svm, BETTING_DEADLINE + 1);
+
+    // The head itself is not the next link
+    let instruction = setup.reveal_crash_round(hashv(&[setup.chain_link.as_ref()]).to_bytes());
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::InvalidHashChainLink,
+    );
+}
+
+#[test]
+fn test_settle_crash_bet_pays_winning_bet() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm)
+        .with_bet(&mut svm)
+        .with_revealed_round(&mut svm, 3 * MAX_BPS);
+
+    let vault_balance_before = svm.get_balance(&setup.vault).unwrap();
+
+    let instruction = setup.settle_crash_bet();
+
+    assert_transaction_success(send(&mut svm, instruction, &setup.player));
+
+    assert_eq!(
+        svm.get_balance(&setup.vault).unwrap(),
+        vault_balance_before - DEPOSIT * CASH_OUT_TARGET / MAX_BPS
+    );
+
+    assert!(svm
+        .get_account(&setup.crash_bet)
+        .is_none_or(|account| account.lamports == 0));
+
+    let global_state = setup.get_global_state(&svm);
+
+    assert_eq!(global_state.open_sessions, 0);
+    assert_eq!(global_state.open_crash_stakes, 0);
+
+    let player_profile = setup.get_player_profile(&svm);
+
+    assert_eq!(player_profile.games_won, 1);
+    assert_eq!(player_profile.active_sessions, 0);
+}
+
+#[test]
+fn test_settle_crash_bet_keeps_losing_deposit() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm)
+        .with_bet(&mut svm)
+        .with_revealed_round(&mut svm, CASH_OUT_TARGET); // Crashes right at the target
+
+    let vault_balance_before = svm.get_balance(&setup.vault).unwrap();
+
+    let instruction = setup.settle_crash_bet();
+
+    assert_transaction_success(send(&mut svm, instruction, &setup.player));
+
+    assert_eq!(svm.get_balance(&setup.vault).unwrap(), vault_balance_before);
+
+    let player_profile = setup.get_player_profile(&svm);
+
+    assert_eq!(player_profile.games_lost, 1);
+    assert_eq!(player_profile.active_sessions, 0);
+}
+
+#[test]
+fn test_settle_crash_bet_fails_before_reveal() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_bet(&mut svm);
+
+    set_current_time(&mut svm, BETTING_DEADLINE + 1);
+
+    let instruction = setup.settle_crash_bet();
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::CrashRoundNotRevealed,
+    );
+}
+
+#[test]
+fn test_settle_crash_bet_pays_after_reveal_window_expired() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_bet(&mut svm);
+
+    set_current_time(&mut svm, BETTING_DEADLINE + CRASH_REVEAL_WINDOW + 1);
+
+    let vault_balance_before = svm.get_balance(&setup.vault).unwrap();
+
+    let instruction = setup.settle_crash_bet();
+
+    assert_transaction_success(send(&mut svm, instruction, &setup.player));
+
+    assert!(svm.get_balance(&setup.vault).unwrap() < vault_balance_before);
+}
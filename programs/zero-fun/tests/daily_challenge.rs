@@ -0,0 +1,707 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_game_config_account, create_game_session_account,
+    create_game_session_account_with_moves, create_global_state_account,
+    create_player_profile_account, create_program_account, create_vault_account,
+    get_player_profile_address, set_current_time,
+};
+
+use zero_fun::{
+    instruction::{
+        RefundDailyChallengeSession, RevealDailyChallenge, SettleDailyChallenge,
+        StartDailyChallenge,
+    },
+    DailyChallenge, DailyChallengeStatus, Difficulty, FailMoveDerivation, GameSession, GameState,
+    GameVariant, GlobalState, RevealDailyChallengeArgs, StartDailyChallengeArgs, TileRules,
+    DAILY_CHALLENGE_DURATION, DAILY_CHALLENGE_REVEAL_WINDOW, HASH_LENGTH,
+    ID as ZERO_FUN_PROGRAM_ID, PACKED_MOVE_PAGE_LENGTH, PUBLIC_SEED,
+};
+
+const DAY: u64 = 20_000;
+
+const DEPOSIT: u64 = 1_000_000;
+
+const ROUND_COUNT: u16 = 3;
+
+// Here what is relevant is that every session of the day is played on the challenge's
+// seed, and once it is revealed the sessions are settled and ranked against it, moves
+// made after the day ended are never paid out.
+struct TestSetup {
+    player: Keypair,
+    private_config_seed: [u8; HASH_LENGTH],
+    daily_challenge: Pubkey,
+    vault: Pubkey,
+    global_state: Pubkey,
+    game_config: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn new(svm: &mut LiteSVM, status: DailyChallengeStatus) -> Self {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        let (daily_challenge, daily_challenge_bump) = Pubkey::find_program_address(
+            &[b"daily-challenge", DAY.to_le_bytes().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut daily_challenge_account = DailyChallenge::new(
+            DAY,
+            GameVariant::Tiles,
+            public_config_seed,
+            daily_challenge_bump,
+        );
+
+        if status == DailyChallengeStatus::Revealed {
+            daily_challenge_account.private_config_seed = private_config_seed;
+            daily_challenge_account.status = DailyChallengeStatus::Revealed;
+        }
+
+        create_program_account(svm, daily_challenge, &daily_challenge_account);
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        Self {
+            player,
+            private_config_seed,
+            daily_challenge,
+            vault,
+            global_state,
+            game_config,
+        }
+    }
+
+    fn get_start_time() -> i64 {
+        DAY as i64 * DAILY_CHALLENGE_DURATION
+    }
+
+    fn get_reveal_deadline() -> i64 {
+        Self::get_start_time() + DAILY_CHALLENGE_DURATION + DAILY_CHALLENGE_REVEAL_WINDOW
+    }
+
+    /// Creates the player's session on the revealed challenge with a move for each
+    /// round, the last one on a fail tile if `hit_fail_tile` is set, and settles it.
+    fn with_session(
+        svm: &mut LiteSVM,
+        hit_fail_tile: bool,
+        last_action_time: i64,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, DailyChallengeStatus::Revealed);
+
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), setup.private_config_seed.as_ref()]).to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"challenge-session",
+                setup.daily_challenge.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let (user_vault, _) = Pubkey::find_program_address(
+            &[
+                b"challenge-vault",
+                setup.daily_challenge.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut game_session_account = GameSession::new(
+            setup.player.pubkey(),
+            DEPOSIT,
+            user_vault,
+            public_config_seed,
+            &[],
+            last_action_time,
+        );
+
+        game_session_account.enter_daily_challenge();
+
+        let tile_rules = TileRules::default();
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        for round in 0..ROUND_COUNT {
+            let move_type_count = tile_rules.get_move_type_count_for_round(
+                &public_config_seed,
+                round,
+                Difficulty::Normal,
+            );
+
+            let fail_moves = tile_rules.get_fail_moves_for_round(
+                &setup.private_config_seed,
+                round,
+                move_type_count,
+                Difficulty::Normal,
+                FailMoveDerivation::PartialShuffle,
+            );
+
+            let recorded_move = if hit_fail_tile && round == ROUND_COUNT - 1 {
+                fail_moves.trailing_zeros() as u8
+            } else {
+                fail_moves.trailing_ones() as u8
+            };
+
+            game_session_account.set_next_player_move(&mut player_moves, recorded_move)?;
+        }
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session,
+            &game_session_account,
+            &player_moves,
+        );
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, user_vault, rent + DEPOSIT);
+
+        // The session was counted in the ledger when it was started
+        let mut global_state_account = GlobalState::try_deserialize(
+            &mut svm
+                .get_account(&setup.global_state)
+                .unwrap()
+                .data
+                .as_slice(),
+        )?;
+
+        global_state_account.record_session_opened(DEPOSIT);
+
+        create_global_state_account(svm, setup.global_state, global_state_account);
+
+        set_current_time(svm, Self::get_start_time() + DAILY_CHALLENGE_DURATION + 100);
+
+        let player_profile = create_player_profile_account(svm, setup.player.pubkey());
+
+        let accounts = vec![
+            AccountMeta::new(setup.daily_challenge, false),
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(setup.player.pubkey(), false),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(setup.game_config, false),
+            AccountMeta::new(player_profile, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: SettleDailyChallenge {}.data(),
+        };
+
+        Ok(([instruction], vec![setup.player]))
+    }
+
+    pub fn with_survived_rounds(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::with_session(svm, false, Self::get_start_time() + 100)
+    }
+
+    pub fn with_fail_tile(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::with_session(svm, true, Self::get_start_time() + 100)
+    }
+
+    pub fn with_late_move(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // The last move was made after the day ended
+        Self::with_session(
+            svm,
+            false,
+            Self::get_start_time() + DAILY_CHALLENGE_DURATION + 1,
+        )
+    }
+
+    pub fn with_ended_challenge(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        let setup = Self::new(svm, DailyChallengeStatus::Open);
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"challenge-session",
+                setup.daily_challenge.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let (user_vault, _) = Pubkey::find_program_address(
+            &[
+                b"challenge-vault",
+                setup.daily_challenge.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        set_current_time(svm, Self::get_start_time() + DAILY_CHALLENGE_DURATION);
+
+        let accounts = vec![
+            AccountMeta::new(setup.daily_challenge, false),
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(setup.player.pubkey(), true),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new_readonly(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(setup.game_config, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(get_player_profile_address(&setup.player.pubkey()), false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: StartDailyChallenge {
+                args: StartDailyChallengeArgs { deposit: DEPOSIT },
+            }
+            .data(),
+        };
+
+        ([instruction], vec![setup.player])
+    }
+
+    /// Creates the player's session on a challenge whose seed was never revealed, and
+    /// refunds it.
+    fn with_unrevealed_session(
+        svm: &mut LiteSVM,
+        current_time: i64,
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        let setup = Self::new(svm, DailyChallengeStatus::Open);
+
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), setup.private_config_seed.as_ref()]).to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"challenge-session",
+                setup.daily_challenge.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let (user_vault, _) = Pubkey::find_program_address(
+            &[
+                b"challenge-vault",
+                setup.daily_challenge.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut game_session_account = GameSession::new(
+            setup.player.pubkey(),
+            DEPOSIT,
+            user_vault,
+            public_config_seed,
+            &[],
+            Self::get_start_time() + 100,
+        );
+
+        game_session_account.enter_daily_challenge();
+
+        create_game_session_account(svm, game_session, &game_session_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, user_vault, rent + DEPOSIT);
+
+        // The session was counted in the ledger when it was started
+        let mut global_state_account = GlobalState::try_deserialize(
+            &mut svm
+                .get_account(&setup.global_state)
+                .unwrap()
+                .data
+                .as_slice(),
+        )
+        .unwrap();
+
+        global_state_account.record_session_opened(DEPOSIT);
+
+        create_global_state_account(svm, setup.global_state, global_state_account);
+
+        set_current_time(svm, current_time);
+
+        let player_profile = create_player_profile_account(svm, setup.player.pubkey());
+
+        let accounts = vec![
+            AccountMeta::new_readonly(setup.daily_challenge, false),
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(setup.player.pubkey(), false),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new(player_profile, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: RefundDailyChallengeSession {}.data(),
+        };
+
+        ([instruction], vec![setup.player])
+    }
+
+    pub fn with_expired_reveal_window(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::with_unrevealed_session(svm, Self::get_reveal_deadline() + 1)
+    }
+
+    pub fn with_open_reveal_window(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::with_unrevealed_session(svm, Self::get_reveal_deadline())
+    }
+
+    fn with_reveal(svm: &mut LiteSVM, current_time: i64) -> ([Instruction; 1], Vec<Keypair>) {
+        let setup = Self::new(svm, DailyChallengeStatus::Open);
+
+        set_current_time(svm, current_time);
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![AccountMeta::new(setup.daily_challenge, false)],
+            data: RevealDailyChallenge {
+                args: RevealDailyChallengeArgs {
+                    private_config_seed: setup.private_config_seed,
+                },
+            }
+            .data(),
+        };
+
+        ([instruction], vec![setup.player])
+    }
+
+    pub fn with_early_reveal(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        // The day is still running
+        Self::with_reveal(svm, Self::get_start_time() + 100)
+    }
+
+    pub fn with_late_reveal(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::with_reveal(svm, Self::get_reveal_deadline() + 1)
+    }
+}
+
+fn get_daily_challenge(svm: &LiteSVM, daily_challenge: &Pubkey) -> DailyChallenge {
+    let daily_challenge_data = svm.get_account(daily_challenge).unwrap().data;
+
+    DailyChallenge::try_deserialize(&mut daily_challenge_data.as_slice()).unwrap()
+}
+
+#[test]
+fn test_settle_daily_challenge_success_with_survived_rounds() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_survived_rounds(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let daily_challenge = instructions[0].accounts[0].pubkey;
+    let vault = instructions[0].accounts[4].pubkey;
+
+    let vault_balance_before = svm.get_balance(&vault).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert!(svm.get_balance(&vault).unwrap() < vault_balance_before);
+
+    let daily_challenge_account = get_daily_challenge(&svm, &daily_challenge);
+
+    assert_eq!(daily_challenge_account.leaderboard.len(), 1);
+    assert_eq!(daily_challenge_account.leaderboard[0].player, payer);
+    assert_eq!(
+        daily_challenge_account.leaderboard[0].rounds_survived,
+        ROUND_COUNT
+    );
+
+    let global_state = GlobalState::try_deserialize(
+        &mut svm
+            .get_account(&instructions[0].accounts[5].pubkey)
+            .unwrap()
+            .data
+            .as_slice(),
+    )
+    .unwrap();
+
+    assert_eq!(global_state.open_sessions, 0);
+}
+
+#[test]
+fn test_settle_daily_challenge_success_with_fail_tile() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_fail_tile(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let daily_challenge = instructions[0].accounts[0].pubkey;
+    let user_vault = instructions[0].accounts[3].pubkey;
+    let vault = instructions[0].accounts[4].pubkey;
+
+    let vault_balance_before = svm.get_balance(&vault).unwrap();
+    let user_vault_balance = svm.get_balance(&user_vault).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The deposit goes to the house
+    assert_eq!(
+        svm.get_balance(&vault).unwrap(),
+        vault_balance_before + user_vault_balance
+    );
+
+    let daily_challenge_account = get_daily_challenge(&svm, &daily_challenge);
+
+    assert_eq!(
+        daily_challenge_account.leaderboard[0].rounds_survived,
+        ROUND_COUNT - 1
+    );
+}
+
+#[test]
+fn test_settle_daily_challenge_refunds_late_move() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_late_move(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let daily_challenge = instructions[0].accounts[0].pubkey;
+    let vault = instructions[0].accounts[4].pubkey;
+
+    let vault_balance_before = svm.get_balance(&vault).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // Only the deposit is paid back
+    assert_eq!(svm.get_balance(&vault).unwrap(), vault_balance_before);
+
+    assert!(get_daily_challenge(&svm, &daily_challenge)
+        .leaderboard
+        .is_empty());
+}
+
+#[test]
+fn test_start_daily_challenge_fails_with_ended_challenge() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_ended_challenge(&mut svm);
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::DailyChallengeNotOpen,
+    );
+}
+
+#[test]
+fn test_reveal_daily_challenge_fails_before_end() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_early_reveal(&mut svm);
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::DailyChallengeNotEnded,
+    );
+}
+
+#[test]
+fn test_reveal_daily_challenge_fails_with_expired_reveal_window() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_late_reveal(&mut svm);
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::RevealWindowExpired,
+    );
+}
+
+#[test]
+fn test_refund_daily_challenge_session_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_expired_reveal_window(&mut svm);
+
+    let game_session = instructions[0].accounts[1].pubkey;
+    let user_vault = instructions[0].accounts[3].pubkey;
+    let global_state = instructions[0].accounts[4].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let player_balance_before = svm.get_balance(&payer).unwrap();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The deposit is back with the player and the session is closed
+    assert_eq!(svm.get_balance(&user_vault).unwrap_or(0), 0);
+    assert!(svm.get_balance(&payer).unwrap() > player_balance_before + DEPOSIT);
+    assert_eq!(svm.get_balance(&game_session).unwrap_or(0), 0);
+
+    let global_state_account =
+        GlobalState::try_deserialize(&mut svm.get_account(&global_state).unwrap().data.as_slice())
+            .unwrap();
+
+    assert_eq!(global_state_account.open_sessions, 0);
+}
+
+#[test]
+fn test_refund_daily_challenge_session_fails_with_open_reveal_window() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_open_reveal_window(&mut svm);
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::RevealWindowNotExpired,
+    );
+}
+
+#[test]
+fn test_daily_challenge_leaderboard_keeps_best_scores() {
+    let mut daily_challenge = DailyChallenge::new(DAY, GameVariant::Tiles, [0; HASH_LENGTH], 0);
+
+    let first = Pubkey::new_unique();
+    let second = Pubkey::new_unique();
+    let third = Pubkey::new_unique();
+
+    daily_challenge.submit_score(first, 3);
+    daily_challenge.submit_score(second, 5);
+    daily_challenge.submit_score(third, 3);
+
+    let leaderboard: Vec<(Pubkey, u16)> = daily_challenge
+        .leaderboard
+        .iter()
+        .map(|entry| (entry.player, entry.rounds_survived))
+        .collect();
+
+    // Ties keep the score that was settled first
+    assert_eq!(leaderboard, vec![(second, 5), (first, 3), (third, 3)]);
+}
@@ -11,12 +11,12 @@ use solana_sdk::{
 mod common;
 use common::utils::{
     add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
-    create_game_session_account, create_vault_account, set_current_time,
+    create_default_global_state_account, create_game_session_account,
+    create_player_profile_account, create_vault_account, set_current_time,
 };
 
 use zero_fun::{
-    instruction::DefaultGame, GameSession, GameSessionStatus, DEFAULT_OFFSET, HASH_LENGTH,
-    ID as ZERO_FUN_PROGRAM_ID,
+    instruction::DefaultGame, GameSession, DEFAULT_OFFSET, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
 };
 
 struct TestSetup {}
@@ -47,17 +47,14 @@ impl TestSetup {
             &Self::ZERO_FUN_PROGRAM_ID,
         );
 
-        let game_session_account = GameSession {
+        let game_session_account = GameSession::new(
+            state_player,
+            1_000_000u64,
+            state_vault,
+            [0u8; HASH_LENGTH],
+            b"metadata",
             last_action_time,
-            player: state_player,
-            deposit: 1_000_000u64,
-            status: GameSessionStatus::Active,
-            public_config_seed: [0u8; HASH_LENGTH],
-            game_metadata: "metadata".to_string(),
-            player_moves: [0u8; zero_fun::MAX_MOVE_COUNT],
-            vault: state_vault,
-            next_player_move_position: 0u8,
-        };
+        );
 
         create_game_session_account(svm, game_session, &game_session_account);
 
@@ -72,10 +69,16 @@ impl TestSetup {
         set_current_time(svm, current_time);
 
         // Build the instruction
+        let player_profile = create_player_profile_account(svm, instruction_player.pubkey());
+
+        let global_state = create_default_global_state_account(svm);
+
         let accounts: Vec<AccountMeta> = vec![
             AccountMeta::new(instruction_player.pubkey(), true),
             AccountMeta::new(instruction_vault, false),
             AccountMeta::new(game_session, false),
+            AccountMeta::new(player_profile, false),
+            AccountMeta::new(global_state, false),
         ];
 
         let instruction = Instruction {
@@ -174,6 +177,23 @@ impl TestSetup {
             current_time,
         )
     }
+
+    pub fn with_daily_challenge_session(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let (instructions, signers) = Self::with_default(svm)?;
+
+        let game_session = instructions[0].accounts[2].pubkey;
+
+        // Make the session part of a daily challenge
+        let mut game_session_account = svm.get_account(&game_session).unwrap();
+        let (game_session_state, _) =
+            GameSession::split_account_data_mut(&mut game_session_account.data);
+        game_session_state.enter_daily_challenge();
+        svm.set_account(game_session, game_session_account)?;
+
+        Ok((instructions, signers))
+    }
 }
 
 #[test]
@@ -282,3 +302,53 @@ fn test_default_game_fails_when_too_soon_to_default() {
         zero_fun::GameError::TooSoonToDefault,
     );
 }
+
+#[test]
+fn test_can_default_after_max_lifetime() {
+    let start_time = 123456789i64;
+
+    let mut game_session = GameSession::new(
+        Pubkey::new_unique(),
+        1_000_000u64,
+        Pubkey::new_unique(),
+        [0u8; HASH_LENGTH],
+        b"metadata",
+        start_time,
+    );
+
+    game_session.set_default_windows(DEFAULT_OFFSET, 2 * DEFAULT_OFFSET);
+
+    // The session is kept active right up to its maximum lifetime
+    game_session.last_action_time = start_time + 2 * DEFAULT_OFFSET;
+
+    assert!(!game_session.can_default(start_time + 2 * DEFAULT_OFFSET));
+    assert!(game_session.can_default(start_time + 2 * DEFAULT_OFFSET + 1));
+}
+
+#[test]
+fn test_default_game_fails_with_daily_challenge_session() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_daily_challenge_session(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::DailyChallengeSession,
+    );
+}
@@ -0,0 +1,379 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_jackpot_account, create_game_config_account,
+    create_game_session_account_with_moves, create_global_state_account,
+    create_player_profile_account, create_vault_account,
+};
+
+use zero_fun::{
+    instruction::{FinalizeGameAsLost, RecordAction, SettleCashOut},
+    instructions::RecordActionArgs,
+    DiceRules, FinalizeGameAsLostArgs, GameConfig, GameParameters, GameSession, GameState,
+    GameVariant, GlobalState, SettleCashOutArgs, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
+    PACKED_MOVE_PAGE_LENGTH, PUBLIC_SEED,
+};
+
+const DICE_RULES: DiceRules = DiceRules { roll_count: 100 };
+
+// Here what is relevant is that Dice sessions settle in a single reveal through the
+// same instructions as the tile ladder, with the roll derived from the private
+// config seed and the client seed.
+struct TestSetup {
+    player: Keypair,
+    private_config_seed: [u8; HASH_LENGTH],
+    roll: u8,
+    game_session: Pubkey,
+    user_vault: Pubkey,
+    vault: Pubkey,
+    global_state: Pubkey,
+    game_config: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    /// Creates a Dice session, with the bet already recorded and cashed out if
+    /// `bet` is set.
+    fn new(svm: &mut LiteSVM, bet: Option<fn(u8) -> u8>) -> Result<Self> {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        let client_seed = Pubkey::new_unique().to_bytes();
+
+        let roll = DICE_RULES.get_roll(&private_config_seed, &client_seed);
+
+        // Create game session
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let user_vault = Pubkey::new_unique();
+
+        let mut game_session_account = GameSession::new(
+            player.pubkey(),
+            1_000_000u64,
+            user_vault,
+            public_config_seed,
+            b"metadata",
+            0,
+        );
+
+        // The variant decides how wide the packed moves are, so it is set first
+        game_session_account.set_variant(GameVariant::Dice);
+        game_session_account.client_seed = client_seed;
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        if let Some(bet) = bet {
+            game_session_account.set_next_player_move(&mut player_moves, bet(roll))?;
+            game_session_account.cash_out(0);
+        }
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session,
+            &game_session_account,
+            &player_moves,
+        );
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        create_vault_account(svm, user_vault, rent + 1_000_000u64);
+
+        let game_config = create_game_config_account(
+            svm,
+            GameConfig::new(GameState::Active, GameParameters::Dice(DICE_RULES), 255),
+        );
+
+        Ok(Self {
+            player,
+            private_config_seed,
+            roll,
+            game_session,
+            user_vault,
+            vault,
+            global_state,
+            game_config,
+        })
+    }
+
+    /// A bet that wins against `roll`.
+    fn winning_bet(roll: u8) -> u8 {
+        if roll > 0 {
+            DiceRules::encode_move(roll - 1, true)
+        } else {
+            DiceRules::encode_move(1, false)
+        }
+    }
+
+    /// A bet that loses against `roll`.
+    fn losing_bet(roll: u8) -> u8 {
+        if roll > 0 {
+            DiceRules::encode_move(roll, false)
+        } else {
+            DiceRules::encode_move(0, true)
+        }
+    }
+
+    pub fn with_bet(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, None)?;
+
+        let action = Self::winning_bet(setup.roll);
+
+        Ok(setup.record_action(action))
+    }
+
+    pub fn with_invalid_bet(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, None)?;
+
+        // Over the highest roll can never win
+        let action = DiceRules::encode_move(DICE_RULES.roll_count - 1, true);
+
+        Ok(setup.record_action(action))
+    }
+
+    pub fn with_losing_bet(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, Some(Self::losing_bet))?;
+
+        let player_profile = create_player_profile_account(svm, setup.player.pubkey());
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(setup.game_session, false),
+            AccountMeta::new(setup.player.pubkey(), false),
+            AccountMeta::new(setup.user_vault, false),
+            AccountMeta::new(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(setup.game_config, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
+        ];
+
+        let args = FinalizeGameAsLostArgs {
+            private_config_seed: setup.private_config_seed,
+            fail_position: 0,
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: FinalizeGameAsLost { args }.data(),
+        };
+
+        Ok(([instruction], vec![setup.player]))
+    }
+
+    pub fn with_winning_bet(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, Some(Self::winning_bet))?;
+
+        let jackpot = create_default_jackpot_account(svm);
+
+        let player_profile = create_player_profile_account(svm, setup.player.pubkey());
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(setup.game_session, false),
+            AccountMeta::new(setup.player.pubkey(), false),
+            AccountMeta::new(setup.user_vault, false),
+            AccountMeta::new(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(setup.game_config, false),
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
+        ];
+
+        let args = SettleCashOutArgs {
+            private_config_seed: Some(setup.private_config_seed),
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: SettleCashOut { args }.data(),
+        };
+
+        Ok(([instruction], vec![setup.player]))
+    }
+
+    fn record_action(self, action: u8) -> ([Instruction; 1], Vec<Keypair>) {
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(self.player.pubkey(), true),
+            AccountMeta::new(self.global_state, false),
+            AccountMeta::new(self.game_session, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            AccountMeta::new_readonly(self.game_config, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: RecordAction {
+                args: RecordActionArgs { action },
+            }
+            .data(),
+        };
+
+        ([instruction], vec![self.player])
+    }
+}
+
+#[test]
+fn test_dice_record_action_cashes_out() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_bet(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[2].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let game_session_account = svm
+        .get_account(&game_session)
+        .expect("Game session should still be open");
+
+    let (game_session, _) = GameSession::split_account_data(&game_session_account.data);
+
+    assert!(game_session.is_cashed_out());
+}
+
+#[test]
+fn test_dice_record_action_fails_with_invalid_bet() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_bet(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidMove,
+    );
+}
+
+#[test]
+fn test_dice_finalize_game_as_lost_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_losing_bet(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+}
+
+#[test]
+fn test_dice_settle_cash_out_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_winning_bet(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let vault = instructions[0].accounts[3].pubkey;
+
+    let vault_balance_before = svm.get_balance(&vault).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert!(svm.get_balance(&vault).unwrap() < vault_balance_before);
+}
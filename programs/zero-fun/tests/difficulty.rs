@@ -0,0 +1,461 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_fee_vault_account, create_default_jackpot_account, create_game_config_account,
+    create_game_session_account_with_moves, create_global_state_account,
+    create_player_profile_account, create_vault_account,
+};
+
+use zero_fun::{
+    instruction::{FinalizeGameAsLost, InitializeGame},
+    Difficulty, FailMoveDerivation, FinalizeGameAsLostArgs, GameConfig, GameParameters,
+    GameSession, GameState, GlobalState, InitializeGameArgs, TileRules, HASH_LENGTH,
+    ID as ZERO_FUN_PROGRAM_ID, PACKED_MOVE_PAGE_LENGTH, PUBLIC_SEED,
+};
+
+// Here what is relevant is that the difficulty is fixed when the session is created and
+// the loss proof accepts any of the round's fail tiles at that difficulty.
+struct TestSetup {
+    player: Keypair,
+    private_config_seed: [u8; HASH_LENGTH],
+    game_session: Pubkey,
+    user_vault: Pubkey,
+    vault: Pubkey,
+    global_state: Pubkey,
+    game_config: Pubkey,
+    jackpot: Pubkey,
+    player_profile: Pubkey,
+    fee_vault: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    /// Creates a session at `difficulty` with a single move, on the round's last fail tile
+    /// if `hit_fail_tile` is set and on its first safe tile otherwise.
+    fn new(
+        svm: &mut LiteSVM,
+        tile_rules: TileRules,
+        difficulty: Difficulty,
+        fail_move_derivation: FailMoveDerivation,
+        hit_fail_tile: bool,
+    ) -> Result<Self> {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        let move_type_count =
+            tile_rules.get_move_type_count_for_round(&public_config_seed, 0, difficulty);
+
+        let fail_moves = tile_rules.get_fail_moves_for_round(
+            &private_config_seed,
+            0,
+            move_type_count,
+            difficulty,
+            fail_move_derivation,
+        );
+
+        let recorded_move = if hit_fail_tile {
+            7 - fail_moves.leading_zeros() as u8
+        } else {
+            fail_moves.trailing_ones() as u8
+        };
+
+        // Create game session
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let user_vault = Pubkey::new_unique();
+
+        let mut game_session_account = GameSession::new(
+            player.pubkey(),
+            1_000_000u64,
+            user_vault,
+            public_config_seed,
+            b"metadata",
+            0,
+        );
+
+        game_session_account.set_difficulty(difficulty);
+        game_session_account.fail_move_derivation = fail_move_derivation.into();
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        game_session_account.set_next_player_move(&mut player_moves, recorded_move)?;
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session,
+            &game_session_account,
+            &player_moves,
+        );
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        create_vault_account(svm, user_vault, rent + 1_000_000u64);
+
+        let game_config = create_game_config_account(
+            svm,
+            GameConfig::new(GameState::Active, GameParameters::Tiles(tile_rules), 255),
+        );
+
+        let jackpot = create_default_jackpot_account(svm);
+
+        let player_profile = create_player_profile_account(svm, player.pubkey());
+
+        let fee_vault = create_default_fee_vault_account(svm);
+
+        Ok(Self {
+            player,
+            private_config_seed,
+            game_session,
+            user_vault,
+            vault,
+            global_state,
+            game_config,
+            jackpot,
+            player_profile,
+            fee_vault,
+        })
+    }
+
+    fn finalize_game_as_lost(&self) -> Instruction {
+        let accounts = vec![
+            AccountMeta::new(self.game_session, false),
+            AccountMeta::new(self.player.pubkey(), false),
+            AccountMeta::new(self.user_vault, false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new(self.global_state, false),
+            AccountMeta::new_readonly(self.game_config, false),
+            AccountMeta::new(self.player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
+        ];
+
+        let args = FinalizeGameAsLostArgs {
+            private_config_seed: self.private_config_seed,
+            fail_position: 0,
+        };
+
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: FinalizeGameAsLost { args }.data(),
+        }
+    }
+
+    fn initialize_game(&self, difficulty: Difficulty) -> Instruction {
+        let public_config_seed = Pubkey::new_unique().to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                self.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let (user_vault, _) = Pubkey::find_program_address(
+            &[
+                b"vault",
+                public_config_seed.as_ref(),
+                self.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let accounts = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(self.player.pubkey(), true),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new_readonly(self.vault, false),
+            AccountMeta::new(self.global_state, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(self.game_config, false),
+            AccountMeta::new(self.jackpot, false),
+            AccountMeta::new(self.player_profile, false),
+            AccountMeta::new(self.fee_vault, false),
+        ];
+
+        let args = InitializeGameArgs {
+            public_config_seed,
+            game_metadata: "V0".to_string(),
+            deposit: 10_000,
+            difficulty,
+            ..Default::default()
+        };
+
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: InitializeGame { args }.data(),
+        }
+    }
+
+    fn send(
+        &self,
+        svm: &mut LiteSVM,
+        instruction: Instruction,
+    ) -> litesvm::types::TransactionResult {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.player.pubkey()),
+            &[&self.player],
+            svm.latest_blockhash(),
+        );
+
+        svm.send_transaction(transaction)
+    }
+}
+
+#[test]
+fn test_finalize_game_as_lost_success_with_last_fail_tile_at_hard() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    // Hard has two fail tiles, either of them loses
+    let setup = TestSetup::new(
+        &mut svm,
+        TileRules::default(),
+        Difficulty::Hard,
+        FailMoveDerivation::PartialShuffle,
+        true,
+    )
+    .unwrap();
+
+    let instruction = setup.finalize_game_as_lost();
+
+    assert_transaction_success(setup.send(&mut svm, instruction));
+}
+
+#[test]
+fn test_finalize_game_as_lost_success_with_last_fail_tile_at_expert() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(
+        &mut svm,
+        TileRules::default(),
+        Difficulty::Expert,
+        FailMoveDerivation::PartialShuffle,
+        true,
+    )
+    .unwrap();
+
+    let instruction = setup.finalize_game_as_lost();
+
+    assert_transaction_success(setup.send(&mut svm, instruction));
+}
+
+#[test]
+fn test_finalize_game_as_lost_success_with_consecutive_fail_tiles() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    // Sessions created before the derivation was versioned keep their fail tiles
+    let setup = TestSetup::new(
+        &mut svm,
+        TileRules::default(),
+        Difficulty::Hard,
+        FailMoveDerivation::Consecutive,
+        true,
+    )
+    .unwrap();
+
+    let instruction = setup.finalize_game_as_lost();
+
+    assert_transaction_success(setup.send(&mut svm, instruction));
+}
+
+#[test]
+fn test_finalize_game_as_lost_fails_with_safe_tile_at_hard() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(
+        &mut svm,
+        TileRules::default(),
+        Difficulty::Hard,
+        FailMoveDerivation::PartialShuffle,
+        false,
+    )
+    .unwrap();
+
+    let instruction = setup.finalize_game_as_lost();
+
+    assert_custom_transaction_error_at(
+        setup.send(&mut svm, instruction),
+        0,
+        zero_fun::GameError::InvalidFailPosition,
+    );
+}
+
+#[test]
+fn test_fail_moves_for_round_match_fail_move_with_single_fail_tile() {
+    let tile_rules = TileRules::default();
+
+    // Both derivations agree with FMFR when there is a single fail tile
+    for _ in 0..64 {
+        let private_config_seed = Pubkey::new_unique().to_bytes();
+
+        for move_type_count in 2..=8 {
+            let fail_move = tile_rules.get_fail_move_for_round(
+                &private_config_seed,
+                0,
+                move_type_count,
+                Difficulty::Normal,
+            );
+
+            for fail_move_derivation in [
+                FailMoveDerivation::Consecutive,
+                FailMoveDerivation::PartialShuffle,
+            ] {
+                assert_eq!(
+                    tile_rules.get_fail_moves_for_round(
+                        &private_config_seed,
+                        0,
+                        move_type_count,
+                        Difficulty::Normal,
+                        fail_move_derivation,
+                    ),
+                    1 << fail_move
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fail_moves_for_round_are_distinct() {
+    let tile_rules = TileRules::default();
+
+    for _ in 0..64 {
+        let private_config_seed = Pubkey::new_unique().to_bytes();
+
+        let fail_moves = tile_rules.get_fail_moves_for_round(
+            &private_config_seed,
+            0,
+            4,
+            Difficulty::Expert,
+            FailMoveDerivation::PartialShuffle,
+        );
+
+        assert_eq!(fail_moves.count_ones(), 3);
+        assert!(fail_moves < 1 << 4);
+    }
+}
+
+#[test]
+fn test_initialize_game_success_with_difficulty() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(
+        &mut svm,
+        TileRules::default(),
+        Difficulty::Normal,
+        FailMoveDerivation::PartialShuffle,
+        false,
+    )
+    .unwrap();
+
+    let instruction = setup.initialize_game(Difficulty::Expert);
+
+    let game_session = instruction.accounts[0].pubkey;
+
+    assert_transaction_success(setup.send(&mut svm, instruction));
+
+    let game_session_data = svm.get_account(&game_session).unwrap().data;
+
+    let (game_session, _) = GameSession::split_account_data(&game_session_data);
+
+    assert_eq!(game_session.get_difficulty(), Difficulty::Expert);
+    assert_eq!(
+        game_session.get_fail_move_derivation(),
+        FailMoveDerivation::PartialShuffle
+    );
+}
+
+#[test]
+fn test_initialize_game_fails_with_unsupported_difficulty() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    // Easy rounds have at least six tiles, which the variant does not allow
+    let tile_rules = TileRules {
+        min_move_type_count: 2,
+        max_move_type_count: 4,
+    };
+
+    let setup = TestSetup::new(
+        &mut svm,
+        tile_rules,
+        Difficulty::Normal,
+        FailMoveDerivation::PartialShuffle,
+        false,
+    )
+    .unwrap();
+
+    let instruction = setup.initialize_game(Difficulty::Easy);
+
+    assert_custom_transaction_error_at(
+        setup.send(&mut svm, instruction),
+        0,
+        zero_fun::GameError::InvalidDifficulty,
+    );
+}
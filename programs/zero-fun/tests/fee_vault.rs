@@ -0,0 +1,197 @@
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_fee_vault_account, create_global_state_account,
+};
+
+use zero_fun::{
+    instruction::WithdrawFees, FeeVault, GameState, GlobalState, WithdrawFeesArgs,
+    ID as ZERO_FUN_PROGRAM_ID,
+};
+
+const FEES: u64 = 500_000;
+
+// Here what is relevant is that only the admin can withdraw the fees and that they can't
+// take more than the fees collected.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, instruction_admin.pubkey(), instruction_admin, FEES)
+    }
+
+    pub fn with_invalid_admin(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, Pubkey::new_unique(), instruction_admin, FEES)
+    }
+
+    pub fn with_amount_exceeding_fees(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        // The rent of the fee vault can't be withdrawn
+        Self::builder(svm, instruction_admin.pubkey(), instruction_admin, FEES + 1)
+    }
+
+    fn builder(
+        svm: &mut LiteSVM,
+        state_admin: Pubkey,
+        instruction_admin: Keypair,
+        withdraw_amount: u64,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Create the admin account
+        svm.airdrop(&instruction_admin.pubkey(), 1_000_000_000)
+            .unwrap();
+
+        let (global_state_key, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (_, vault_bump) = Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state = GlobalState::new(
+            state_admin,
+            Pubkey::default(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            0,
+        );
+
+        create_global_state_account(svm, global_state_key, global_state);
+
+        let mut fee_vault = FeeVault::new(0);
+
+        fee_vault.balance = FEES;
+        fee_vault.total_collected = FEES;
+
+        let fee_vault_key = create_fee_vault_account(svm, fee_vault);
+
+        let recipient_key = Pubkey::new_unique();
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new_readonly(global_state_key, false),
+            AccountMeta::new(fee_vault_key, false),
+            AccountMeta::new(recipient_key, false),
+            AccountMeta::new_readonly(instruction_admin.pubkey(), true),
+        ];
+
+        let args = WithdrawFeesArgs {
+            amount: withdraw_amount,
+        };
+
+        let withdraw_fees = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: WithdrawFees { args }.data(),
+        };
+
+        Ok(([withdraw_fees], vec![instruction_admin]))
+    }
+}
+
+#[test]
+fn test_withdraw_fees_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let fee_vault = instructions[0].accounts[1].pubkey;
+    let recipient = instructions[0].accounts[2].pubkey;
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert_eq!(svm.get_balance(&recipient).unwrap(), FEES);
+
+    let fee_vault =
+        FeeVault::try_deserialize(&mut svm.get_account(&fee_vault).unwrap().data.as_slice())
+            .unwrap();
+
+    assert_eq!(fee_vault.balance, 0);
+    assert_eq!(fee_vault.total_withdrawn, FEES);
+}
+
+#[test]
+fn test_withdraw_fees_fails_with_invalid_admin() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_admin(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidAdmin,
+    );
+}
+
+#[test]
+fn test_withdraw_fees_fails_with_amount_exceeding_fees() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_amount_exceeding_fees(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InsufficientFees,
+    );
+}
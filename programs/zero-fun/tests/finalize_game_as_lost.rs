@@ -1,5 +1,5 @@
 use anchor_lang::solana_program::hash::hashv;
-use anchor_lang::InstructionData;
+use anchor_lang::{AccountDeserialize, InstructionData};
 use anyhow::Result;
 use litesvm::LiteSVM;
 use solana_sdk::{
@@ -12,12 +12,15 @@ use solana_sdk::{
 mod common;
 use common::utils::{
     add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
-    create_game_session_account, create_global_state_account, create_vault_account,
+    create_default_game_config_account, create_game_session_account_with_moves,
+    create_global_state_account, create_player_profile_account, create_program_account,
+    create_referrer_rewards_account, create_vault_account, set_current_time,
 };
 
 use zero_fun::{
     instruction::FinalizeGameAsLost, FinalizeGameAsLostArgs, GameSession, GameSessionStatus,
-    GameState, GlobalState, ID as ZERO_FUN_PROGRAM_ID, MAX_MOVE_COUNT, MAX_MOVE_TYPE_COUNT,
+    GameState, GlobalState, PlayerProfile, ReferrerRewards, CASH_OUT_REVEAL_WINDOW, DEFAULT_OFFSET,
+    ID as ZERO_FUN_PROGRAM_ID, MAX_MOVE_TYPE_COUNT, MOVE_PAGE_SIZE, PACKED_MOVE_PAGE_LENGTH,
     PUBLIC_SEED,
 };
 
@@ -26,6 +29,10 @@ struct TestSetup {}
 impl TestSetup {
     const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
 
+    fn random_fail_position() -> u16 {
+        u16::try_from(rand::random_range(0..MOVE_PAGE_SIZE)).unwrap()
+    }
+
     pub fn builder(
         svm: &mut LiteSVM,
         state_player: Pubkey,
@@ -35,6 +42,7 @@ impl TestSetup {
         game_session_status: GameSessionStatus,
         correct_public_config: bool,
         matching_move: bool,
+        fail_position: u16,
     ) -> Result<([Instruction; 1], Vec<Keypair>)> {
         // Create the player
         svm.airdrop(&instruction_player.pubkey(), 1_000_000_000)
@@ -48,25 +56,27 @@ impl TestSetup {
         let derived_public_config_seed =
             hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
 
-        // Generate a fail position
-        let fail_position = u8::try_from(rand::random_range(0..MAX_MOVE_COUNT)).unwrap();
+        // Rounds below 256 are hashed as a single byte, later ones as two little-endian bytes
+        let fail_position_bytes = fail_position.to_le_bytes();
+        let round_seed: &[u8] = if fail_position < 256 {
+            &fail_position_bytes[..1]
+        } else {
+            &fail_position_bytes
+        };
 
         // Compute public_config_seed_for_move and number of move types for the round
         let public_config_seed_for_move =
-            hashv(&[&[fail_position], derived_public_config_seed.as_ref()]).to_bytes();
+            hashv(&[round_seed, derived_public_config_seed.as_ref()]).to_bytes();
 
         let move_type_count_for_round: u8 =
             (public_config_seed_for_move[0] % (u8::try_from(MAX_MOVE_TYPE_COUNT).unwrap() - 1)) + 2;
 
         // Compute private_config_seed_for_move and fail_move
         let private_config_seed_for_move =
-            hashv(&[&[fail_position], private_config_seed.as_ref()]).to_bytes();
+            hashv(&[round_seed, private_config_seed.as_ref()]).to_bytes();
 
         let fail_move: u8 = private_config_seed_for_move[0] % move_type_count_for_round;
 
-        // Prepare the GameSession.player_moves array and the stored public_config_seed
-        let mut player_moves = [0u8; MAX_MOVE_COUNT];
-
         // If matching_move is true, record the real fail_move. Otherwise record a different move.
         let recorded_move = if matching_move {
             fail_move
@@ -75,11 +85,6 @@ impl TestSetup {
             (fail_move + 1) % move_type_count_for_round
         };
 
-        player_moves[usize::from(fail_position)] = recorded_move;
-
-        // Ensure next_player_move_position > fail_position
-        let next_player_move_position = fail_position + 1;
-
         // Decide what public_config_seed to store in GameSession
         let pub_config_seed_to_store = if correct_public_config {
             derived_public_config_seed
@@ -98,19 +103,34 @@ impl TestSetup {
             &Self::ZERO_FUN_PROGRAM_ID,
         );
 
-        let game_session_account = GameSession {
-            last_action_time: 0,
-            player: state_player,
-            deposit: 1_000_000u64,
-            status: game_session_status,
-            public_config_seed: pub_config_seed_to_store,
-            game_metadata: "metadata".to_string(),
-            player_moves,
-            vault: state_vault,
-            next_player_move_position,
-        };
+        let mut game_session_account = GameSession::new(
+            state_player,
+            1_000_000u64,
+            state_vault,
+            pub_config_seed_to_store,
+            b"metadata",
+            0,
+        );
+
+        game_session_account.set_status(game_session_status);
 
-        create_game_session_account(svm, game_session_pda, &game_session_account);
+        // Fill the moves up to and including the fail position, so that
+        // next_player_move_position > fail_position
+        let page_count = usize::from(fail_position) / MOVE_PAGE_SIZE + 1;
+        let mut player_moves = vec![0u8; page_count * PACKED_MOVE_PAGE_LENGTH];
+
+        for _ in 0..fail_position {
+            game_session_account.set_next_player_move(&mut player_moves, 0)?;
+        }
+
+        game_session_account.set_next_player_move(&mut player_moves, recorded_move)?;
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session_pda,
+            &game_session_account,
+            &player_moves,
+        );
 
         // Create global state & main vault
         let (vault_pda, vault_bump) =
@@ -122,10 +142,33 @@ impl TestSetup {
         let global_state_account = GlobalState {
             admin: Pubkey::new_unique(),
             message_signer: Pubkey::new_unique(),
-            max_deposit: 10u8,
-            max_payout: 100u8,
+            max_deposit: 10u16,
+            max_payout: 100u16,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
             game_state: GameState::Active,
             vault_bump: vault_bump,
+            house_edge: 0u16,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve: 0,
+            daily_withdraw_limit: 0,
+            withdraw_buckets: Default::default(),
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: Default::default(),
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes: 0,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
         };
 
         create_global_state_account(svm, global_state, global_state_account);
@@ -139,13 +182,21 @@ impl TestSetup {
 
         create_vault_account(svm, instruction_vault, rent + deposit_amount); // user vault holds deposit
 
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
         // Build the instruction
+        let player_profile = create_player_profile_account(svm, instruction_player.pubkey());
+
         let accounts: Vec<AccountMeta> = vec![
             AccountMeta::new(game_session_pda, false),
             AccountMeta::new(instruction_player.pubkey(), false),
             AccountMeta::new(instruction_vault, false),
             AccountMeta::new(vault_pda, false),
             AccountMeta::new(global_state, false),
+            AccountMeta::new_readonly(game_config, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
         ];
 
         let args = FinalizeGameAsLostArgs {
@@ -177,6 +228,67 @@ impl TestSetup {
             GameSessionStatus::Active,
             true,
             true,
+            Self::random_fail_position(),
+        )
+    }
+
+    pub fn with_referred_player(
+        svm: &mut LiteSVM,
+        has_referrer_rewards: bool,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let (mut instructions, signers) = Self::with_default(svm)?;
+
+        let global_state = instructions[0].accounts[4].pubkey;
+        let player_profile = instructions[0].accounts[6].pubkey;
+
+        // Share half of the house edge with the referrer
+        let mut global_state_account = GlobalState::try_deserialize(
+            &mut svm.get_account(&global_state).unwrap().data.as_slice(),
+        )?;
+
+        global_state_account.house_edge = 100;
+        global_state_account.referral_fee = 5_000;
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let referrer = Pubkey::new_unique();
+
+        let mut player_profile_account = PlayerProfile::try_deserialize(
+            &mut svm.get_account(&player_profile).unwrap().data.as_slice(),
+        )?;
+
+        player_profile_account.referrer = referrer;
+
+        create_program_account(svm, player_profile, &player_profile_account);
+
+        if has_referrer_rewards {
+            let referrer_rewards =
+                create_referrer_rewards_account(svm, ReferrerRewards::new(referrer, 0));
+
+            instructions[0].accounts[7] = AccountMeta::new(referrer_rewards, false);
+        }
+
+        Ok((instructions, signers))
+    }
+
+    pub fn with_cashed_out_game_session(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        let vault = Pubkey::new_unique();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            vault,
+            vault,
+            GameSessionStatus::CashedOut, // Cashed out at time 0
+            true,
+            true,
+            Self::random_fail_position(),
         )
     }
 
@@ -195,6 +307,7 @@ impl TestSetup {
             GameSessionStatus::Active,
             true,
             true,
+            Self::random_fail_position(),
         )
     }
 
@@ -214,6 +327,7 @@ impl TestSetup {
             GameSessionStatus::Active,
             true,
             true,
+            Self::random_fail_position(),
         )
     }
 
@@ -234,6 +348,7 @@ impl TestSetup {
             GameSessionStatus::Active,
             false, // Incorrect public config
             true,
+            Self::random_fail_position(),
         )
     }
 
@@ -254,8 +369,46 @@ impl TestSetup {
             GameSessionStatus::Active,
             true,
             false, // recorded move != computed move
+            Self::random_fail_position(),
+        )
+    }
+    pub fn with_fail_position_beyond_first_page(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        let vault = Pubkey::new_unique();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            vault,
+            vault,
+            GameSessionStatus::Active,
+            true,
+            true,
+            300, // Past the first page and past a single byte round index
         )
     }
+
+    pub fn with_daily_challenge_session(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let (instructions, signers) = Self::with_default(svm)?;
+
+        let game_session = instructions[0].accounts[0].pubkey;
+
+        // Make the session part of a daily challenge
+        let mut game_session_account = svm.get_account(&game_session).unwrap();
+        let (game_session_state, _) =
+            GameSession::split_account_data_mut(&mut game_session_account.data);
+        game_session_state.enter_daily_challenge();
+        svm.set_account(game_session, game_session_account)?;
+
+        Ok((instructions, signers))
+    }
 }
 
 #[test]
@@ -272,6 +425,69 @@ fn test_finalize_game_as_lost_success() {
         Err(error) => panic!("Failed to create instruction: {}", error),
     };
 
+    let user_vault = instructions[0].accounts[2].pubkey;
+    let global_state = instructions[0].accounts[4].pubkey;
+
+    let user_vault_balance = svm.get_balance(&user_vault).unwrap();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The deposit, with the vault's rent, is recorded as swept into the vault
+    let global_state =
+        GlobalState::try_deserialize(&mut svm.get_account(&global_state).unwrap().data.as_slice())
+            .unwrap();
+
+    assert_eq!(global_state.total_swept, user_vault_balance);
+    assert_eq!(global_state.total_paid_out, 0);
+}
+
+#[test]
+fn test_finalize_game_as_lost_success_with_fail_position_beyond_first_page() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_fail_position_beyond_first_page(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+}
+
+#[test]
+fn test_finalize_game_as_lost_success_with_cashed_out_game_session() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_cashed_out_game_session(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    set_current_time(&mut svm, CASH_OUT_REVEAL_WINDOW);
+
     let recent_blockhash = svm.latest_blockhash();
 
     let payer = signers[0].pubkey();
@@ -282,6 +498,36 @@ fn test_finalize_game_as_lost_success() {
     assert_transaction_success(svm.send_transaction(transaction));
 }
 
+#[test]
+fn test_finalize_game_as_lost_fails_with_expired_reveal_window() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_cashed_out_game_session(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    set_current_time(&mut svm, CASH_OUT_REVEAL_WINDOW + 1);
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::RevealWindowExpired,
+    );
+}
+
 #[test]
 fn test_finalize_game_as_lost_fails_with_invalid_player() {
     let mut svm = LiteSVM::new();
@@ -393,3 +639,94 @@ fn test_finalize_game_as_lost_fails_with_invalid_fail_position() {
         zero_fun::GameError::InvalidFailPosition,
     );
 }
+
+#[test]
+fn test_finalize_game_as_lost_success_with_referred_player() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_referred_player(&mut svm, true);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let referrer_rewards = instructions[0].accounts[7].pubkey;
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let referrer_rewards = ReferrerRewards::try_deserialize(
+        &mut svm.get_account(&referrer_rewards).unwrap().data.as_slice(),
+    )
+    .unwrap();
+
+    // Half of the 1% house edge on the 1_000_000 lamport deposit
+    assert_eq!(referrer_rewards.unclaimed, 5_000);
+    assert_eq!(referrer_rewards.total_earned, 5_000);
+}
+
+#[test]
+fn test_finalize_game_as_lost_fails_with_missing_referrer_rewards() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_referred_player(&mut svm, false);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidReferrerRewards,
+    );
+}
+
+#[test]
+fn test_finalize_game_as_lost_fails_with_daily_challenge_session() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_daily_challenge_session(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::DailyChallengeSession,
+    );
+}
@@ -1,5 +1,6 @@
+use anchor_lang::solana_program::hash::hashv;
 use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ADDRESS;
-use anchor_lang::InstructionData;
+use anchor_lang::{AccountDeserialize, InstructionData};
 use anyhow::Result;
 use litesvm::LiteSVM;
 use solana_sdk::{
@@ -12,14 +13,15 @@ use solana_sdk::{
 mod common;
 use common::utils::{
     add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
-    create_game_session_account, create_global_state_account, create_vault_account,
-    ed25519_instruction_for_parts, set_current_time,
+    create_default_jackpot_account, create_game_session_account, create_global_state_account,
+    create_player_profile_account, create_vault_account, ed25519_instruction_for_parts,
+    set_current_time,
 };
 
 use zero_fun::{
-    instruction::FinalizeGameAsWon, FinalizeGameAsWonArgs, GameSession, GameSessionStatus,
-    GameState, GlobalState, FINALIZE_WIN_ACTION, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
-    MAX_MOVE_COUNT,
+    instruction::FinalizeGameAsWon, FinalizeGameAsWonArgs, GameSession, GameState, GlobalState,
+    DEFAULT_OFFSET, FINALIZE_WIN_ACTION, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
+    PAYOUT_BREAKER_BUCKET_LENGTH, PAYOUT_BREAKER_WINDOW, PUBLIC_SEED,
 };
 
 struct FinalizeWonTestParams {
@@ -33,16 +35,28 @@ struct FinalizeWonTestParams {
     pub signed_payout: u64,
     pub signed_deadline: i64,
     pub signed_public_config_seed: [u8; HASH_LENGTH],
+    pub signed_private_config_seed: [u8; HASH_LENGTH],
 
     // instruction values (what is passed into the program)
     pub instruction_payout: u64,
     pub instruction_deadline: i64,
     pub state_public_config_seed: [u8; HASH_LENGTH],
+    pub instruction_private_config_seed: [u8; HASH_LENGTH],
 
     // time + vault settings
     pub current_time: i64,
     pub vault_balance: u64,
-    pub global_state_max_payout_bps: u8,
+    pub global_state_max_payout_bps: u16,
+}
+
+/// A private config seed and the public config seed derived from it.
+fn get_config_seeds() -> ([u8; HASH_LENGTH], [u8; HASH_LENGTH]) {
+    let private_config_seed = hashv(&[Pubkey::new_unique().as_ref()]).to_bytes();
+
+    let public_config_seed =
+        hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+    (private_config_seed, public_config_seed)
 }
 
 struct TestSetup {}
@@ -68,17 +82,14 @@ impl TestSetup {
             &Self::ZERO_FUN_PROGRAM_ID,
         );
 
-        let game_session_account = GameSession {
-            last_action_time: 0,
-            player: params.state_player,
-            deposit: 1_000_000u64,
-            status: GameSessionStatus::Active,
-            public_config_seed: params.state_public_config_seed,
-            game_metadata: "meta".to_string(),
-            player_moves: [0u8; MAX_MOVE_COUNT],
-            vault: params.state_vault,
-            next_player_move_position: 0,
-        };
+        let game_session_account = GameSession::new(
+            params.state_player,
+            1_000_000u64,
+            params.state_vault,
+            params.state_public_config_seed,
+            b"meta",
+            0,
+        );
 
         create_game_session_account(svm, game_session, &game_session_account);
 
@@ -94,10 +105,33 @@ impl TestSetup {
         let global_state_account = GlobalState {
             admin: Pubkey::new_unique(),
             message_signer: message_signer.pubkey(),
-            max_deposit: 10u8,
+            max_deposit: 10u16,
             max_payout: params.global_state_max_payout_bps,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
             game_state: GameState::Active,
             vault_bump: vault_bump,
+            house_edge: 0u16,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve: 0,
+            daily_withdraw_limit: 0,
+            withdraw_buckets: Default::default(),
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: Default::default(),
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes: 0,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
         };
 
         create_global_state_account(svm, global_state, global_state_account);
@@ -111,6 +145,8 @@ impl TestSetup {
 
         create_vault_account(svm, params.instruction_vault, rent + deposit_amount);
 
+        let jackpot = create_default_jackpot_account(svm);
+
         // Set current time
         set_current_time(svm, params.current_time);
 
@@ -118,11 +154,12 @@ impl TestSetup {
         let signed_payout_bytes = params.signed_payout.to_le_bytes();
         let signed_deadline_bytes = params.signed_deadline.to_le_bytes();
 
-        let parts: [&[u8]; 4] = [
+        let parts: [&[u8]; 5] = [
             FINALIZE_WIN_ACTION.as_bytes(),
             &signed_payout_bytes,
             &signed_deadline_bytes,
             params.signed_public_config_seed.as_ref(),
+            params.signed_private_config_seed.as_ref(),
         ];
 
         let ed25519_instruction = ed25519_instruction_for_parts(&message_signer, &parts);
@@ -131,8 +168,11 @@ impl TestSetup {
         let args = FinalizeGameAsWonArgs {
             payout: params.instruction_payout,
             deadline: params.instruction_deadline,
+            private_config_seed: params.instruction_private_config_seed,
         };
 
+        let player_profile = create_player_profile_account(svm, params.instruction_player.pubkey());
+
         let accounts: Vec<AccountMeta> = vec![
             AccountMeta::new(game_session, false),
             AccountMeta::new(params.instruction_player.pubkey(), true),
@@ -141,6 +181,10 @@ impl TestSetup {
             AccountMeta::new(global_state, false),
             AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
             AccountMeta::new_readonly(INSTRUCTIONS_SYSVAR_ADDRESS, false),
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
         ];
 
         let program_instruction = Instruction {
@@ -160,7 +204,7 @@ impl TestSetup {
         let state_player = instruction_player.pubkey();
         let vault = Pubkey::new_unique();
 
-        let public_config_seed = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, public_config_seed) = get_config_seeds();
 
         let params = FinalizeWonTestParams {
             instruction_player,
@@ -173,21 +217,69 @@ impl TestSetup {
             instruction_deadline: 1_750_000_000i64,
             state_public_config_seed: public_config_seed,
             signed_public_config_seed: public_config_seed,
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             current_time: 1_650_000_000i64,
             vault_balance: 1_000_000_000u64,
-            global_state_max_payout_bps: 10u8,
+            global_state_max_payout_bps: 10u16,
         };
 
         Self::builder(svm, params)
     }
 
+    fn with_global_state(
+        svm: &mut LiteSVM,
+        update_global_state: impl FnOnce(&mut GlobalState),
+    ) -> Result<([Instruction; 2], Vec<Keypair>)> {
+        let result = Self::with_default(svm)?;
+
+        let global_state = result.0[1].accounts[4].pubkey;
+
+        let mut global_state_account = GlobalState::try_deserialize(
+            &mut svm.get_account(&global_state).unwrap().data.as_slice(),
+        )?;
+
+        update_global_state(&mut global_state_account);
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        Ok(result)
+    }
+
+    pub fn with_settlement_restricted(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 2], Vec<Keypair>)> {
+        Self::with_global_state(svm, |global_state| {
+            global_state.game_state = GameState::Restricted;
+        })
+    }
+
+    pub fn with_payout_breaker_tripped(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 2], Vec<Keypair>)> {
+        // The payout of 100 lamports crosses the threshold, even though it is well below
+        // the deposit of 1_000_000 lamports
+        Self::with_global_state(svm, |global_state| {
+            global_state.payout_breaker_threshold = 99;
+        })
+    }
+
+    pub fn with_payout_exceeds_max_lamports(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 2], Vec<Keypair>)> {
+        // The payout of 100 lamports is within the bps limit but not the absolute one
+        Self::with_global_state(svm, |global_state| {
+            global_state.max_payout_lamports = 100;
+        })
+    }
+
     pub fn with_invalid_player(svm: &mut LiteSVM) -> Result<([Instruction; 2], Vec<Keypair>)> {
         let instruction_player = Keypair::new();
         let state_player = Pubkey::new_unique();
 
         let vault = Pubkey::new_unique();
 
-        let public_config_seed = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, public_config_seed) = get_config_seeds();
 
         let params = FinalizeWonTestParams {
             state_player, // different from instruction
@@ -199,10 +291,12 @@ impl TestSetup {
             signed_deadline: 1_750_000_000i64,
             instruction_deadline: 1_750_000_000i64,
             signed_public_config_seed: public_config_seed,
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             state_public_config_seed: public_config_seed,
             current_time: 1_650_000_000i64,
             vault_balance: 1_000_000_000u64,
-            global_state_max_payout_bps: 10u8,
+            global_state_max_payout_bps: 10u16,
         };
 
         Self::builder(svm, params)
@@ -215,7 +309,7 @@ impl TestSetup {
         let state_vault = Pubkey::new_unique();
         let instruction_vault = Pubkey::new_unique();
 
-        let public_config_seed = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, public_config_seed) = get_config_seeds();
 
         let params = FinalizeWonTestParams {
             state_player,
@@ -227,10 +321,12 @@ impl TestSetup {
             signed_deadline: 1_750_000_000i64,
             instruction_deadline: 1_750_000_000i64,
             signed_public_config_seed: public_config_seed,
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             state_public_config_seed: public_config_seed,
             current_time: 1_650_000_000i64,
             vault_balance: 1_000_000_000u64,
-            global_state_max_payout_bps: 10u8,
+            global_state_max_payout_bps: 10u16,
         };
 
         Self::builder(svm, params)
@@ -244,7 +340,7 @@ impl TestSetup {
 
         let vault = Pubkey::new_unique();
 
-        let public_config_seed = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, public_config_seed) = get_config_seeds();
 
         let params = FinalizeWonTestParams {
             instruction_player,
@@ -257,9 +353,11 @@ impl TestSetup {
             instruction_deadline: 1_750_000_000i64,
             state_public_config_seed: public_config_seed,
             signed_public_config_seed: public_config_seed,
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             current_time: 1_650_000_000i64,
             vault_balance: 1_000_000_000u64,
-            global_state_max_payout_bps: 10u8,
+            global_state_max_payout_bps: 10u16,
         };
 
         Self::builder(svm, params)
@@ -272,7 +370,7 @@ impl TestSetup {
         let state_player = instruction_player.pubkey();
         let vault = Pubkey::new_unique();
 
-        let public_config_seed = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, public_config_seed) = get_config_seeds();
 
         let params = FinalizeWonTestParams {
             instruction_player,
@@ -285,9 +383,11 @@ impl TestSetup {
             instruction_deadline: 1_750_000_000i64, // different from signed(commited to by the message signer)
             state_public_config_seed: public_config_seed,
             signed_public_config_seed: public_config_seed,
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             current_time: 1_650_000_000i64,
             vault_balance: 1_000_000_000u64,
-            global_state_max_payout_bps: 10u8,
+            global_state_max_payout_bps: 10u16,
         };
 
         Self::builder(svm, params)
@@ -302,7 +402,7 @@ impl TestSetup {
 
         let signed_public_config = Pubkey::new_unique().to_bytes();
 
-        let state_public_config = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, state_public_config) = get_config_seeds();
 
         let params = FinalizeWonTestParams {
             state_player,
@@ -314,10 +414,44 @@ impl TestSetup {
             signed_deadline: 1_750_000_000i64,
             instruction_deadline: 1_750_000_000i64,
             signed_public_config_seed: signed_public_config, // different from state(stored in the game session account)
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             state_public_config_seed: state_public_config, // different from signed(commited to by the message signer)
             current_time: 1_650_000_000i64,
             vault_balance: 1_000_000_000u64,
-            global_state_max_payout_bps: 10u8,
+            global_state_max_payout_bps: 10u16,
+        };
+
+        Self::builder(svm, params)
+    }
+
+    pub fn with_mismatched_signed_private_config(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 2], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+        let vault = Pubkey::new_unique();
+
+        let (private_config_seed, public_config_seed) = get_config_seeds();
+
+        let (signed_private_config_seed, _) = get_config_seeds();
+
+        let params = FinalizeWonTestParams {
+            state_player,
+            instruction_player,
+            state_vault: vault,
+            instruction_vault: vault,
+            signed_payout: 100,
+            instruction_payout: 100,
+            signed_deadline: 1_750_000_000i64,
+            instruction_deadline: 1_750_000_000i64,
+            signed_public_config_seed: public_config_seed,
+            signed_private_config_seed, // different from instruction
+            instruction_private_config_seed: private_config_seed, // different from signed(commited to by the message signer)
+            state_public_config_seed: public_config_seed,
+            current_time: 1_650_000_000i64,
+            vault_balance: 1_000_000_000u64,
+            global_state_max_payout_bps: 10u16,
         };
 
         Self::builder(svm, params)
@@ -328,7 +462,7 @@ impl TestSetup {
         let state_player = instruction_player.pubkey();
         let vault = Pubkey::new_unique();
 
-        let public_config_seed = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, public_config_seed) = get_config_seeds();
 
         let instruction_deadline = 1_650_000_000i64;
 
@@ -345,9 +479,11 @@ impl TestSetup {
             signed_deadline: instruction_deadline,
             state_public_config_seed: public_config_seed,
             signed_public_config_seed: public_config_seed,
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             current_time,
             vault_balance: 1_000_000_000u64,
-            global_state_max_payout_bps: 10u8,
+            global_state_max_payout_bps: 10u16,
         };
 
         Self::builder(svm, params)
@@ -358,11 +494,11 @@ impl TestSetup {
         let state_player = instruction_player.pubkey();
         let vault = Pubkey::new_unique();
 
-        let public_config_seed = Pubkey::new_unique().to_bytes();
+        let (private_config_seed, public_config_seed) = get_config_seeds();
 
         let vault_balance = 1_000_000_000u64;
 
-        let max_bps = 10u8; // Max payout = 1_000_000_000
+        let max_bps = 10u16; // Max payout = 1_000_000_000
 
         let params = FinalizeWonTestParams {
             state_player,
@@ -375,6 +511,8 @@ impl TestSetup {
             instruction_deadline: 1_750_000_000i64,
             state_public_config_seed: public_config_seed,
             signed_public_config_seed: public_config_seed,
+            signed_private_config_seed: private_config_seed,
+            instruction_private_config_seed: private_config_seed,
             current_time: 1_650_000_000i64,
             vault_balance,
             global_state_max_payout_bps: max_bps,
@@ -550,6 +688,34 @@ fn test_finalize_game_as_won_fails_with_invalid_public_config() {
     );
 }
 
+#[test]
+fn test_finalize_game_as_won_fails_with_mismatched_signed_private_config() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_mismatched_signed_private_config(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        1,
+        zero_fun::GameError::InvalidCommitment,
+    );
+}
+
 #[test]
 fn test_finalize_game_as_won_fails_when_deadline_passed() {
     let mut svm = LiteSVM::new();
@@ -605,3 +771,145 @@ fn test_finalize_game_as_won_fails_when_payout_exceeds_max() {
         zero_fun::GameError::PayoutExceedsMaximum,
     );
 }
+
+#[test]
+fn test_finalize_game_as_won_fails_when_payout_exceeds_max_lamports() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_payout_exceeds_max_lamports(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        1,
+        zero_fun::GameError::PayoutExceedsMaximum,
+    );
+}
+
+#[test]
+fn test_finalize_game_as_won_fails_when_settlement_restricted() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_settlement_restricted(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        1,
+        zero_fun::GameError::SettlementRestricted,
+    );
+}
+
+#[test]
+fn test_finalize_game_as_won_success_trips_payout_breaker() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_payout_breaker_tripped(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let global_state = instructions[1].accounts[4].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    // The win that trips the breaker is still paid, the ones after it are held back
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let global_state =
+        GlobalState::try_deserialize(&mut svm.get_account(&global_state).unwrap().data.as_slice())
+            .unwrap();
+
+    assert!(global_state.is_restricted());
+    assert_eq!(global_state.get_payouts_in_window(1_650_000_000), 100);
+}
+
+#[test]
+fn test_record_payout_counts_payouts_below_deposit() {
+    let mut global_state = GlobalState {
+        payout_breaker_threshold: 1_000,
+        ..Default::default()
+    };
+
+    // The payouts leave out the deposits, so wins doubling a deposit of 600 count in full
+    assert!(!global_state.record_payout(600, 0));
+    assert!(global_state.record_payout(600, 0));
+
+    assert_eq!(global_state.get_payouts_in_window(0), 1_200);
+    assert!(global_state.is_restricted());
+}
+
+#[test]
+fn test_record_payout_counts_within_rolling_window() {
+    let mut global_state = GlobalState {
+        payout_breaker_threshold: 1_000,
+        ..Default::default()
+    };
+
+    assert!(!global_state.record_payout(600, 0));
+
+    // Payouts on both sides of where a fixed window would restart are counted together
+    assert!(global_state.record_payout(600, PAYOUT_BREAKER_WINDOW - PAYOUT_BREAKER_BUCKET_LENGTH));
+
+    assert!(global_state.is_restricted());
+}
+
+#[test]
+fn test_record_payout_drops_payouts_out_of_window() {
+    let mut global_state = GlobalState {
+        payout_breaker_threshold: 1_000,
+        ..Default::default()
+    };
+
+    assert!(!global_state.record_payout(600, 0));
+    assert!(!global_state.record_payout(400, PAYOUT_BREAKER_BUCKET_LENGTH));
+
+    // The first bucket rolls out of the window, the second one is still in it
+    assert_eq!(
+        global_state.get_payouts_in_window(PAYOUT_BREAKER_WINDOW),
+        400
+    );
+
+    assert!(!global_state.record_payout(600, PAYOUT_BREAKER_WINDOW));
+    assert!(global_state.record_payout(1, PAYOUT_BREAKER_WINDOW));
+
+    assert!(global_state.is_restricted());
+}
@@ -11,12 +11,14 @@ use solana_sdk::{
 mod common;
 use common::utils::{
     add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
-    create_game_session_account, create_global_state_account, create_vault_account,
+    create_game_session_account, create_global_state_account, create_player_profile_account,
+    create_vault_account,
 };
 
 use zero_fun::{
     instruction::FinalizeGameAsWonForPlayer, FinalizeGameAsWonForPlayerArgs, GameSession,
-    GameSessionStatus, GameState, GlobalState, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
+    GameSessionStatus, GameState, GlobalState, DEFAULT_OFFSET, HASH_LENGTH,
+    ID as ZERO_FUN_PROGRAM_ID,
 };
 
 struct TestSetup {}
@@ -48,17 +50,16 @@ impl TestSetup {
             &Self::ZERO_FUN_PROGRAM_ID,
         );
 
-        let game_session_account = GameSession {
-            last_action_time: 0,
-            player: state_player,
-            deposit: 1_000_000u64,
-            status: game_session_status,
-            public_config_seed: [0; HASH_LENGTH],
-            game_metadata: "metadata".to_string(),
-            player_moves: [0u8; zero_fun::MAX_MOVE_COUNT],
-            vault: state_player_vault,
-            next_player_move_position: 0u8,
-        };
+        let mut game_session_account = GameSession::new(
+            state_player,
+            1_000_000u64,
+            state_player_vault,
+            [0; HASH_LENGTH],
+            b"metadata",
+            0,
+        );
+
+        game_session_account.set_status(game_session_status);
 
         create_game_session_account(svm, game_session_pda, &game_session_account);
 
@@ -72,10 +73,33 @@ impl TestSetup {
         let global_state_account = GlobalState {
             admin: state_admin,
             message_signer: Pubkey::new_unique(),
-            max_deposit: 10u8,
-            max_payout: 100u8,
+            max_deposit: 10u16,
+            max_payout: 100u16,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
             game_state: GameState::Active,
             vault_bump: vault_bump,
+            house_edge: 0u16,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve: 0,
+            daily_withdraw_limit: 0,
+            withdraw_buckets: Default::default(),
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: Default::default(),
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes: 0,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
         };
 
         create_global_state_account(svm, global_state, global_state_account);
@@ -96,6 +120,8 @@ impl TestSetup {
         );
 
         // Build instruction
+        let player_profile = create_player_profile_account(svm, instruction_player.pubkey());
+
         let accounts: Vec<AccountMeta> = vec![
             AccountMeta::new(game_session_pda, false),
             AccountMeta::new(instruction_player.pubkey(), false),
@@ -103,6 +129,9 @@ impl TestSetup {
             AccountMeta::new(vault, false),
             AccountMeta::new(global_state, false),
             AccountMeta::new(instruction_admin.pubkey(), true),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
         ];
 
         let args = FinalizeGameAsWonForPlayerArgs { payout };
@@ -1,4 +1,4 @@
-use anchor_lang::InstructionData;
+use anchor_lang::{AccountDeserialize, InstructionData};
 use anyhow::Result;
 use litesvm::LiteSVM;
 use solana_sdk::{
@@ -12,12 +12,15 @@ use solana_sdk::{
 mod common;
 use common::utils::{
     add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
-    create_global_state_account, create_vault_account,
+    create_default_fee_vault_account, create_default_game_config_account,
+    create_default_jackpot_account, create_global_state_account, create_vault_account,
+    get_player_profile_address,
 };
 
 use zero_fun::{
-    instruction::InitializeGame, GameState, GlobalState, InitializeGameArgs, HASH_LENGTH,
-    ID as ZERO_FUN_PROGRAM_ID, MAX_METADATA_LENGTH,
+    instruction::InitializeGame, Difficulty, FeeVault, GameSession, GameState, GlobalState,
+    InitializeGameArgs, DEFAULT_OFFSET, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
+    MAX_METADATA_LENGTH,
 };
 
 // Here what is relevant is that the player should have signed(the system program would test this),
@@ -36,7 +39,7 @@ impl TestSetup {
         metadata: String,
         deposit: u64,
         vault_balance: u64,
-        max_deposit_bps: u8,
+        max_deposit_bps: u16,
         game_state: GameState,
     ) -> Result<([Instruction; 1], Vec<Keypair>)> {
         // Create the player account
@@ -74,9 +77,32 @@ impl TestSetup {
             admin: Pubkey::default(),
             message_signer: Pubkey::default(),
             max_deposit: max_deposit_bps,
-            max_payout: 100u8,
+            max_payout: 100u16,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
             game_state,
             vault_bump: vault_bump as u8,
+            house_edge: 0u16,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve: 0,
+            daily_withdraw_limit: 0,
+            withdraw_buckets: Default::default(),
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: Default::default(),
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes: 0,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
         };
 
         create_global_state_account(svm, global_state, global_state_account);
@@ -84,20 +110,35 @@ impl TestSetup {
         // Create the vault account
         create_vault_account(svm, vault, vault_balance);
 
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        let jackpot = create_default_jackpot_account(svm);
+
+        let fee_vault = create_default_fee_vault_account(svm);
+
         // Build the instruction
         let accounts: Vec<AccountMeta> = vec![
             AccountMeta::new(game_session, false),
             AccountMeta::new(player.pubkey(), true),
             AccountMeta::new(user_vault, false),
             AccountMeta::new_readonly(vault, false),
-            AccountMeta::new_readonly(global_state, false),
+            AccountMeta::new(global_state, false),
             AccountMeta::new_readonly(Self::SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(game_config, false),
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(get_player_profile_address(&player.pubkey()), false),
+            AccountMeta::new(fee_vault, false),
         ];
 
         let args = InitializeGameArgs {
             public_config_seed: [0u8; HASH_LENGTH],
             game_metadata: metadata,
             deposit,
+            hash_chain_head: None,
+            client_seed: None,
+            difficulty: Difficulty::Normal,
+            auto_play_commitment: None,
+            practice: false,
         };
 
         let initialize_game = Instruction {
@@ -113,7 +154,7 @@ impl TestSetup {
         let metadata = "V0".to_string();
         let deposit = 10_000u64;
         let vault_balance = 1_000_000_000u64;
-        let max_deposit_bps = 10u8;
+        let max_deposit_bps = 10u16;
         let game_state = GameState::Active;
 
         Self::builder(
@@ -130,7 +171,7 @@ impl TestSetup {
         let metadata = "0".repeat(MAX_METADATA_LENGTH + 1); // Metadata exceeds max by 1
         let deposit = 10_000u64;
         let vault_balance = 1_000_000_000u64;
-        let max_deposit_bps = 10u8;
+        let max_deposit_bps = 10u16;
         let game_state = GameState::Active;
 
         Self::builder(
@@ -147,7 +188,7 @@ impl TestSetup {
         let metadata = "V0".to_string();
         let deposit = 101u64; // will exceed computed max deposit
         let vault_balance = 1_000_000u64;
-        let max_deposit_bps = 1u8;
+        let max_deposit_bps = 1u16;
         let game_state = GameState::Active;
 
         Self::builder(
@@ -164,7 +205,7 @@ impl TestSetup {
         let metadata = "V0".to_string();
         let deposit = 10_000u64;
         let vault_balance = 1_000_000_000u64;
-        let max_deposit_bps = 10u8;
+        let max_deposit_bps = 10u16;
         let game_state = GameState::Locked; // Games can only be created when the game is active
 
         Self::builder(
@@ -176,6 +217,66 @@ impl TestSetup {
             game_state,
         )
     }
+
+    fn with_global_state(
+        svm: &mut LiteSVM,
+        update_global_state: impl FnOnce(&mut GlobalState),
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let result = Self::with_default(svm)?;
+
+        let global_state = result.0[0].accounts[4].pubkey;
+
+        let mut global_state_account = GlobalState::try_deserialize(
+            &mut svm.get_account(&global_state).unwrap().data.as_slice(),
+        )?;
+
+        update_global_state(&mut global_state_account);
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        Ok(result)
+    }
+
+    pub fn with_protocol_fee(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Take 1% of the deposit as the protocol fee
+        Self::with_global_state(svm, |global_state| {
+            global_state.protocol_fee = 100;
+        })
+    }
+
+    pub fn with_default_windows(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::with_global_state(svm, |global_state| {
+            global_state.default_offset = 2 * DEFAULT_OFFSET;
+            global_state.max_session_lifetime = 3 * DEFAULT_OFFSET;
+        })
+    }
+
+    pub fn with_deposit_below_min(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // The deposit of 10_000 lamports is 1 short of the minimum
+        Self::with_global_state(svm, |global_state| {
+            global_state.min_deposit = 10_001;
+        })
+    }
+
+    pub fn with_deposit_exceeds_max_lamports(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // The deposit is within the bps limit but not the absolute one
+        Self::with_global_state(svm, |global_state| {
+            global_state.max_deposit_lamports = 9_999;
+        })
+    }
+
+    pub fn with_game_variant_not_active(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let result = Self::with_default(svm)?;
+
+        // Sessions can only be created for an active variant
+        create_default_game_config_account(svm, GameState::Locked);
+
+        Ok(result)
+    }
 }
 
 #[test]
@@ -258,6 +359,62 @@ fn test_initialize_game_fails_when_deposit_exceeds_max() {
     );
 }
 
+#[test]
+fn test_initialize_game_fails_when_deposit_exceeds_max_lamports() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_deposit_exceeds_max_lamports(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::DepositExceedsMaximum,
+    );
+}
+
+#[test]
+fn test_initialize_game_fails_when_deposit_below_min() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_deposit_below_min(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::DepositBelowMinimum,
+    );
+}
+
 #[test]
 fn test_initialize_game_fails_when_game_not_active() {
     let mut svm = LiteSVM::new();
@@ -285,3 +442,114 @@ fn test_initialize_game_fails_when_game_not_active() {
         zero_fun::GameError::GameNotActive,
     );
 }
+
+#[test]
+fn test_initialize_game_fails_when_game_variant_not_active() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_game_variant_not_active(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::GameVariantNotActive,
+    );
+}
+
+#[test]
+fn test_initialize_game_success_with_protocol_fee() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_protocol_fee(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+    let vault = instructions[0].accounts[3].pubkey;
+    let fee_vault = instructions[0].accounts[9].pubkey;
+
+    let vault_balance = svm.get_balance(&vault).unwrap();
+    let fee_vault_balance = svm.get_balance(&fee_vault).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The fee is kept apart from the bankroll
+    assert_eq!(
+        svm.get_balance(&fee_vault).unwrap(),
+        fee_vault_balance + 100
+    );
+    assert_eq!(svm.get_balance(&vault).unwrap(), vault_balance);
+
+    let fee_vault =
+        FeeVault::try_deserialize(&mut svm.get_account(&fee_vault).unwrap().data.as_slice())
+            .unwrap();
+
+    assert_eq!(fee_vault.balance, 100);
+    assert_eq!(fee_vault.total_collected, 100);
+
+    let game_session_data = svm.get_account(&game_session).unwrap().data;
+    let (game_session, _) = GameSession::split_account_data(&game_session_data);
+
+    assert_eq!(game_session.deposit, 9_900);
+}
+
+#[test]
+fn test_initialize_game_success_keeps_default_windows() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default_windows(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The session keeps the windows it was started with
+    let game_session_data = svm.get_account(&game_session).unwrap().data;
+    let (game_session, _) = GameSession::split_account_data(&game_session_data);
+
+    assert_eq!(game_session.default_offset, 2 * DEFAULT_OFFSET);
+    assert_eq!(game_session.max_lifetime, 3 * DEFAULT_OFFSET);
+}
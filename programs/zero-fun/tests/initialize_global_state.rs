@@ -76,9 +76,10 @@ impl TestSetup {
         ];
 
         let args = InitializeGlobalStateArgs {
-            max_deposit: 10u8,
-            max_payout: 100u8,
+            max_deposit: 10u16,
+            max_payout: 100u16,
             initial_state: GameState::Active,
+            house_edge: 0u16,
         };
 
         let initialize = Instruction {
@@ -0,0 +1,519 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ADDRESS;
+use anchor_lang::{AccountDeserialize, InstructionData};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_fee_vault_account, create_default_game_config_account,
+    create_game_session_account, create_global_state_account, create_jackpot_account,
+    create_player_profile_account, create_vault_account, ed25519_instruction_for_parts,
+    get_player_profile_address, set_current_time,
+};
+
+use zero_fun::{
+    instruction::{FinalizeGameAsWon, InitializeGame, InitializeJackpot},
+    FinalizeGameAsWonArgs, GameSession, GameState, GlobalState, InitializeGameArgs,
+    InitializeJackpotArgs, Jackpot, FINALIZE_WIN_ACTION, ID as ZERO_FUN_PROGRAM_ID,
+    MAX_JACKPOT_CONTRIBUTION, PUBLIC_SEED,
+};
+
+const DEPOSIT: u64 = 1_000_000;
+
+const JACKPOT_BALANCE: u64 = 5_000_000;
+
+const DEADLINE: i64 = 1_750_000_000;
+
+// Here what is relevant is that the jackpot takes its share of each deposit when the
+// session is created and pays its whole pool out of its own account when a won session
+// hits it, the global vault only pays the signed payout.
+struct TestSetup {
+    player: Keypair,
+    message_signer: Keypair,
+    vault: Pubkey,
+    global_state: Pubkey,
+    jackpot: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn new(svm: &mut LiteSVM, jackpot_account: Jackpot) -> Self {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create global state & main vault
+        let message_signer = Keypair::new();
+
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            message_signer.pubkey(),
+            10,
+            10,
+            GameState::Active,
+            vault_bump,
+            0,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 1_000_000_000u64);
+
+        let jackpot = create_jackpot_account(svm, jackpot_account);
+
+        set_current_time(svm, DEADLINE - 1_000);
+
+        Self {
+            player,
+            message_signer,
+            vault,
+            global_state,
+            jackpot,
+        }
+    }
+
+    pub fn with_contribution(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        let setup = Self::new(svm, Jackpot::new(MAX_JACKPOT_CONTRIBUTION, u32::MAX, 0, 0));
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        let public_config_seed = Pubkey::new_unique().to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let (user_vault, _) = Pubkey::find_program_address(
+            &[
+                b"vault",
+                public_config_seed.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let fee_vault = create_default_fee_vault_account(svm);
+
+        let accounts = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(setup.player.pubkey(), true),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new_readonly(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(game_config, false),
+            AccountMeta::new(setup.jackpot, false),
+            AccountMeta::new(get_player_profile_address(&setup.player.pubkey()), false),
+            AccountMeta::new(fee_vault, false),
+        ];
+
+        let args = InitializeGameArgs {
+            public_config_seed,
+            game_metadata: "V0".to_string(),
+            deposit: DEPOSIT,
+            ..Default::default()
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: InitializeGame { args }.data(),
+        };
+
+        ([instruction], vec![setup.player])
+    }
+
+    /// Creates a session won with a payout of 100 after `move_count` moves and finalizes
+    /// it.
+    fn with_won_session(
+        svm: &mut LiteSVM,
+        jackpot_account: Jackpot,
+        valid_seed: bool,
+        move_count: u16,
+    ) -> ([Instruction; 2], Vec<Keypair>) {
+        let setup = Self::new(svm, jackpot_account);
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                setup.player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let user_vault = Pubkey::new_unique();
+
+        let mut game_session_account = GameSession::new(
+            setup.player.pubkey(),
+            DEPOSIT,
+            user_vault,
+            public_config_seed,
+            b"meta",
+            0,
+        );
+
+        game_session_account.next_player_move_position = move_count;
+
+        create_game_session_account(svm, game_session, &game_session_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, user_vault, rent + DEPOSIT);
+
+        let payout = 100u64;
+
+        let payout_bytes = payout.to_le_bytes();
+        let deadline_bytes = DEADLINE.to_le_bytes();
+
+        let revealed_seed = if valid_seed {
+            private_config_seed
+        } else {
+            random_seed
+        };
+
+        let parts: [&[u8]; 5] = [
+            FINALIZE_WIN_ACTION.as_bytes(),
+            &payout_bytes,
+            &deadline_bytes,
+            public_config_seed.as_ref(),
+            revealed_seed.as_ref(),
+        ];
+
+        let ed25519_instruction = ed25519_instruction_for_parts(&setup.message_signer, &parts);
+
+        let args = FinalizeGameAsWonArgs {
+            payout,
+            deadline: DEADLINE,
+            private_config_seed: revealed_seed,
+        };
+
+        let player_profile = create_player_profile_account(svm, setup.player.pubkey());
+
+        let accounts = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(setup.player.pubkey(), true),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(INSTRUCTIONS_SYSVAR_ADDRESS, false),
+            AccountMeta::new(setup.jackpot, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: FinalizeGameAsWon { args }.data(),
+        };
+
+        ([ed25519_instruction, instruction], vec![setup.player])
+    }
+
+    pub fn with_jackpot_hit(svm: &mut LiteSVM) -> ([Instruction; 2], Vec<Keypair>) {
+        // Every seed hits with odds of one
+        let mut jackpot = Jackpot::new(0, 1, 0, 0);
+        jackpot.balance = JACKPOT_BALANCE;
+
+        Self::with_won_session(svm, jackpot, true, 0)
+    }
+
+    pub fn with_move_threshold_reached(svm: &mut LiteSVM) -> ([Instruction; 2], Vec<Keypair>) {
+        // The seed is all but certain to miss, the moves hit it instead
+        let mut jackpot = Jackpot::new(0, u32::MAX, 10, 0);
+        jackpot.balance = JACKPOT_BALANCE;
+
+        Self::with_won_session(svm, jackpot, true, 10)
+    }
+
+    pub fn with_jackpot_missed(svm: &mut LiteSVM) -> ([Instruction; 2], Vec<Keypair>) {
+        let mut jackpot = Jackpot::new(0, u32::MAX, 10, 0);
+        jackpot.balance = JACKPOT_BALANCE;
+
+        Self::with_won_session(svm, jackpot, true, 9)
+    }
+
+    pub fn with_invalid_seed(svm: &mut LiteSVM) -> ([Instruction; 2], Vec<Keypair>) {
+        let mut jackpot = Jackpot::new(0, 1, 0, 0);
+        jackpot.balance = JACKPOT_BALANCE;
+
+        Self::with_won_session(svm, jackpot, false, 0)
+    }
+
+    pub fn with_invalid_contribution(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to admin");
+
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            admin.pubkey(),
+            Pubkey::new_unique(),
+            10,
+            10,
+            GameState::Active,
+            vault_bump,
+            0,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent);
+
+        let (jackpot, _) = Pubkey::find_program_address(&[b"jackpot"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let accounts = vec![
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(admin.pubkey(), true),
+            AccountMeta::new_readonly(global_state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+
+        let args = InitializeJackpotArgs {
+            contribution: MAX_JACKPOT_CONTRIBUTION + 1,
+            odds: 1_000,
+            move_threshold: 0,
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: InitializeJackpot { args }.data(),
+        };
+
+        ([instruction], vec![admin])
+    }
+}
+
+fn get_jackpot(svm: &LiteSVM, jackpot: &Pubkey) -> Jackpot {
+    let jackpot_data = svm.get_account(jackpot).unwrap().data;
+
+    Jackpot::try_deserialize(&mut jackpot_data.as_slice()).unwrap()
+}
+
+#[test]
+fn test_initialize_game_contributes_to_jackpot() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_contribution(&mut svm);
+
+    let game_session = instructions[0].accounts[0].pubkey;
+    let jackpot = instructions[0].accounts[7].pubkey;
+
+    let jackpot_balance_before = svm.get_balance(&jackpot).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let contribution = DEPOSIT * u64::from(MAX_JACKPOT_CONTRIBUTION) / 10_000;
+
+    assert_eq!(
+        svm.get_balance(&jackpot).unwrap(),
+        jackpot_balance_before + contribution
+    );
+
+    let jackpot_account = get_jackpot(&svm, &jackpot);
+
+    assert_eq!(jackpot_account.balance, contribution);
+    assert_eq!(jackpot_account.total_contributed, contribution);
+
+    // The session is played with what is left of the deposit
+    let game_session_data = svm.get_account(&game_session).unwrap().data;
+    let (game_session, _) = GameSession::split_account_data(&game_session_data);
+
+    assert_eq!(game_session.deposit, DEPOSIT - contribution);
+}
+
+#[test]
+fn test_finalize_game_as_won_pays_jackpot() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_jackpot_hit(&mut svm);
+
+    let player = signers[0].pubkey();
+    let vault = instructions[1].accounts[3].pubkey;
+    let jackpot = instructions[1].accounts[7].pubkey;
+
+    let vault_balance_before = svm.get_balance(&vault).unwrap();
+    let jackpot_balance_before = svm.get_balance(&jackpot).unwrap();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&player),
+        &signers,
+        recent_blockhash,
+    );
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The global vault only pays the signed payout
+    assert_eq!(svm.get_balance(&vault).unwrap(), vault_balance_before - 100);
+
+    assert_eq!(
+        svm.get_balance(&jackpot).unwrap(),
+        jackpot_balance_before - JACKPOT_BALANCE
+    );
+
+    let jackpot_account = get_jackpot(&svm, &jackpot);
+
+    assert_eq!(jackpot_account.balance, 0);
+    assert_eq!(jackpot_account.total_paid, JACKPOT_BALANCE);
+}
+
+#[test]
+fn test_finalize_game_as_won_pays_jackpot_past_move_threshold() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_move_threshold_reached(&mut svm);
+
+    let player = signers[0].pubkey();
+    let jackpot = instructions[1].accounts[7].pubkey;
+
+    let jackpot_balance_before = svm.get_balance(&jackpot).unwrap();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&player),
+        &signers,
+        recent_blockhash,
+    );
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert_eq!(
+        svm.get_balance(&jackpot).unwrap(),
+        jackpot_balance_before - JACKPOT_BALANCE
+    );
+}
+
+#[test]
+fn test_finalize_game_as_won_skips_jackpot_when_missed() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_jackpot_missed(&mut svm);
+
+    let player = signers[0].pubkey();
+    let jackpot = instructions[1].accounts[7].pubkey;
+
+    let jackpot_balance_before = svm.get_balance(&jackpot).unwrap();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&player),
+        &signers,
+        recent_blockhash,
+    );
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert_eq!(svm.get_balance(&jackpot).unwrap(), jackpot_balance_before);
+}
+
+#[test]
+fn test_finalize_game_as_won_fails_with_invalid_seed() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_invalid_seed(&mut svm);
+
+    let player = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&player),
+        &signers,
+        recent_blockhash,
+    );
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        1,
+        zero_fun::GameError::InvalidGameSeed,
+    );
+}
+
+#[test]
+fn test_initialize_jackpot_fails_with_invalid_contribution() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_invalid_contribution(&mut svm);
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidJackpotParameters,
+    );
+}
@@ -42,17 +42,16 @@ impl TestSetup {
             &Self::ZERO_FUN_PROGRAM_ID,
         );
 
-        let game_session_account = GameSession {
-            last_action_time: 0,
-            player: state_player,
-            deposit: 1_000_000u64,
-            status,
-            public_config_seed: [0u8; HASH_LENGTH],
-            game_metadata: "metadata".to_string(),
-            player_moves: [0u8; zero_fun::MAX_MOVE_COUNT],
-            vault: Pubkey::new_unique(),
-            next_player_move_position: 0u8,
-        };
+        let mut game_session_account = GameSession::new(
+            state_player,
+            1_000_000u64,
+            Pubkey::new_unique(),
+            [0u8; HASH_LENGTH],
+            b"metadata",
+            0,
+        );
+
+        game_session_account.set_status(status);
 
         create_game_session_account(svm, game_session, &game_session_account);
 
@@ -0,0 +1,199 @@
+use anchor_lang::{AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, Space};
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    transaction::Transaction,
+};
+
+mod common;
+
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_global_state_account,
+};
+
+use zero_fun::{
+    instruction::MigrateGlobalState, GameState, GlobalState, LegacyGlobalState,
+    ID as ZERO_FUN_PROGRAM_ID,
+};
+
+// Here what is relevant is that only the admin can migrate the global state, that it is
+// only migrated off the legacy layout and that its fields carry over.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, instruction_admin.pubkey(), instruction_admin, true)
+    }
+
+    pub fn with_invalid_admin(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, Pubkey::new_unique(), instruction_admin, true)
+    }
+
+    pub fn with_already_migrated(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, instruction_admin.pubkey(), instruction_admin, false)
+    }
+
+    fn builder(
+        svm: &mut LiteSVM,
+        state_admin: Pubkey,
+        instruction_admin: Keypair,
+        is_legacy: bool,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Create the admin account
+        svm.airdrop(&instruction_admin.pubkey(), 1_000_000_000)
+            .unwrap();
+
+        let (global_state_key, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (_, vault_bump) = Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let legacy_global_state = LegacyGlobalState {
+            admin: state_admin,
+            message_signer: Pubkey::default(),
+            max_deposit: 10,
+            max_payout: 100,
+            game_state: GameState::Active,
+            vault_bump,
+        };
+
+        if is_legacy {
+            let mut data = GlobalState::DISCRIMINATOR.to_vec();
+
+            legacy_global_state.serialize(&mut data)?;
+
+            let account = SolanaAccount {
+                lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: ZERO_FUN_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            };
+
+            svm.set_account(global_state_key, account)?;
+        } else {
+            create_global_state_account(svm, global_state_key, legacy_global_state.migrate());
+        }
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(global_state_key, false),
+            AccountMeta::new(instruction_admin.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+
+        let migrate_global_state = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: MigrateGlobalState {}.data(),
+        };
+
+        Ok(([migrate_global_state], vec![instruction_admin]))
+    }
+}
+
+#[test]
+fn test_migrate_global_state_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let global_state = instructions[0].accounts[0].pubkey;
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let global_state_account = svm.get_account(&global_state).unwrap();
+
+    assert_eq!(global_state_account.data.len(), 8 + GlobalState::INIT_SPACE);
+
+    let global_state =
+        GlobalState::try_deserialize(&mut global_state_account.data.as_slice()).unwrap();
+
+    assert_eq!(global_state.admin, payer);
+    assert_eq!(global_state.max_deposit, 10);
+    assert_eq!(global_state.max_payout, 100);
+    assert_eq!(global_state.min_deposit, 0);
+    assert_eq!(global_state.max_deposit_lamports, 0);
+    assert_eq!(global_state.max_payout_lamports, 0);
+}
+
+#[test]
+fn test_migrate_global_state_fails_with_invalid_admin() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_admin(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidAdmin,
+    );
+}
+
+#[test]
+fn test_migrate_global_state_fails_when_already_migrated() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_already_migrated(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::GlobalStateAlreadyMigrated,
+    );
+}
@@ -0,0 +1,334 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_jackpot_account, create_game_config_account,
+    create_game_session_account_with_moves, create_global_state_account,
+    create_player_profile_account, create_vault_account,
+};
+
+use zero_fun::{
+    instruction::{FinalizeGameAsLost, RecordAction, SettleCashOut},
+    instructions::RecordActionArgs,
+    FinalizeGameAsLostArgs, GameConfig, GameParameters, GameSession, GameState, GameVariant,
+    GlobalState, MinesRules, SettleCashOutArgs, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
+    PACKED_MOVE_PAGE_LENGTH, PUBLIC_SEED,
+};
+
+const MINES_RULES: MinesRules = MinesRules {
+    tile_count: 25,
+    mine_count: 3,
+};
+
+// Here what is relevant is that Mines sessions go through the same instructions as
+// the tile ladder, with tiles that can only be revealed once and the mines laid out
+// by the private config seed.
+struct TestSetup {
+    player: Keypair,
+    private_config_seed: [u8; HASH_LENGTH],
+    game_session: Pubkey,
+    user_vault: Pubkey,
+    vault: Pubkey,
+    global_state: Pubkey,
+    game_config: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    /// Creates a Mines session where the player revealed `safe_tile_count` safe tiles,
+    /// followed by a mine if `hit_mine` is set.
+    fn new(svm: &mut LiteSVM, safe_tile_count: usize, hit_mine: bool) -> Result<Self> {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        let mine_positions = MINES_RULES.get_mine_positions(&private_config_seed);
+
+        let (safe_tiles, mines): (Vec<u8>, Vec<u8>) =
+            (0..MINES_RULES.tile_count).partition(|tile| mine_positions & (1 << tile) == 0);
+
+        // Create game session
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let user_vault = Pubkey::new_unique();
+
+        let mut game_session_account = GameSession::new(
+            player.pubkey(),
+            1_000_000u64,
+            user_vault,
+            public_config_seed,
+            b"metadata",
+            0,
+        );
+
+        // The variant decides how wide the packed moves are, so it is set first
+        game_session_account.set_variant(GameVariant::Mines);
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        for tile in safe_tiles.iter().take(safe_tile_count) {
+            game_session_account.set_next_player_move(&mut player_moves, *tile)?;
+        }
+
+        if hit_mine {
+            game_session_account.set_next_player_move(&mut player_moves, mines[0])?;
+        }
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session,
+            &game_session_account,
+            &player_moves,
+        );
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        create_vault_account(svm, user_vault, rent + 1_000_000u64);
+
+        let game_config = create_game_config_account(
+            svm,
+            GameConfig::new(GameState::Active, GameParameters::Mines(MINES_RULES), 255),
+        );
+
+        Ok(Self {
+            player,
+            private_config_seed,
+            game_session,
+            user_vault,
+            vault,
+            global_state,
+            game_config,
+        })
+    }
+
+    pub fn with_revealed_tile(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, 2, false)?;
+
+        let game_session_data = svm.get_account(&setup.game_session).unwrap().data;
+        let (game_session, player_moves) = GameSession::split_account_data(&game_session_data);
+
+        // Reveal the first tile again
+        let action = game_session.get_player_move(player_moves, 0).unwrap();
+
+        Ok(setup.record_action(action))
+    }
+
+    pub fn with_mine_hit(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, 2, true)?;
+
+        let player_profile = create_player_profile_account(svm, setup.player.pubkey());
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(setup.game_session, false),
+            AccountMeta::new(setup.player.pubkey(), false),
+            AccountMeta::new(setup.user_vault, false),
+            AccountMeta::new(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(setup.game_config, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
+        ];
+
+        let args = FinalizeGameAsLostArgs {
+            private_config_seed: setup.private_config_seed,
+            fail_position: 2,
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: FinalizeGameAsLost { args }.data(),
+        };
+
+        Ok(([instruction], vec![setup.player]))
+    }
+
+    pub fn with_cash_out(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let setup = Self::new(svm, 3, false)?;
+
+        // Cash the session out, the seed is revealed right away
+        let mut game_session_account = svm.get_account(&setup.game_session).unwrap();
+        let (game_session, _) = GameSession::split_account_data_mut(&mut game_session_account.data);
+        game_session.cash_out(0);
+        svm.set_account(setup.game_session, game_session_account)
+            .unwrap();
+
+        let jackpot = create_default_jackpot_account(svm);
+
+        let player_profile = create_player_profile_account(svm, setup.player.pubkey());
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(setup.game_session, false),
+            AccountMeta::new(setup.player.pubkey(), false),
+            AccountMeta::new(setup.user_vault, false),
+            AccountMeta::new(setup.vault, false),
+            AccountMeta::new(setup.global_state, false),
+            AccountMeta::new_readonly(setup.game_config, false),
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
+        ];
+
+        let args = SettleCashOutArgs {
+            private_config_seed: Some(setup.private_config_seed),
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: SettleCashOut { args }.data(),
+        };
+
+        Ok(([instruction], vec![setup.player]))
+    }
+
+    fn record_action(self, action: u8) -> ([Instruction; 1], Vec<Keypair>) {
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(self.player.pubkey(), true),
+            AccountMeta::new(self.global_state, false),
+            AccountMeta::new(self.game_session, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            AccountMeta::new_readonly(self.game_config, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: RecordAction {
+                args: RecordActionArgs { action },
+            }
+            .data(),
+        };
+
+        ([instruction], vec![self.player])
+    }
+}
+
+#[test]
+fn test_mines_record_action_fails_with_revealed_tile() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_revealed_tile(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidMove,
+    );
+}
+
+#[test]
+fn test_mines_finalize_game_as_lost_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_mine_hit(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+}
+
+#[test]
+fn test_mines_settle_cash_out_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_cash_out(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let vault = instructions[0].accounts[3].pubkey;
+
+    let vault_balance_before = svm.get_balance(&vault).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert!(svm.get_balance(&vault).unwrap() < vault_balance_before);
+}
@@ -0,0 +1,331 @@
+use anchor_lang::InstructionData;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_fee_vault_account, create_default_game_config_account,
+    create_default_jackpot_account, create_global_state_account, create_program_account,
+    create_vault_account, set_current_time,
+};
+
+use zero_fun::{
+    instruction::InitializeGame, GameState, GlobalState, InitializeGameArgs, PlayerLimits,
+    PlayerProfile, DEPOSIT_LIMIT_DAY, ID as ZERO_FUN_PROGRAM_ID, LIMIT_COOLING_OFF_PERIOD,
+};
+
+const DEPOSIT: u64 = 10_000;
+
+const CURRENT_TIME: i64 = 1_000_000;
+
+// Here what is relevant is that `initialize_game` holds the player to the limits on their
+// profile, and that loosened limits only take effect after the cooling-off period.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn builder(
+        svm: &mut LiteSVM,
+        update_profile: impl FnOnce(&mut PlayerProfile),
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        let public_config_seed = Pubkey::new_unique().to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let (user_vault, _) = Pubkey::find_program_address(
+            &[
+                b"vault",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 1_000_000_000u64);
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        let jackpot = create_default_jackpot_account(svm);
+
+        let fee_vault = create_default_fee_vault_account(svm);
+
+        // Create the player's profile with their limits
+        let (player_profile, player_profile_bump) = Pubkey::find_program_address(
+            &[b"player-profile", player.pubkey().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut player_profile_account = PlayerProfile::new(player.pubkey(), player_profile_bump);
+
+        update_profile(&mut player_profile_account);
+
+        create_program_account(svm, player_profile, &player_profile_account);
+
+        set_current_time(svm, CURRENT_TIME);
+
+        let accounts = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(global_state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(game_config, false),
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(player_profile, false),
+            AccountMeta::new(fee_vault, false),
+        ];
+
+        let args = InitializeGameArgs {
+            public_config_seed,
+            game_metadata: "V0".to_string(),
+            deposit: DEPOSIT,
+            ..Default::default()
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: InitializeGame { args }.data(),
+        };
+
+        ([instruction], vec![player])
+    }
+
+    pub fn with_deposit_within_limit(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::builder(svm, |player_profile| {
+            player_profile.set_limits(
+                PlayerLimits {
+                    daily_deposit_limit: 2 * DEPOSIT,
+                    ..Default::default()
+                },
+                0,
+                LIMIT_COOLING_OFF_PERIOD,
+            );
+
+            // The deposit made yesterday doesn't count towards today's limit
+            player_profile.record_game_started(2 * DEPOSIT, CURRENT_TIME - DEPOSIT_LIMIT_DAY);
+        })
+    }
+
+    pub fn with_deposit_over_limit(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::builder(svm, |player_profile| {
+            player_profile.set_limits(
+                PlayerLimits {
+                    daily_deposit_limit: 2 * DEPOSIT,
+                    ..Default::default()
+                },
+                0,
+                LIMIT_COOLING_OFF_PERIOD,
+            );
+
+            player_profile.record_game_started(DEPOSIT + 1, CURRENT_TIME);
+        })
+    }
+
+    pub fn with_too_many_active_sessions(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::builder(svm, |player_profile| {
+            player_profile.set_limits(
+                PlayerLimits {
+                    max_active_sessions: 1,
+                    ..Default::default()
+                },
+                0,
+                LIMIT_COOLING_OFF_PERIOD,
+            );
+
+            player_profile.record_game_started(DEPOSIT, 0);
+        })
+    }
+
+    pub fn with_self_exclusion(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::builder(svm, |player_profile| {
+            player_profile.self_exclude(CURRENT_TIME - 10, 20);
+        })
+    }
+
+    pub fn with_pending_loosened_limit(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::builder(svm, |player_profile| {
+            player_profile.set_limits(
+                PlayerLimits {
+                    max_active_sessions: 1,
+                    ..Default::default()
+                },
+                0,
+                LIMIT_COOLING_OFF_PERIOD,
+            );
+
+            player_profile.record_game_started(DEPOSIT, 0);
+
+            // Lifting the limit is still cooling off
+            player_profile.set_limits(
+                PlayerLimits::default(),
+                CURRENT_TIME - 10,
+                LIMIT_COOLING_OFF_PERIOD,
+            );
+        })
+    }
+}
+
+fn send(
+    svm: &mut LiteSVM,
+    instructions: [Instruction; 1],
+    signers: Vec<Keypair>,
+) -> litesvm::types::TransactionResult {
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    svm.send_transaction(transaction)
+}
+
+#[test]
+fn test_initialize_game_success_with_deposit_within_limit() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_deposit_within_limit(&mut svm);
+
+    assert_transaction_success(send(&mut svm, instructions, signers));
+}
+
+#[test]
+fn test_initialize_game_fails_with_deposit_over_limit() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_deposit_over_limit(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::DepositLimitExceeded,
+    );
+}
+
+#[test]
+fn test_initialize_game_fails_with_too_many_active_sessions() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_too_many_active_sessions(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::TooManyActiveSessions,
+    );
+}
+
+#[test]
+fn test_initialize_game_fails_with_self_exclusion() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_self_exclusion(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::PlayerSelfExcluded,
+    );
+}
+
+#[test]
+fn test_initialize_game_fails_with_pending_loosened_limit() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_pending_loosened_limit(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::TooManyActiveSessions,
+    );
+}
+
+#[test]
+fn test_set_limits_tightens_immediately_and_loosens_after_cooling_off() {
+    let mut player_profile = PlayerProfile::new(Pubkey::new_unique(), 255);
+
+    let tight_limits = PlayerLimits {
+        daily_deposit_limit: DEPOSIT,
+        weekly_deposit_limit: 5 * DEPOSIT,
+        ..Default::default()
+    };
+
+    assert!(!player_profile.set_limits(tight_limits, 0, LIMIT_COOLING_OFF_PERIOD));
+    assert!(player_profile.get_limits(0) == tight_limits);
+
+    // The daily limit is lifted while the weekly one is tightened
+    let loose_limits = PlayerLimits {
+        weekly_deposit_limit: 2 * DEPOSIT,
+        ..Default::default()
+    };
+
+    assert!(player_profile.set_limits(loose_limits, 10, LIMIT_COOLING_OFF_PERIOD));
+
+    let limits = player_profile.get_limits(10 + LIMIT_COOLING_OFF_PERIOD - 1);
+
+    assert_eq!(limits.daily_deposit_limit, DEPOSIT);
+    assert_eq!(limits.weekly_deposit_limit, 2 * DEPOSIT);
+
+    assert!(player_profile.get_limits(10 + LIMIT_COOLING_OFF_PERIOD) == loose_limits);
+
+    // Exclusions can't be shortened
+    player_profile.self_exclude(0, 100);
+    player_profile.self_exclude(0, 10);
+
+    assert!(player_profile.is_excluded(99));
+    assert!(!player_profile.is_excluded(100));
+}
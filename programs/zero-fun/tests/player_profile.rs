@@ -0,0 +1,209 @@
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_transaction_success, create_default_global_state_account,
+    create_game_session_account, create_program_account, create_vault_account,
+    get_player_profile_address, set_current_time,
+};
+
+use zero_fun::{
+    instruction::{DefaultGame, InitializePlayerProfile},
+    GameSession, PlayerProfile, DEFAULT_OFFSET, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
+};
+
+const DEPOSIT: u64 = 1_000_000;
+
+// Here what is relevant is that profiles can be created for any player and that
+// settling a session is reflected in the player's profile.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn with_new_profile(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to payer");
+
+        // The profile is created for someone else, the player does not need to sign
+        let player = Pubkey::new_unique();
+
+        let accounts = vec![
+            AccountMeta::new(get_player_profile_address(&player), false),
+            AccountMeta::new_readonly(player, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: InitializePlayerProfile {}.data(),
+        };
+
+        ([instruction], vec![payer])
+    }
+
+    pub fn with_defaulted_game(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        let user_vault = Pubkey::new_unique();
+
+        let current_time = 123456789i64;
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                [0u8; HASH_LENGTH].as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let game_session_account = GameSession::new(
+            player.pubkey(),
+            DEPOSIT,
+            user_vault,
+            [0u8; HASH_LENGTH],
+            b"metadata",
+            current_time - DEFAULT_OFFSET - 10,
+        );
+
+        create_game_session_account(svm, game_session, &game_session_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, user_vault, rent + DEPOSIT);
+
+        // The profile as it was left by `initialize_game`
+        let (player_profile, player_profile_bump) = Pubkey::find_program_address(
+            &[b"player-profile", player.pubkey().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut player_profile_account = PlayerProfile::new(player.pubkey(), player_profile_bump);
+
+        player_profile_account.record_game_started(DEPOSIT, 0);
+
+        create_program_account(svm, player_profile, &player_profile_account);
+
+        set_current_time(svm, current_time);
+
+        let global_state = create_default_global_state_account(svm);
+
+        let accounts = vec![
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(player_profile, false),
+            AccountMeta::new(global_state, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: DefaultGame {}.data(),
+        };
+
+        Ok(([instruction], vec![player]))
+    }
+}
+
+fn get_player_profile(svm: &LiteSVM, player_profile: &Pubkey) -> PlayerProfile {
+    let player_profile_data = svm.get_account(player_profile).unwrap().data;
+
+    PlayerProfile::try_deserialize(&mut player_profile_data.as_slice()).unwrap()
+}
+
+#[test]
+fn test_initialize_player_profile_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_new_profile(&mut svm);
+
+    let player_profile = instructions[0].accounts[0].pubkey;
+    let player = instructions[0].accounts[1].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let player_profile = get_player_profile(&svm, &player_profile);
+
+    assert_eq!(player_profile.player, player);
+    assert_eq!(player_profile.games_played, 0);
+    assert_eq!(player_profile.active_sessions, 0);
+}
+
+#[test]
+fn test_default_game_success_records_default() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_defaulted_game(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let player_profile = instructions[0].accounts[3].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let player_profile = get_player_profile(&svm, &player_profile);
+
+    assert_eq!(player_profile.games_played, 1);
+    assert_eq!(player_profile.games_defaulted, 1);
+    assert_eq!(player_profile.total_wagered, DEPOSIT);
+    assert_eq!(player_profile.total_paid_out, DEPOSIT);
+    assert_eq!(player_profile.active_sessions, 0);
+}
+
+#[test]
+fn test_player_profile_streaks() {
+    let mut player_profile = PlayerProfile::new(Pubkey::new_unique(), 255);
+
+    for _ in 0..4 {
+        player_profile.record_game_started(DEPOSIT, 0);
+    }
+
+    player_profile.record_game_won(2 * DEPOSIT);
+    player_profile.record_game_won(2 * DEPOSIT);
+    player_profile.record_game_lost();
+    player_profile.record_game_won(2 * DEPOSIT);
+
+    assert_eq!(player_profile.games_won, 3);
+    assert_eq!(player_profile.games_lost, 1);
+    assert_eq!(player_profile.longest_streak, 2);
+    assert_eq!(player_profile.current_streak, 1);
+    assert_eq!(player_profile.active_sessions, 0);
+    assert_eq!(player_profile.total_paid_out, 6 * DEPOSIT);
+}
@@ -0,0 +1,342 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_fee_vault_account, create_default_game_config_account,
+    create_default_jackpot_account, create_game_session_account_with_moves,
+    create_global_state_account, create_vault_account, get_player_profile_address,
+    set_current_time,
+};
+
+use zero_fun::{
+    instruction::{InitializeGame, RevealPracticeGame},
+    GameSession, GameState, GlobalState, InitializeGameArgs, RevealPracticeGameArgs, HASH_LENGTH,
+    ID as ZERO_FUN_PROGRAM_ID, PACKED_MOVE_PAGE_LENGTH, PUBLIC_SEED,
+};
+
+const LAST_ACTION_TIME: i64 = 1_000;
+
+// Here what is relevant is that practice sessions are created with no deposit and no
+// vault, and are closed by revealing their seed without anything being paid out.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn initialize_practice_game(
+        svm: &mut LiteSVM,
+        deposit: u64,
+        hash_chain_head: Option<[u8; HASH_LENGTH]>,
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        let public_config_seed = Pubkey::new_unique().to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        let jackpot = create_default_jackpot_account(svm);
+
+        let fee_vault = create_default_fee_vault_account(svm);
+
+        // The program id stands in for the missing user vault
+        let accounts = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new_readonly(Self::ZERO_FUN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(global_state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(game_config, false),
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(get_player_profile_address(&player.pubkey()), false),
+            AccountMeta::new(fee_vault, false),
+        ];
+
+        let args = InitializeGameArgs {
+            public_config_seed,
+            game_metadata: "V0".to_string(),
+            deposit,
+            practice: true,
+            hash_chain_head,
+            ..Default::default()
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: InitializeGame { args }.data(),
+        };
+
+        ([instruction], vec![player])
+    }
+
+    pub fn with_practice_game(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::initialize_practice_game(svm, 0, None)
+    }
+
+    pub fn with_practice_deposit(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        // Practice sessions can't put anything at stake
+        Self::initialize_practice_game(svm, 1_000, None)
+    }
+
+    pub fn with_practice_hash_chain(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        // Practice sessions have no vault to settle rounds against
+        Self::initialize_practice_game(svm, 0, Some(Pubkey::new_unique().to_bytes()))
+    }
+
+    /// Creates a practice session with a recorded move and reveals it, with its seed if
+    /// `reveal_seed` is set.
+    fn reveal_practice_game(
+        svm: &mut LiteSVM,
+        reveal_seed: bool,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed: [u8; HASH_LENGTH] = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let mut game_session_account = GameSession::new(
+            player.pubkey(),
+            0,
+            Pubkey::default(),
+            public_config_seed,
+            b"metadata",
+            LAST_ACTION_TIME,
+        );
+
+        game_session_account.enable_practice();
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        game_session_account.set_next_player_move(&mut player_moves, 0)?;
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session,
+            &game_session_account,
+            &player_moves,
+        );
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        set_current_time(svm, LAST_ACTION_TIME + 1);
+
+        let accounts = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(player.pubkey(), false),
+            AccountMeta::new_readonly(game_config, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: RevealPracticeGame {
+                args: RevealPracticeGameArgs {
+                    private_config_seed: reveal_seed.then_some(private_config_seed),
+                },
+            }
+            .data(),
+        };
+
+        Ok(([instruction], vec![player]))
+    }
+
+    pub fn with_revealed_seed(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::reveal_practice_game(svm, true)
+    }
+
+    pub fn with_unrevealed_seed(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // The session can't be defaulted yet
+        Self::reveal_practice_game(svm, false)
+    }
+}
+
+#[test]
+fn test_initialize_game_success_with_practice() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_practice_game(&mut svm);
+
+    let game_session = instructions[0].accounts[0].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let game_session_data = svm.get_account(&game_session).unwrap().data;
+    let (game_session, _) = GameSession::split_account_data(&game_session_data);
+
+    assert!(game_session.is_practice());
+    assert_eq!(game_session.deposit, 0);
+    assert_eq!(game_session.vault, Pubkey::default());
+}
+
+#[test]
+fn test_initialize_game_fails_with_practice_deposit() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_practice_deposit(&mut svm);
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidPracticeGame,
+    );
+}
+
+#[test]
+fn test_initialize_game_fails_with_practice_hash_chain() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_practice_hash_chain(&mut svm);
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidPracticeGame,
+    );
+}
+
+#[test]
+fn test_reveal_practice_game_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_revealed_seed(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert!(svm
+        .get_account(&game_session)
+        .is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn test_reveal_practice_game_fails_with_unrevealed_seed() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_unrevealed_seed(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::TooSoonToDefault,
+    );
+}
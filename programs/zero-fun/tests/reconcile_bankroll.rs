@@ -0,0 +1,158 @@
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_global_state_account, create_vault_account,
+};
+
+use zero_fun::{instruction::ReconcileBankroll, GameState, GlobalState, ID as ZERO_FUN_PROGRAM_ID};
+
+// The bankroll held on top of the vault's rent
+const VAULT_BALANCE: u64 = 500_000;
+
+// Here what is relevant is that the vault must hold at least what the ledger says was
+// swept into it less what was paid out and withdrawn.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // The vault was funded with 400_000, swept 300_000 and paid out 200_000
+        Self::builder(svm, 300_000, 200_000)
+    }
+
+    pub fn with_bankroll_mismatch(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // The ledger is short of 1 lamport
+        Self::builder(svm, VAULT_BALANCE + 1, 0)
+    }
+
+    fn builder(
+        svm: &mut LiteSVM,
+        total_swept: u64,
+        total_paid_out: u64,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let payer = Keypair::new();
+
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+        let (global_state_key, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (vault_key, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let mut global_state = GlobalState::new(
+            Pubkey::default(),
+            Pubkey::default(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            0,
+        );
+
+        global_state.total_swept = total_swept;
+        global_state.total_paid_out = total_paid_out;
+
+        create_global_state_account(svm, global_state_key, global_state);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+        create_vault_account(svm, vault_key, rent + VAULT_BALANCE);
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new_readonly(global_state_key, false),
+            AccountMeta::new_readonly(vault_key, false),
+        ];
+
+        let reconcile_bankroll = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: ReconcileBankroll {}.data(),
+        };
+
+        Ok(([reconcile_bankroll], vec![payer]))
+    }
+}
+
+#[test]
+fn test_reconcile_bankroll_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+}
+
+#[test]
+fn test_reconcile_bankroll_fails_with_bankroll_mismatch() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_bankroll_mismatch(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::BankrollMismatch,
+    );
+}
+
+#[test]
+fn test_reconcile_counts_unaccounted_inflows() {
+    let mut global_state = GlobalState::default();
+
+    global_state.record_session_opened(1_000);
+    global_state.record_swept(1_000);
+    global_state.record_paid_out(300);
+    global_state.record_withdrawal(200, 0).unwrap();
+
+    // Funded with 5_000 on top of the rent of 100
+    let vault_lamports = 100 + 5_000 + 1_000 - 300 - 200;
+
+    assert_eq!(global_state.reconcile(vault_lamports, 100).unwrap(), 5_000);
+    assert!(global_state.reconcile(100 + 499, 100).is_err());
+
+    global_state.record_session_closed();
+    global_state.record_session_closed();
+
+    assert_eq!(global_state.open_sessions, 0);
+    assert_eq!(global_state.total_withdrawn, 200);
+}
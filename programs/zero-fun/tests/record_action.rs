@@ -11,12 +11,14 @@ use solana_sdk::{
 mod common;
 use common::utils::{
     add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
-    create_game_session_account, create_global_state_account,
+    create_default_game_config_account, create_game_session_account_with_moves,
+    create_global_state_account,
 };
 
 use zero_fun::{
     instruction::RecordAction, instructions::RecordActionArgs, GameSession, GameSessionStatus,
-    GameState, GlobalState, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID,
+    GameState, GlobalState, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID, MAX_MOVE_TYPE_COUNT,
+    MOVE_PAGE_SIZE, PACKED_MOVE_PAGE_LENGTH,
 };
 
 struct TestSetup {}
@@ -30,6 +32,9 @@ impl TestSetup {
         instruction_player: Keypair,
         global_state_status: GameState,
         game_session_status: GameSessionStatus,
+        action: u8,
+        recorded_moves: usize,
+        hash_chain: bool,
     ) -> Result<([Instruction; 1], Vec<Keypair>)> {
         svm.airdrop(&instruction_player.pubkey(), 1_000_000_000)
             .expect("Could not airdrop to player");
@@ -42,6 +47,7 @@ impl TestSetup {
             100,
             global_state_status,
             255,
+            0,
         );
         create_global_state_account(svm, global_state_pubkey, global_state);
 
@@ -54,31 +60,50 @@ impl TestSetup {
             &Self::ZERO_FUN_PROGRAM_ID,
         );
 
-        let game_session_account = GameSession {
-            last_action_time: 0,
-            player: state_player,
-            deposit: 1_000_000u64,
-            status: game_session_status,
-            public_config_seed: [0u8; HASH_LENGTH],
-            game_metadata: "metadata".to_string(),
-            player_moves: [0u8; zero_fun::MAX_MOVE_COUNT],
-            vault: Pubkey::new_unique(),
-            next_player_move_position: 0u8,
-        };
+        let mut game_session_account = GameSession::new(
+            state_player,
+            1_000_000u64,
+            Pubkey::new_unique(),
+            [0u8; HASH_LENGTH],
+            b"metadata",
+            0,
+        );
+
+        game_session_account.set_status(game_session_status);
+
+        if hash_chain {
+            game_session_account.enable_hash_chain(Pubkey::new_unique().to_bytes());
+        }
+
+        // Fill the session's move storage with the moves that were already made
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        for _ in 0..recorded_moves {
+            game_session_account.set_next_player_move(&mut player_moves, 1)?;
+        }
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session_pubkey,
+            &game_session_account,
+            &player_moves,
+        );
 
-        create_game_session_account(svm, game_session_pubkey, &game_session_account);
+        let game_config = create_default_game_config_account(svm, GameState::Active);
 
         let accounts: Vec<AccountMeta> = vec![
             AccountMeta::new(instruction_player.pubkey(), true),
             AccountMeta::new(global_state_pubkey, false),
             AccountMeta::new(game_session_pubkey, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::id(), false),
+            AccountMeta::new_readonly(game_config, false),
         ];
 
         let instruction = Instruction {
             program_id: Self::ZERO_FUN_PROGRAM_ID,
             accounts,
             data: RecordAction {
-                args: RecordActionArgs { action: 1 },
+                args: RecordActionArgs { action },
             }
             .data(),
         };
@@ -96,6 +121,9 @@ impl TestSetup {
             instruction_player,
             GameState::Active,
             GameSessionStatus::Active,
+            1,
+            0,
+            false,
         )
     }
 
@@ -109,6 +137,9 @@ impl TestSetup {
             instruction_player,
             GameState::Active,
             GameSessionStatus::Active,
+            1,
+            0,
+            false,
         )
     }
 
@@ -124,6 +155,28 @@ impl TestSetup {
             instruction_player,
             GameState::Active,
             GameSessionStatus::Lost,
+            1,
+            0,
+            false,
+        )
+    }
+
+    pub fn with_restricted_global_state(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        // The circuit breaker doesn't hold back sessions already started
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameState::Restricted,
+            GameSessionStatus::Active,
+            1,
+            0,
+            false,
         )
     }
 
@@ -139,6 +192,57 @@ impl TestSetup {
             instruction_player,
             GameState::Locked,
             GameSessionStatus::Active,
+            1,
+            0,
+            false,
+        )
+    }
+
+    pub fn with_invalid_move(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameState::Active,
+            GameSessionStatus::Active,
+            u8::try_from(MAX_MOVE_TYPE_COUNT).unwrap(), // Does not fit in a packed move
+            0,
+            false,
+        )
+    }
+
+    pub fn with_full_move_page(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameState::Active,
+            GameSessionStatus::Active,
+            1,
+            MOVE_PAGE_SIZE, // The next move needs a new page
+            false,
+        )
+    }
+
+    pub fn with_unrevealed_round(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_player = Keypair::new();
+        let state_player = instruction_player.pubkey();
+
+        Self::builder(
+            svm,
+            state_player,
+            instruction_player,
+            GameState::Active,
+            GameSessionStatus::Active,
+            1,
+            1,    // The last move has not been revealed
+            true, // Hash chain session
         )
     }
 }
@@ -166,6 +270,29 @@ fn test_record_action_success() {
     assert_transaction_success(svm.send_transaction(transaction));
 }
 
+#[test]
+fn test_record_action_success_with_restricted_global_state() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_restricted_global_state(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+}
+
 #[test]
 fn test_record_action_fails_with_invalid_player() {
     let mut svm = LiteSVM::new();
@@ -246,3 +373,91 @@ fn test_record_action_fails_with_inactive_global_state() {
         zero_fun::GameError::GameNotActive,
     );
 }
+
+#[test]
+fn test_record_action_fails_with_invalid_move() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_move(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidMove,
+    );
+}
+
+#[test]
+fn test_record_action_success_with_full_move_page() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_full_move_page(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let game_session = instructions[0].accounts[2].pubkey;
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let game_session_account = svm
+        .get_account(&game_session)
+        .expect("Game session should exist");
+
+    assert_eq!(
+        game_session_account.data.len(),
+        GameSession::INITIAL_SPACE + PACKED_MOVE_PAGE_LENGTH
+    );
+}
+
+#[test]
+fn test_record_action_fails_with_unrevealed_round() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_unrevealed_round(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::RoundNotRevealed,
+    );
+}
@@ -0,0 +1,274 @@
+use anchor_lang::{AccountDeserialize, InstructionData};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_program_account, create_referrer_rewards_account, get_player_profile_address,
+};
+
+use zero_fun::{
+    instruction::{ClaimReferralRewards, SetReferrer},
+    GameState, GlobalState, PlayerProfile, ReferrerRewards, ID as ZERO_FUN_PROGRAM_ID,
+};
+
+// Here what is relevant is that players can bind a referrer once, and that referrers can
+// claim the rewards accrued to them, the accruals are covered by the settlement tests.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn set_referrer(
+        svm: &mut LiteSVM,
+        referrer: Option<Pubkey>,
+        existing_referrer: Option<Pubkey>,
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        let player_profile = get_player_profile_address(&player.pubkey());
+
+        if let Some(existing_referrer) = existing_referrer {
+            let (_, player_profile_bump) = Pubkey::find_program_address(
+                &[b"player-profile", player.pubkey().as_ref()],
+                &Self::ZERO_FUN_PROGRAM_ID,
+            );
+
+            let mut player_profile_account =
+                PlayerProfile::new(player.pubkey(), player_profile_bump);
+
+            player_profile_account.referrer = existing_referrer;
+
+            create_program_account(svm, player_profile, &player_profile_account);
+        }
+
+        // The player refers themselves if no referrer is given
+        let referrer = referrer.unwrap_or(player.pubkey());
+
+        let (referrer_rewards, _) = Pubkey::find_program_address(
+            &[b"referrer-rewards", referrer.as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let accounts = vec![
+            AccountMeta::new(player_profile, false),
+            AccountMeta::new(player.pubkey(), true),
+            AccountMeta::new_readonly(referrer, false),
+            AccountMeta::new(referrer_rewards, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: SetReferrer {}.data(),
+        };
+
+        ([instruction], vec![player])
+    }
+
+    pub fn with_referrer(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::set_referrer(svm, Some(Pubkey::new_unique()), None)
+    }
+
+    pub fn with_self_referral(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::set_referrer(svm, None, None)
+    }
+
+    pub fn with_referrer_already_set(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::set_referrer(svm, Some(Pubkey::new_unique()), Some(Pubkey::new_unique()))
+    }
+
+    fn claim_referral_rewards(
+        svm: &mut LiteSVM,
+        unclaimed: u64,
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        let referrer = Keypair::new();
+        svm.airdrop(&referrer.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to referrer");
+
+        let mut referrer_rewards_account = ReferrerRewards::new(referrer.pubkey(), 0);
+
+        referrer_rewards_account.unclaimed = unclaimed;
+        referrer_rewards_account.total_earned = unclaimed;
+
+        let referrer_rewards = create_referrer_rewards_account(svm, referrer_rewards_account);
+
+        let accounts = vec![
+            AccountMeta::new(referrer_rewards, false),
+            AccountMeta::new(referrer.pubkey(), true),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: ClaimReferralRewards {}.data(),
+        };
+
+        ([instruction], vec![referrer])
+    }
+
+    pub fn with_unclaimed_rewards(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::claim_referral_rewards(svm, 50_000)
+    }
+
+    pub fn with_no_unclaimed_rewards(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::claim_referral_rewards(svm, 0)
+    }
+}
+
+fn send(
+    svm: &mut LiteSVM,
+    instructions: &[Instruction; 1],
+    signers: &[Keypair],
+) -> litesvm::types::TransactionResult {
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(&payer), signers, recent_blockhash);
+
+    svm.send_transaction(transaction)
+}
+
+#[test]
+fn test_set_referrer_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_referrer(&mut svm);
+
+    assert_transaction_success(send(&mut svm, &instructions, &signers));
+
+    let player_profile = PlayerProfile::try_deserialize(
+        &mut svm
+            .get_account(&instructions[0].accounts[0].pubkey)
+            .unwrap()
+            .data
+            .as_slice(),
+    )
+    .unwrap();
+
+    let referrer_rewards = ReferrerRewards::try_deserialize(
+        &mut svm
+            .get_account(&instructions[0].accounts[3].pubkey)
+            .unwrap()
+            .data
+            .as_slice(),
+    )
+    .unwrap();
+
+    let referrer = instructions[0].accounts[2].pubkey;
+
+    assert_eq!(player_profile.referrer, referrer);
+    assert_eq!(referrer_rewards.referrer, referrer);
+    assert_eq!(referrer_rewards.referral_count, 1);
+}
+
+#[test]
+fn test_set_referrer_fails_with_self_referral() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_self_referral(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, &instructions, &signers),
+        0,
+        zero_fun::GameError::InvalidReferrer,
+    );
+}
+
+#[test]
+fn test_set_referrer_fails_with_referrer_already_set() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_referrer_already_set(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, &instructions, &signers),
+        0,
+        zero_fun::GameError::ReferrerAlreadySet,
+    );
+}
+
+#[test]
+fn test_claim_referral_rewards_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_unclaimed_rewards(&mut svm);
+
+    let referrer_rewards = instructions[0].accounts[0].pubkey;
+
+    let referrer_rewards_balance = svm.get_balance(&referrer_rewards).unwrap();
+
+    assert_transaction_success(send(&mut svm, &instructions, &signers));
+
+    // Only the rewards leave the account, its rent stays
+    assert_eq!(
+        svm.get_balance(&referrer_rewards).unwrap(),
+        referrer_rewards_balance - 50_000
+    );
+
+    let referrer_rewards = ReferrerRewards::try_deserialize(
+        &mut svm.get_account(&referrer_rewards).unwrap().data.as_slice(),
+    )
+    .unwrap();
+
+    assert_eq!(referrer_rewards.unclaimed, 0);
+    assert_eq!(referrer_rewards.total_claimed, 50_000);
+}
+
+#[test]
+fn test_claim_referral_rewards_fails_with_no_unclaimed_rewards() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_no_unclaimed_rewards(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, &instructions, &signers),
+        0,
+        zero_fun::GameError::NoReferralRewards,
+    );
+}
+
+#[test]
+fn test_get_referral_reward_is_capped() {
+    let mut global_state = GlobalState::new(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        10,
+        100,
+        GameState::Active,
+        255,
+        200,
+    );
+
+    global_state.referral_fee = 2_500;
+
+    // A quarter of the 2% house edge
+    assert_eq!(global_state.get_referral_reward(1_000_000), 5_000);
+
+    global_state.max_referral_reward = 1_000;
+
+    assert_eq!(global_state.get_referral_reward(1_000_000), 1_000);
+}
@@ -0,0 +1,244 @@
+use anchor_lang::{AnchorSerialize, Discriminator, InstructionData};
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_game_session_account, create_global_state_account, create_vault_account,
+};
+
+use zero_fun::{
+    instruction::RefundLegacyGameSession, GameSession, GameSessionStatus, GameState, GlobalState,
+    LegacyGameSession, ID as ZERO_FUN_PROGRAM_ID,
+};
+
+// Here what is relevant is that only the admin can refund a session, that only sessions in
+// the legacy layout are refunded and that the deposit and rent go back to the player.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    const DEPOSIT: u64 = 100_000_000;
+
+    pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, instruction_admin.pubkey(), instruction_admin, true)
+    }
+
+    pub fn with_invalid_admin(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, Pubkey::new_unique(), instruction_admin, true)
+    }
+
+    pub fn with_current_layout(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(svm, instruction_admin.pubkey(), instruction_admin, false)
+    }
+
+    fn builder(
+        svm: &mut LiteSVM,
+        state_admin: Pubkey,
+        instruction_admin: Keypair,
+        is_legacy: bool,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Create the admin account
+        svm.airdrop(&instruction_admin.pubkey(), 1_000_000_000)
+            .unwrap();
+
+        let (global_state_key, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_vault_key, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        create_global_state_account(
+            svm,
+            global_state_key,
+            GlobalState::new(
+                state_admin,
+                Pubkey::default(),
+                10,
+                100,
+                GameState::Active,
+                vault_bump,
+                0,
+            ),
+        );
+
+        let player = Pubkey::new_unique();
+
+        let public_config_seed = [7u8; 32];
+
+        let (user_vault_key, _) = Pubkey::find_program_address(
+            &[b"vault", public_config_seed.as_ref(), player.as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        create_vault_account(svm, user_vault_key, Self::DEPOSIT);
+
+        let game_session_key = Pubkey::new_unique();
+
+        if is_legacy {
+            // Legacy sessions stored the global vault in place of their own.
+            let legacy_game_session = LegacyGameSession {
+                last_action_time: 0,
+                player,
+                deposit: Self::DEPOSIT,
+                status: GameSessionStatus::Active,
+                public_config_seed,
+                game_metadata: String::from("v1"),
+                player_moves: [0; LegacyGameSession::MAX_MOVE_COUNT],
+                vault: global_vault_key,
+                next_player_move_position: 0,
+            };
+
+            let mut data = GameSession::DISCRIMINATOR.to_vec();
+
+            legacy_game_session.serialize(&mut data)?;
+
+            data.resize(8 + LegacyGameSession::SPACE, 0);
+
+            let account = SolanaAccount {
+                lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+                data,
+                owner: ZERO_FUN_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            };
+
+            svm.set_account(game_session_key, account)?;
+        } else {
+            let game_session = GameSession::new(
+                player,
+                Self::DEPOSIT,
+                user_vault_key,
+                public_config_seed,
+                b"v1",
+                0,
+            );
+
+            create_game_session_account(svm, game_session_key, &game_session);
+        }
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(game_session_key, false),
+            AccountMeta::new(player, false),
+            AccountMeta::new(user_vault_key, false),
+            AccountMeta::new_readonly(global_state_key, false),
+            AccountMeta::new_readonly(instruction_admin.pubkey(), true),
+        ];
+
+        let refund_legacy_game_session = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: RefundLegacyGameSession {}.data(),
+        };
+
+        Ok(([refund_legacy_game_session], vec![instruction_admin]))
+    }
+}
+
+#[test]
+fn test_refund_legacy_game_session_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+    let player = instructions[0].accounts[1].pubkey;
+    let user_vault = instructions[0].accounts[2].pubkey;
+
+    let game_session_balance = svm.get_balance(&game_session).unwrap();
+    let user_vault_balance = svm.get_balance(&user_vault).unwrap();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert_eq!(
+        svm.get_balance(&player).unwrap_or_default(),
+        game_session_balance + user_vault_balance
+    );
+
+    assert_eq!(svm.get_balance(&game_session).unwrap_or_default(), 0);
+    assert_eq!(svm.get_balance(&user_vault).unwrap_or_default(), 0);
+}
+
+#[test]
+fn test_refund_legacy_game_session_fails_with_invalid_admin() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_admin(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidAdmin,
+    );
+}
+
+#[test]
+fn test_refund_legacy_game_session_fails_with_current_layout() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_current_layout(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::NotLegacyGameSession,
+    );
+}
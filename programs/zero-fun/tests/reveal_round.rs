@@ -0,0 +1,427 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_game_config_account, create_game_session_account_with_moves,
+    create_global_state_account, create_player_profile_account, create_vault_account,
+};
+
+use zero_fun::{
+    instruction::RevealRound, GameSession, GameState, GlobalState, PlayerProfile, RevealRoundArgs,
+    ID as ZERO_FUN_PROGRAM_ID, MAX_MOVE_TYPE_COUNT, PACKED_MOVE_PAGE_LENGTH,
+};
+
+struct RevealRoundTestParams {
+    pub hash_chain: bool,
+    pub record_move: bool,
+    pub record_fail_move: bool,
+    pub correct_link: bool,
+    pub practice: bool,
+}
+
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn builder(
+        svm: &mut LiteSVM,
+        params: RevealRoundTestParams,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Build a reverse hash chain, the head is committed and the first link is
+        // revealed for round 0
+        let chain_seed = Pubkey::new_unique().to_bytes();
+        let first_link = hashv(&[chain_seed.as_ref()]).to_bytes();
+        let hash_chain_head = hashv(&[first_link.as_ref()]).to_bytes();
+
+        let public_config_seed = Pubkey::new_unique().to_bytes();
+
+        // Compute the number of move types and the fail move for round 0
+        let public_config_seed_for_move = hashv(&[&[0u8], public_config_seed.as_ref()]).to_bytes();
+
+        let move_type_count_for_round: u8 =
+            (public_config_seed_for_move[0] % (u8::try_from(MAX_MOVE_TYPE_COUNT).unwrap() - 1)) + 2;
+
+        let link_for_move = hashv(&[&[0u8], first_link.as_ref()]).to_bytes();
+
+        let fail_move: u8 = link_for_move[0] % move_type_count_for_round;
+
+        let recorded_move = if params.record_fail_move {
+            fail_move
+        } else {
+            (fail_move + 1) % move_type_count_for_round
+        };
+
+        // Create game session
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let user_vault = Pubkey::new_unique();
+
+        let mut game_session_account = GameSession::new(
+            player.pubkey(),
+            1_000_000u64,
+            user_vault,
+            public_config_seed,
+            b"metadata",
+            0,
+        );
+
+        if params.hash_chain {
+            game_session_account.enable_hash_chain(hash_chain_head);
+        }
+
+        if params.practice {
+            game_session_account.enable_practice();
+        }
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        if params.record_move {
+            game_session_account.set_next_player_move(&mut player_moves, recorded_move)?;
+        }
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session,
+            &game_session_account,
+            &player_moves,
+        );
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            0,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent);
+
+        create_vault_account(svm, user_vault, rent + 1_000_000u64);
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        let player_profile = create_player_profile_account(svm, player.pubkey());
+
+        // Build the instruction
+        let hash_chain_link = if params.correct_link {
+            first_link
+        } else {
+            Pubkey::new_unique().to_bytes()
+        };
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(player.pubkey(), false),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(global_state, false),
+            AccountMeta::new_readonly(game_config, false),
+            AccountMeta::new(player_profile, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: RevealRound {
+                args: RevealRoundArgs { hash_chain_link },
+            }
+            .data(),
+        };
+
+        Ok(([instruction], vec![player]))
+    }
+
+    pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            RevealRoundTestParams {
+                hash_chain: true,
+                record_move: true,
+                record_fail_move: false,
+                correct_link: true,
+                practice: false,
+            },
+        )
+    }
+
+    pub fn with_fail_move(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            RevealRoundTestParams {
+                hash_chain: true,
+                record_move: true,
+                record_fail_move: true,
+                correct_link: true,
+                practice: false,
+            },
+        )
+    }
+
+    pub fn with_invalid_link(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            RevealRoundTestParams {
+                hash_chain: true,
+                record_move: true,
+                record_fail_move: false,
+                correct_link: false, // Does not hash to the committed head
+                practice: false,
+            },
+        )
+    }
+
+    pub fn with_seed_mode(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            RevealRoundTestParams {
+                hash_chain: false, // Session reveals the private seed instead
+                record_move: true,
+                record_fail_move: false,
+                correct_link: true,
+                practice: false,
+            },
+        )
+    }
+
+    pub fn with_practice(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            RevealRoundTestParams {
+                hash_chain: true,
+                record_move: true,
+                record_fail_move: true,
+                correct_link: true,
+                practice: true, // Closed through `reveal_practice_game` instead
+            },
+        )
+    }
+
+    pub fn with_no_move(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            RevealRoundTestParams {
+                hash_chain: true,
+                record_move: false, // Nothing to reveal yet
+                record_fail_move: false,
+                correct_link: true,
+                practice: false,
+            },
+        )
+    }
+}
+
+#[test]
+fn test_reveal_round_success_when_survived() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let game_session_account = svm
+        .get_account(&game_session)
+        .expect("Game session should still be open");
+
+    let (game_session, _) = GameSession::split_account_data(&game_session_account.data);
+
+    assert!(game_session.is_active());
+    assert_eq!(game_session.revealed_round_count, 1);
+}
+
+#[test]
+fn test_reveal_round_success_when_lost() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_fail_move(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+    let player_profile = instructions[0].accounts[6].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The loss is finalized right away
+    assert!(svm
+        .get_account(&game_session)
+        .is_none_or(|account| account.lamports == 0));
+
+    let player_profile_account = PlayerProfile::try_deserialize(
+        &mut svm.get_account(&player_profile).unwrap().data.as_slice(),
+    )
+    .unwrap();
+
+    assert_eq!(player_profile_account.games_lost, 1);
+}
+
+#[test]
+fn test_reveal_round_fails_with_invalid_link() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_link(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidHashChainLink,
+    );
+}
+
+#[test]
+fn test_reveal_round_fails_with_seed_mode() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_seed_mode(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidFairnessMode,
+    );
+}
+
+#[test]
+fn test_reveal_round_fails_with_practice() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_practice(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::PracticeSession,
+    );
+}
+
+#[test]
+fn test_reveal_round_fails_with_no_move() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_no_move(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::NoRoundToReveal,
+    );
+}
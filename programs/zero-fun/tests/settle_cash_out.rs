@@ -0,0 +1,446 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::InstructionData;
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_game_config_account, create_game_session_account_with_moves,
+    create_global_state_account, create_jackpot_account, create_player_profile_account,
+    create_vault_account, set_current_time,
+};
+
+use zero_fun::{
+    instruction::SettleCashOut, GameSession, GameSessionStatus, GameState, GlobalState, Jackpot,
+    SettleCashOutArgs, CASH_OUT_REVEAL_WINDOW, ID as ZERO_FUN_PROGRAM_ID, MAX_MOVE_TYPE_COUNT,
+    PACKED_MOVE_PAGE_LENGTH, PUBLIC_SEED,
+};
+
+const ROUND_COUNT: u8 = 3;
+
+const CASH_OUT_TIME: i64 = 1_000;
+
+const JACKPOT_BALANCE: u64 = 5_000_000;
+
+struct SettleCashOutTestParams {
+    pub game_session_status: GameSessionStatus,
+    pub reveal_seed: bool,
+    pub record_fail_move: bool,
+    pub window_expired: bool,
+    pub jackpot_move_threshold: u16,
+}
+
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn builder(
+        svm: &mut LiteSVM,
+        params: SettleCashOutTestParams,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Create the player
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        // Create game session
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                public_config_seed.as_ref(),
+                player.pubkey().as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let user_vault = Pubkey::new_unique();
+
+        let mut game_session_account = GameSession::new(
+            player.pubkey(),
+            1_000_000u64,
+            user_vault,
+            public_config_seed,
+            b"metadata",
+            0,
+        );
+
+        // Record a move for each round, the last one fails if requested
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        for round in 0..ROUND_COUNT {
+            let public_config_seed_for_move =
+                hashv(&[&[round], public_config_seed.as_ref()]).to_bytes();
+
+            let move_type_count_for_round: u8 = (public_config_seed_for_move[0]
+                % (u8::try_from(MAX_MOVE_TYPE_COUNT).unwrap() - 1))
+                + 2;
+
+            let private_config_seed_for_move =
+                hashv(&[&[round], private_config_seed.as_ref()]).to_bytes();
+
+            let fail_move: u8 = private_config_seed_for_move[0] % move_type_count_for_round;
+
+            let recorded_move = if params.record_fail_move && round == ROUND_COUNT - 1 {
+                fail_move
+            } else {
+                (fail_move + 1) % move_type_count_for_round
+            };
+
+            game_session_account.set_next_player_move(&mut player_moves, recorded_move)?;
+        }
+
+        game_session_account.cash_out(CASH_OUT_TIME);
+        game_session_account.set_status(params.game_session_status);
+
+        create_game_session_account_with_moves(
+            svm,
+            game_session,
+            &game_session_account,
+            &player_moves,
+        );
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        create_vault_account(svm, user_vault, rent + 1_000_000u64);
+
+        if params.window_expired {
+            set_current_time(svm, CASH_OUT_TIME + CASH_OUT_REVEAL_WINDOW + 1);
+        } else {
+            set_current_time(svm, CASH_OUT_TIME + 1);
+        }
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        // The seed is all but certain to miss the jackpot
+        let mut jackpot_account = Jackpot::new(0, u32::MAX, params.jackpot_move_threshold, 0);
+        jackpot_account.balance = JACKPOT_BALANCE;
+
+        let jackpot = create_jackpot_account(svm, jackpot_account);
+
+        // Build the instruction
+        let player_profile = create_player_profile_account(svm, player.pubkey());
+
+        let accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(player.pubkey(), false),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(global_state, false),
+            AccountMeta::new_readonly(game_config, false),
+            AccountMeta::new(jackpot, false),
+            AccountMeta::new(player_profile, false),
+            // The player has no referrer
+            AccountMeta::new_readonly(ZERO_FUN_PROGRAM_ID, false),
+        ];
+
+        let args = SettleCashOutArgs {
+            private_config_seed: params.reveal_seed.then_some(private_config_seed),
+        };
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: SettleCashOut { args }.data(),
+        };
+
+        Ok(([instruction], vec![player]))
+    }
+
+    pub fn with_expired_window(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            SettleCashOutTestParams {
+                game_session_status: GameSessionStatus::CashedOut,
+                reveal_seed: false,
+                record_fail_move: false,
+                window_expired: true,
+                jackpot_move_threshold: 0,
+            },
+        )
+    }
+
+    pub fn with_revealed_seed(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            SettleCashOutTestParams {
+                game_session_status: GameSessionStatus::CashedOut,
+                reveal_seed: true, // Settles before the window expires
+                record_fail_move: false,
+                window_expired: false,
+                jackpot_move_threshold: 0,
+            },
+        )
+    }
+
+    pub fn with_jackpot_move_threshold_reached(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            SettleCashOutTestParams {
+                game_session_status: GameSessionStatus::CashedOut,
+                reveal_seed: false, // The house never reveals
+                record_fail_move: false,
+                window_expired: true,
+                jackpot_move_threshold: u16::from(ROUND_COUNT),
+            },
+        )
+    }
+
+    pub fn with_open_window(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            SettleCashOutTestParams {
+                game_session_status: GameSessionStatus::CashedOut,
+                reveal_seed: false,
+                record_fail_move: false,
+                window_expired: false, // The house can still reveal
+                jackpot_move_threshold: 0,
+            },
+        )
+    }
+
+    pub fn with_fail_move(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            SettleCashOutTestParams {
+                game_session_status: GameSessionStatus::CashedOut,
+                reveal_seed: true,
+                record_fail_move: true, // The session was lost
+                window_expired: false,
+                jackpot_move_threshold: 0,
+            },
+        )
+    }
+
+    pub fn with_active_game_session(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        Self::builder(
+            svm,
+            SettleCashOutTestParams {
+                game_session_status: GameSessionStatus::Active, // Never cashed out
+                reveal_seed: false,
+                record_fail_move: false,
+                window_expired: true,
+                jackpot_move_threshold: 0,
+            },
+        )
+    }
+}
+
+#[test]
+fn test_settle_cash_out_success_with_expired_window() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_expired_window(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+    let user_vault = instructions[0].accounts[2].pubkey;
+    let vault = instructions[0].accounts[3].pubkey;
+
+    let vault_balance_before = svm.get_balance(&vault).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert!(svm
+        .get_account(&game_session)
+        .is_none_or(|account| account.lamports == 0));
+
+    assert_eq!(svm.get_balance(&user_vault).unwrap_or(0), 0);
+
+    // Surviving rounds always pays out something with a small house edge
+    assert!(svm.get_balance(&vault).unwrap() < vault_balance_before);
+}
+
+#[test]
+fn test_settle_cash_out_success_with_revealed_seed() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_revealed_seed(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_session = instructions[0].accounts[0].pubkey;
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    assert!(svm
+        .get_account(&game_session)
+        .is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn test_settle_cash_out_pays_jackpot_past_move_threshold() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_jackpot_move_threshold_reached(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let jackpot = instructions[0].accounts[6].pubkey;
+
+    let jackpot_balance_before = svm.get_balance(&jackpot).unwrap();
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    // The jackpot is paid even though the house withheld its seed
+    assert_eq!(
+        svm.get_balance(&jackpot).unwrap(),
+        jackpot_balance_before - JACKPOT_BALANCE
+    );
+}
+
+#[test]
+fn test_settle_cash_out_fails_with_open_window() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_open_window(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::RevealWindowNotExpired,
+    );
+}
+
+#[test]
+fn test_settle_cash_out_fails_with_fail_move() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_fail_move(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidFailPosition,
+    );
+}
+
+#[test]
+fn test_settle_cash_out_fails_with_active_game_session() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_active_game_session(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::GameSessionNotCashedOut,
+    );
+}
@@ -0,0 +1,715 @@
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::{AccountDeserialize, InstructionData};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_game_config_account, create_game_session_account_with_moves,
+    create_global_state_account, create_program_account, create_vault_account, set_current_time,
+};
+
+use zero_fun::{
+    instruction::{
+        CreateTournament, DistributeTournamentPrizes, EnterTournament, RefundTournamentEntry,
+        RevealTournament, SubmitTournamentScore,
+    },
+    CreateTournamentArgs, Difficulty, GameError, GameRules, GameSession, GameState, GameVariant,
+    GlobalState, LeaderboardEntry, PlayerProfile, RevealTournamentArgs, TileRules, Tournament,
+    TournamentStatus, HASH_LENGTH, ID as ZERO_FUN_PROGRAM_ID, PACKED_MOVE_PAGE_LENGTH, PUBLIC_SEED,
+    TOURNAMENT_REVEAL_WINDOW, TOURNAMENT_SCORING_WINDOW,
+};
+
+const TOURNAMENT_ID: u64 = 7;
+
+const ENTRY_FEE: u64 = 1_000_000;
+
+const START_TIME: i64 = 1_000;
+
+const END_TIME: i64 = 2_000;
+
+const REVEAL_TIME: i64 = END_TIME + 1;
+
+// The house keeps the last 10%
+const PRIZE_SHARES: [u16; 2] = [6_000, 3_000];
+
+// Every test starts with a running tournament on a Tiles config, the house holds the
+// private config seed behind its public one.
+struct TestSetup {
+    admin: Keypair,
+    player: Keypair,
+    private_config_seed: [u8; HASH_LENGTH],
+    public_config_seed: [u8; HASH_LENGTH],
+    tournament: Pubkey,
+    vault: Pubkey,
+    global_state: Pubkey,
+    game_config: Pubkey,
+}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn new(svm: &mut LiteSVM) -> Self {
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to admin");
+
+        let player = Keypair::new();
+        svm.airdrop(&player.pubkey(), 1_000_000_000)
+            .expect("Could not airdrop to player");
+
+        // Create a random seed and hash it to form the private_config_seed
+        let random_seed = Pubkey::new_unique().to_bytes();
+        let private_config_seed = hashv(&[random_seed.as_ref()]).to_bytes();
+
+        // Derive public_config_seed from the private_config_seed
+        let public_config_seed =
+            hashv(&[PUBLIC_SEED.as_ref(), private_config_seed.as_ref()]).to_bytes();
+
+        // Create global state & main vault
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState::new(
+            admin.pubkey(),
+            Pubkey::new_unique(),
+            10,
+            100,
+            GameState::Active,
+            vault_bump,
+            100,
+        );
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+
+        create_vault_account(svm, vault, rent + 100_000_000u64);
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        let (tournament, tournament_bump) = Self::get_tournament_address(TOURNAMENT_ID);
+
+        create_program_account(
+            svm,
+            tournament,
+            &Tournament::new(
+                TOURNAMENT_ID,
+                GameVariant::Tiles,
+                ENTRY_FEE,
+                START_TIME,
+                END_TIME,
+                public_config_seed,
+                PRIZE_SHARES.to_vec(),
+                tournament_bump,
+            ),
+        );
+
+        set_current_time(svm, START_TIME + 1);
+
+        Self {
+            admin,
+            player,
+            private_config_seed,
+            public_config_seed,
+            tournament,
+            vault,
+            global_state,
+            game_config,
+        }
+    }
+
+    fn get_tournament_address(tournament_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"tournament", tournament_id.to_le_bytes().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        )
+    }
+
+    fn get_game_session_address(&self, player: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"game-session",
+                self.public_config_seed.as_ref(),
+                player.as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn get_player_profile_address(&self) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"player-profile", self.player.pubkey().as_ref()],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        )
+    }
+
+    fn get_player_profile(&self, svm: &LiteSVM) -> PlayerProfile {
+        let account = svm
+            .get_account(&self.get_player_profile_address().0)
+            .unwrap();
+
+        PlayerProfile::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Creates the player's profile, with `update` applied to it.
+    fn with_player_profile(
+        self,
+        svm: &mut LiteSVM,
+        update: impl FnOnce(&mut PlayerProfile),
+    ) -> Self {
+        let (player_profile, player_profile_bump) = self.get_player_profile_address();
+
+        let mut player_profile_account =
+            PlayerProfile::new(self.player.pubkey(), player_profile_bump);
+
+        update(&mut player_profile_account);
+
+        create_program_account(svm, player_profile, &player_profile_account);
+
+        self
+    }
+
+    fn get_tournament(&self, svm: &LiteSVM) -> Tournament {
+        let account = svm.get_account(&self.tournament).unwrap();
+
+        Tournament::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Updates the tournament, it holds the lamports of its prize pool on top of its rent.
+    fn update_tournament(&self, svm: &mut LiteSVM, update: impl FnOnce(&mut Tournament)) {
+        let mut tournament = self.get_tournament(svm);
+
+        update(&mut tournament);
+
+        create_program_account(svm, self.tournament, &tournament);
+
+        let mut account = svm.get_account(&self.tournament).unwrap();
+        account.lamports += tournament.prize_pool;
+        svm.set_account(self.tournament, account).unwrap();
+    }
+
+    /// Marks the tournament as revealed at `REVEAL_TIME`.
+    fn with_revealed_seed(self, svm: &mut LiteSVM) -> Self {
+        let private_config_seed = self.private_config_seed;
+
+        self.update_tournament(svm, |tournament| {
+            tournament.private_config_seed = private_config_seed;
+            tournament.reveal_time = REVEAL_TIME;
+            tournament.status = TournamentStatus::Revealed;
+        });
+
+        self
+    }
+
+    /// Creates the player's tournament session, it survives `rounds_survived` rounds and
+    /// then fails a round, its last move is at `last_action_time`.
+    fn with_entry(self, svm: &mut LiteSVM, rounds_survived: u16, last_action_time: i64) -> Self {
+        let tile_rules = TileRules::default();
+
+        let mut game_session_account = GameSession::new(
+            self.player.pubkey(),
+            0,
+            self.tournament,
+            self.public_config_seed,
+            &[],
+            last_action_time,
+        );
+
+        game_session_account.enter_tournament(self.tournament);
+
+        let mut player_moves = vec![0u8; PACKED_MOVE_PAGE_LENGTH];
+
+        for round in 0..=rounds_survived {
+            let move_type_count = tile_rules
+                .get_round_config(&game_session_account, round)
+                .move_type_count;
+
+            let fail_move = tile_rules.get_fail_move_for_round(
+                &self.private_config_seed,
+                round,
+                move_type_count,
+                Difficulty::Normal,
+            );
+
+            let recorded_move = if round == rounds_survived {
+                fail_move
+            } else {
+                (fail_move + 1) % move_type_count
+            };
+
+            game_session_account
+                .set_next_player_move(&mut player_moves, recorded_move)
+                .unwrap();
+        }
+
+        create_game_session_account_with_moves(
+            svm,
+            self.get_game_session_address(&self.player.pubkey()),
+            &game_session_account,
+            &player_moves,
+        );
+
+        self.update_tournament(svm, |tournament| {
+            tournament.prize_pool += ENTRY_FEE;
+            tournament.entry_count += 1;
+        });
+
+        self.with_player_profile(svm, |player_profile| {
+            player_profile.record_game_started(ENTRY_FEE, START_TIME + 1)
+        })
+    }
+
+    fn create_tournament(&self, prize_shares: Vec<u16>) -> Instruction {
+        let (tournament, _) = Self::get_tournament_address(TOURNAMENT_ID + 1);
+
+        let args = CreateTournamentArgs {
+            tournament_id: TOURNAMENT_ID + 1,
+            entry_fee: ENTRY_FEE,
+            start_time: START_TIME,
+            end_time: END_TIME,
+            public_config_seed: self.public_config_seed,
+            prize_shares,
+        };
+
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(tournament, false),
+                AccountMeta::new(self.admin.pubkey(), true),
+                AccountMeta::new_readonly(self.global_state, false),
+                AccountMeta::new_readonly(self.game_config, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: CreateTournament { args }.data(),
+        }
+    }
+
+    fn enter_tournament(&self) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tournament, false),
+                AccountMeta::new(self.get_game_session_address(&self.player.pubkey()), false),
+                AccountMeta::new(self.player.pubkey(), true),
+                AccountMeta::new_readonly(self.global_state, false),
+                AccountMeta::new_readonly(self.game_config, false),
+                AccountMeta::new(self.get_player_profile_address().0, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: EnterTournament {}.data(),
+        }
+    }
+
+    fn reveal_tournament(&self, private_config_seed: [u8; HASH_LENGTH]) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![AccountMeta::new(self.tournament, false)],
+            data: RevealTournament {
+                args: RevealTournamentArgs {
+                    private_config_seed,
+                },
+            }
+            .data(),
+        }
+    }
+
+    fn submit_tournament_score(&self) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tournament, false),
+                AccountMeta::new(self.get_game_session_address(&self.player.pubkey()), false),
+                AccountMeta::new(self.player.pubkey(), false),
+                AccountMeta::new_readonly(self.game_config, false),
+                AccountMeta::new(self.get_player_profile_address().0, false),
+            ],
+            data: SubmitTournamentScore {}.data(),
+        }
+    }
+
+    fn distribute_tournament_prizes(&self, recipients: &[Pubkey]) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(self.tournament, false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new_readonly(self.global_state, false),
+        ];
+
+        accounts.extend(
+            recipients
+                .iter()
+                .map(|recipient| AccountMeta::new(*recipient, false)),
+        );
+
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: DistributeTournamentPrizes {}.data(),
+        }
+    }
+
+    fn refund_tournament_entry(&self) -> Instruction {
+        Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tournament, false),
+                AccountMeta::new(self.get_game_session_address(&self.player.pubkey()), false),
+                AccountMeta::new(self.player.pubkey(), false),
+                AccountMeta::new(self.get_player_profile_address().0, false),
+            ],
+            data: RefundTournamentEntry {}.data(),
+        }
+    }
+
+    fn send(
+        svm: &mut LiteSVM,
+        instruction: Instruction,
+        signer: &Keypair,
+    ) -> litesvm::types::TransactionResult {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            svm.latest_blockhash(),
+        );
+
+        svm.send_transaction(transaction)
+    }
+}
+
+#[test]
+fn test_create_tournament_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    let instruction = setup.create_tournament(PRIZE_SHARES.to_vec());
+
+    assert_transaction_success(TestSetup::send(&mut svm, instruction, &setup.admin));
+}
+
+#[test]
+fn test_create_tournament_fails_with_prize_shares_over_pool() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    let instruction = setup.create_tournament(vec![6_000, 5_000]);
+
+    assert_custom_transaction_error_at(
+        TestSetup::send(&mut svm, instruction, &setup.admin),
+        0,
+        GameError::InvalidTournamentParameters,
+    );
+}
+
+#[test]
+fn test_enter_tournament_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    let instruction = setup.enter_tournament();
+
+    assert_transaction_success(TestSetup::send(&mut svm, instruction, &setup.player));
+
+    let tournament = setup.get_tournament(&svm);
+
+    assert_eq!(tournament.prize_pool, ENTRY_FEE);
+    assert_eq!(tournament.entry_count, 1);
+
+    let game_session_data = svm
+        .get_account(&setup.get_game_session_address(&setup.player.pubkey()))
+        .unwrap()
+        .data;
+
+    let (game_session, _) = GameSession::split_account_data(&game_session_data);
+
+    assert!(game_session.is_tournament());
+    assert_eq!(game_session.public_config_seed, setup.public_config_seed);
+
+    // The entry fee is wagered like a deposit
+    let player_profile = setup.get_player_profile(&svm);
+
+    assert_eq!(player_profile.games_played, 1);
+    assert_eq!(player_profile.total_wagered, ENTRY_FEE);
+    assert_eq!(player_profile.active_sessions, 1);
+}
+
+#[test]
+fn test_enter_tournament_fails_with_self_exclusion() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_player_profile(&mut svm, |player_profile| {
+        player_profile.self_exclude(START_TIME, END_TIME)
+    });
+
+    let instruction = setup.enter_tournament();
+
+    assert_custom_transaction_error_at(
+        TestSetup::send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::PlayerSelfExcluded,
+    );
+}
+
+#[test]
+fn test_enter_tournament_fails_after_end() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    set_current_time(&mut svm, END_TIME + 1);
+
+    let instruction = setup.enter_tournament();
+
+    assert_custom_transaction_error_at(
+        TestSetup::send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::TournamentNotOpen,
+    );
+}
+
+#[test]
+fn test_reveal_tournament_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    set_current_time(&mut svm, END_TIME + 1);
+
+    let instruction = setup.reveal_tournament(setup.private_config_seed);
+
+    assert_transaction_success(TestSetup::send(&mut svm, instruction, &setup.player));
+
+    assert!(setup.get_tournament(&svm).is_revealed());
+}
+
+#[test]
+fn test_reveal_tournament_fails_before_end() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    let instruction = setup.reveal_tournament(setup.private_config_seed);
+
+    assert_custom_transaction_error_at(
+        TestSetup::send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::TournamentNotEnded,
+    );
+}
+
+#[test]
+fn test_reveal_tournament_fails_with_invalid_seed() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm);
+
+    set_current_time(&mut svm, END_TIME + 1);
+
+    let instruction = setup.reveal_tournament(Pubkey::new_unique().to_bytes());
+
+    assert_custom_transaction_error_at(
+        TestSetup::send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::InvalidGameSeed,
+    );
+}
+
+#[test]
+fn test_submit_tournament_score_places_on_leaderboard() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm)
+        .with_entry(&mut svm, 5, END_TIME)
+        .with_revealed_seed(&mut svm);
+
+    set_current_time(&mut svm, REVEAL_TIME + 1);
+
+    let instruction = setup.submit_tournament_score();
+
+    assert_transaction_success(TestSetup::send(&mut svm, instruction, &setup.player));
+
+    assert_eq!(
+        setup.get_tournament(&svm).leaderboard,
+        vec![LeaderboardEntry {
+            player: setup.player.pubkey(),
+            rounds_survived: 5,
+        }]
+    );
+
+    assert!(svm
+        .get_account(&setup.get_game_session_address(&setup.player.pubkey()))
+        .is_none_or(|account| account.lamports == 0));
+
+    assert_eq!(setup.get_player_profile(&svm).active_sessions, 0);
+}
+
+#[test]
+fn test_submit_tournament_score_skips_moves_after_end() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    // The last move was made once the tournament was over
+    let setup = TestSetup::new(&mut svm)
+        .with_entry(&mut svm, 5, END_TIME + 1)
+        .with_revealed_seed(&mut svm);
+
+    set_current_time(&mut svm, REVEAL_TIME + 1);
+
+    let instruction = setup.submit_tournament_score();
+
+    assert_transaction_success(TestSetup::send(&mut svm, instruction, &setup.player));
+
+    assert!(setup.get_tournament(&svm).leaderboard.is_empty());
+}
+
+#[test]
+fn test_submit_tournament_score_fails_before_reveal() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_entry(&mut svm, 5, END_TIME);
+
+    set_current_time(&mut svm, END_TIME + 1);
+
+    let instruction = setup.submit_tournament_score();
+
+    assert_custom_transaction_error_at(
+        TestSetup::send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::InvalidTournamentStatus,
+    );
+}
+
+#[test]
+fn test_distribute_tournament_prizes_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let winners = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let setup = TestSetup::new(&mut svm).with_revealed_seed(&mut svm);
+
+    setup.update_tournament(&mut svm, |tournament| {
+        tournament.prize_pool = 10 * ENTRY_FEE;
+        tournament.leaderboard = winners
+            .iter()
+            .map(|player| LeaderboardEntry {
+                player: *player,
+                rounds_survived: 3,
+            })
+            .collect();
+    });
+
+    for winner in winners {
+        svm.airdrop(&winner, 1_000_000_000).unwrap();
+    }
+
+    let vault_balance_before = svm.get_balance(&setup.vault).unwrap();
+
+    set_current_time(&mut svm, REVEAL_TIME + TOURNAMENT_SCORING_WINDOW + 1);
+
+    let instruction = setup.distribute_tournament_prizes(&winners);
+
+    assert_transaction_success(TestSetup::send(&mut svm, instruction, &setup.player));
+
+    assert_eq!(
+        svm.get_balance(&winners[0]).unwrap(),
+        1_000_000_000 + 6 * ENTRY_FEE
+    );
+    assert_eq!(
+        svm.get_balance(&winners[1]).unwrap(),
+        1_000_000_000 + 3 * ENTRY_FEE
+    );
+    assert_eq!(
+        svm.get_balance(&setup.vault).unwrap(),
+        vault_balance_before + ENTRY_FEE
+    );
+
+    let tournament = setup.get_tournament(&svm);
+
+    assert_eq!(tournament.status, TournamentStatus::Distributed);
+    assert_eq!(tournament.prize_pool, 0);
+}
+
+#[test]
+fn test_distribute_tournament_prizes_fails_before_scoring_window_expired() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_revealed_seed(&mut svm);
+
+    set_current_time(&mut svm, REVEAL_TIME + 1);
+
+    let instruction = setup.distribute_tournament_prizes(&[]);
+
+    assert_custom_transaction_error_at(
+        TestSetup::send(&mut svm, instruction, &setup.player),
+        0,
+        GameError::ScoringWindowNotExpired,
+    );
+}
+
+#[test]
+fn test_refund_tournament_entry_success_after_reveal_window_expired() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let setup = TestSetup::new(&mut svm).with_entry(&mut svm, 1, END_TIME);
+
+    set_current_time(&mut svm, END_TIME + TOURNAMENT_REVEAL_WINDOW + 1);
+
+    let player_balance_before = svm.get_balance(&setup.player.pubkey()).unwrap();
+
+    let instruction = setup.refund_tournament_entry();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+    assert_transaction_success(TestSetup::send(&mut svm, instruction, &payer));
+
+    // The entry fee and the session's rent are paid back
+    assert!(svm.get_balance(&setup.player.pubkey()).unwrap() > player_balance_before + ENTRY_FEE);
+    assert_eq!(setup.get_tournament(&svm).prize_pool, 0);
+
+    let player_profile = setup.get_player_profile(&svm);
+
+    assert_eq!(player_profile.games_defaulted, 1);
+    assert_eq!(player_profile.active_sessions, 0);
+}
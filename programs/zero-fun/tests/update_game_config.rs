@@ -0,0 +1,174 @@
+use anchor_lang::{AccountDeserialize, InstructionData};
+use anyhow::Result;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::Transaction,
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_success,
+    create_default_game_config_account, create_global_state_account,
+};
+
+use zero_fun::{
+    instruction::UpdateGameConfig, GameConfig, GameState, GlobalState, UpdateGameConfigArgs,
+    DEFAULT_OFFSET, ID as ZERO_FUN_PROGRAM_ID,
+};
+
+// Here what is important is that a variant can only be updated by the admin,
+// other stuff is filled with defaults
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let admin = Keypair::new();
+
+        Self::builder(svm, admin.insecure_clone(), admin)
+    }
+
+    pub fn with_invalid_admin(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let admin = Keypair::new();
+
+        let invalid_admin = Keypair::new();
+
+        Self::builder(svm, admin, invalid_admin)
+    }
+
+    fn builder(
+        svm: &mut LiteSVM,
+        state_admin: Keypair,
+        instruction_admin: Keypair,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        // Create the admin account
+        svm.airdrop(&instruction_admin.pubkey(), 1_000_000_000)
+            .unwrap();
+
+        // Create the global state account
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let global_state_account = GlobalState {
+            admin: state_admin.pubkey(),
+            message_signer: Pubkey::default(),
+            max_deposit: 10u16,
+            max_payout: 100u16,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
+            game_state: GameState::Active,
+            vault_bump: 255u8,
+            house_edge: 0u16,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve: 0,
+            daily_withdraw_limit: 0,
+            withdraw_buckets: Default::default(),
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: Default::default(),
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes: 0,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
+        };
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let game_config = create_default_game_config_account(svm, GameState::Active);
+
+        // Build the instruction
+        let accounts = vec![
+            AccountMeta::new(game_config, false),
+            AccountMeta::new_readonly(global_state, false),
+            AccountMeta::new_readonly(instruction_admin.pubkey(), true),
+        ];
+
+        let args = UpdateGameConfigArgs {
+            game_state: GameState::Locked,
+        };
+
+        let update = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: UpdateGameConfig { args }.data(),
+        };
+
+        Ok(([update], vec![instruction_admin]))
+    }
+}
+
+#[test]
+fn test_update_game_config_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_default(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let game_config = instructions[0].accounts[0].pubkey;
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_transaction_success(svm.send_transaction(transaction));
+
+    let game_config_account = svm
+        .get_account(&game_config)
+        .expect("Game config should exist");
+
+    let game_config = GameConfig::try_deserialize(&mut game_config_account.data.as_slice())
+        .expect("Could not deserialize GameConfig");
+
+    assert!(!game_config.is_active());
+}
+
+#[test]
+fn test_update_game_config_fails_with_invalid_admin() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_admin(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidAdmin,
+    );
+}
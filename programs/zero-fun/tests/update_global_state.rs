@@ -17,7 +17,7 @@ use common::utils::{
 
 use zero_fun::{
     instruction::UpdateGlobalState, GameState, GlobalState, GlobalStateUpdate,
-    UpdateGlobalStateArgs, ID as ZERO_FUN_PROGRAM_ID,
+    UpdateGlobalStateArgs, DEFAULT_OFFSET, ID as ZERO_FUN_PROGRAM_ID, MIN_DEFAULT_OFFSET,
 };
 
 use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
@@ -34,7 +34,12 @@ impl TestSetup {
     pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
         let admin = Keypair::new();
 
-        Self::builder(svm, admin.insecure_clone(), admin)
+        Self::builder(
+            svm,
+            admin.insecure_clone(),
+            admin,
+            GlobalStateUpdate::MaxDeposit(50),
+        )
     }
 
     pub fn with_invalid_admin(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
@@ -42,13 +47,28 @@ impl TestSetup {
 
         let invalid_admin = Keypair::new();
 
-        Self::builder(svm, admin, invalid_admin)
+        Self::builder(svm, admin, invalid_admin, GlobalStateUpdate::MaxDeposit(50))
+    }
+
+    pub fn with_invalid_default_offset(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let admin = Keypair::new();
+
+        // The house would have less than the minimum to settle a session
+        Self::builder(
+            svm,
+            admin.insecure_clone(),
+            admin,
+            GlobalStateUpdate::DefaultOffset(MIN_DEFAULT_OFFSET - 1),
+        )
     }
 
     fn builder(
         svm: &mut LiteSVM,
         state_admin: Keypair,
         instruction_admin: Keypair,
+        update: GlobalStateUpdate,
     ) -> Result<([Instruction; 1], Vec<Keypair>)> {
         // Create the admin account
         svm.airdrop(&state_admin.pubkey(), 1_000_000_000).unwrap();
@@ -64,10 +84,33 @@ impl TestSetup {
         let global_state = GlobalState {
             admin: state_admin.pubkey(),
             message_signer: Pubkey::default(),
-            max_deposit: 10u8,
-            max_payout: 100u8,
+            max_deposit: 10u16,
+            max_payout: 100u16,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
             game_state: GameState::Active,
             vault_bump: 255u8,
+            house_edge: 0u16,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve: 0,
+            daily_withdraw_limit: 0,
+            withdraw_buckets: Default::default(),
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: Default::default(),
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes: 0,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
         };
 
         create_global_state_account(svm, global_state_key, global_state);
@@ -78,9 +121,7 @@ impl TestSetup {
             AccountMeta::new_readonly(instruction_admin.pubkey(), true),
         ];
 
-        let args = UpdateGlobalStateArgs {
-            update: GlobalStateUpdate::MaxDeposit(50),
-        };
+        let args = UpdateGlobalStateArgs { update };
 
         let update_state = Instruction {
             program_id: Self::ZERO_FUN_PROGRAM_ID,
@@ -183,3 +224,31 @@ fn test_update_global_state_fails_when_admin_does_not_sign() {
         TransactionError::InstructionError(0, InstructionError::Custom(3010)),
     );
 }
+
+#[test]
+fn test_update_global_state_fails_with_invalid_default_offset() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_invalid_default_offset(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InvalidDefaultWindows,
+    );
+}
@@ -0,0 +1,388 @@
+use anchor_lang::{error::ErrorCode, InstructionData};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+mod common;
+use common::utils::{
+    add_zero_fun_program, assert_custom_transaction_error_at, assert_transaction_error,
+    assert_transaction_success, create_game_session_account, create_global_state_account,
+    create_player_profile_account, create_program_account, create_vault_account, set_current_time,
+};
+
+use zero_fun::{
+    instruction::{CloseGlobalState, DefaultGame},
+    CrashState, GameSession, GameSessionStatus, GameState, GlobalState, HASH_LENGTH,
+    ID as ZERO_FUN_PROGRAM_ID, WIND_DOWN_SETTLEMENT_WINDOW,
+};
+
+const DEPOSIT: u64 = 1_000_000;
+
+const WIND_DOWN_START: i64 = 1_700_000_000;
+
+// Here what is relevant is that while the game winds down a keeper can default active
+// sessions at once, marked wins only once the settlement window has passed, and that the
+// admin can only close the global state once no sessions, bets or open crash rounds remain.
+struct TestSetup {}
+
+impl TestSetup {
+    const ZERO_FUN_PROGRAM_ID: Pubkey = ZERO_FUN_PROGRAM_ID;
+
+    fn create_global_state(
+        svm: &mut LiteSVM,
+        admin: Pubkey,
+        game_state: GameState,
+        open_sessions: u64,
+    ) -> (Pubkey, Pubkey) {
+        let (global_state, _) =
+            Pubkey::find_program_address(&[b"global-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        let mut global_state_account =
+            GlobalState::new(admin, Pubkey::default(), 10, 100, game_state, vault_bump, 0);
+
+        global_state_account.open_sessions = open_sessions;
+        global_state_account.wind_down_start = WIND_DOWN_START;
+
+        create_global_state_account(svm, global_state, global_state_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+        create_vault_account(svm, vault, rent + DEPOSIT);
+
+        (global_state, vault)
+    }
+
+    fn default_game_builder(
+        svm: &mut LiteSVM,
+        game_state: GameState,
+        status: GameSessionStatus,
+        current_time: i64,
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        let keeper = Keypair::new();
+        let player = Pubkey::new_unique();
+
+        svm.airdrop(&keeper.pubkey(), 1_000_000_000).unwrap();
+
+        let (global_state, _) = Self::create_global_state(svm, Pubkey::default(), game_state, 1);
+
+        let (game_session, _) = Pubkey::find_program_address(
+            &[
+                b"game-session",
+                [0u8; HASH_LENGTH].as_ref(),
+                player.as_ref(),
+            ],
+            &Self::ZERO_FUN_PROGRAM_ID,
+        );
+
+        let user_vault = Pubkey::new_unique();
+
+        // The session was last played right before the game started winding down
+        let mut game_session_account = GameSession::new(
+            player,
+            DEPOSIT,
+            user_vault,
+            [0u8; HASH_LENGTH],
+            b"metadata",
+            WIND_DOWN_START,
+        );
+
+        game_session_account.set_status(status);
+
+        create_game_session_account(svm, game_session, &game_session_account);
+
+        let rent = svm.minimum_balance_for_rent_exemption(0);
+        create_vault_account(svm, user_vault, rent + DEPOSIT);
+
+        set_current_time(svm, current_time);
+
+        let player_profile = create_player_profile_account(svm, player);
+
+        // The player does not sign, the keeper pays for the transaction
+        let accounts = vec![
+            AccountMeta::new(player, false),
+            AccountMeta::new(user_vault, false),
+            AccountMeta::new(game_session, false),
+            AccountMeta::new(player_profile, false),
+            AccountMeta::new(global_state, false),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: DefaultGame {}.data(),
+        };
+
+        ([instruction], vec![keeper])
+    }
+
+    /// The crash state is only created if `has_open_crash_round` is set.
+    fn close_global_state_builder(
+        svm: &mut LiteSVM,
+        game_state: GameState,
+        open_sessions: u64,
+        has_open_crash_round: Option<bool>,
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        let admin = Keypair::new();
+
+        svm.airdrop(&admin.pubkey(), 1_000_000_000).unwrap();
+
+        let (global_state, vault) =
+            Self::create_global_state(svm, admin.pubkey(), game_state, open_sessions);
+
+        let (crash_state, crash_state_bump) =
+            Pubkey::find_program_address(&[b"crash-state"], &Self::ZERO_FUN_PROGRAM_ID);
+
+        if let Some(has_open_crash_round) = has_open_crash_round {
+            let mut crash_state_account =
+                CrashState::new([0; HASH_LENGTH], 30, 1_000, crash_state_bump);
+
+            crash_state_account.has_open_round = has_open_crash_round;
+
+            create_program_account(svm, crash_state, &crash_state_account);
+        }
+
+        let accounts = vec![
+            AccountMeta::new(global_state, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(crash_state, false),
+            AccountMeta::new(admin.pubkey(), true),
+        ];
+
+        let instruction = Instruction {
+            program_id: Self::ZERO_FUN_PROGRAM_ID,
+            accounts,
+            data: CloseGlobalState {}.data(),
+        };
+
+        ([instruction], vec![admin])
+    }
+
+    pub fn with_active_session(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::default_game_builder(
+            svm,
+            GameState::WindDown,
+            GameSessionStatus::Active,
+            WIND_DOWN_START + 1,
+        )
+    }
+
+    pub fn with_active_session_not_winding_down(
+        svm: &mut LiteSVM,
+    ) -> ([Instruction; 1], Vec<Keypair>) {
+        // Without the wind down only the player can default their session
+        Self::default_game_builder(
+            svm,
+            GameState::Locked,
+            GameSessionStatus::Active,
+            WIND_DOWN_START + 1,
+        )
+    }
+
+    pub fn with_marked_win(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        // The admin still has the settlement window to pay the win
+        Self::default_game_builder(
+            svm,
+            GameState::WindDown,
+            GameSessionStatus::Won,
+            WIND_DOWN_START + WIND_DOWN_SETTLEMENT_WINDOW,
+        )
+    }
+
+    pub fn with_marked_win_unsettled(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::default_game_builder(
+            svm,
+            GameState::WindDown,
+            GameSessionStatus::Won,
+            WIND_DOWN_START + WIND_DOWN_SETTLEMENT_WINDOW + 1,
+        )
+    }
+
+    pub fn with_wound_down(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::close_global_state_builder(svm, GameState::WindDown, 0, None)
+    }
+
+    pub fn with_crash_rounds_revealed(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::close_global_state_builder(svm, GameState::WindDown, 0, Some(false))
+    }
+
+    pub fn with_sessions_still_open(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::close_global_state_builder(svm, GameState::WindDown, 1, None)
+    }
+
+    pub fn with_crash_round_still_open(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::close_global_state_builder(svm, GameState::WindDown, 0, Some(true))
+    }
+
+    pub fn with_game_not_winding_down(svm: &mut LiteSVM) -> ([Instruction; 1], Vec<Keypair>) {
+        Self::close_global_state_builder(svm, GameState::Locked, 0, None)
+    }
+}
+
+fn send(
+    svm: &mut LiteSVM,
+    instructions: [Instruction; 1],
+    signers: Vec<Keypair>,
+) -> litesvm::types::TransactionResult {
+    let payer = signers[0].pubkey();
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    svm.send_transaction(transaction)
+}
+
+#[test]
+fn test_default_game_success_by_keeper_when_winding_down() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_active_session(&mut svm);
+
+    let player = instructions[0].accounts[0].pubkey;
+
+    assert_transaction_success(send(&mut svm, instructions, signers));
+
+    // The deposit, with the vault's rent, went back to the player
+    assert!(svm.get_balance(&player).unwrap() > DEPOSIT);
+}
+
+#[test]
+fn test_default_game_fails_by_keeper_when_not_winding_down() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_active_session_not_winding_down(&mut svm);
+
+    assert_transaction_error(
+        send(&mut svm, instructions, signers),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ErrorCode::AccountNotSigner.into()),
+        ),
+    );
+}
+
+#[test]
+fn test_default_game_fails_with_marked_win_within_settlement_window() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_marked_win(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::TooSoonToDefault,
+    );
+}
+
+#[test]
+fn test_default_game_success_with_marked_win_after_settlement_window() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_marked_win_unsettled(&mut svm);
+
+    assert_transaction_success(send(&mut svm, instructions, signers));
+}
+
+#[test]
+fn test_close_global_state_success() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_wound_down(&mut svm);
+
+    let global_state = instructions[0].accounts[0].pubkey;
+    let vault = instructions[0].accounts[1].pubkey;
+
+    assert_transaction_success(send(&mut svm, instructions, signers));
+
+    assert!(svm.get_account(&global_state).is_none());
+    assert!(svm.get_account(&vault).is_none());
+}
+
+#[test]
+fn test_close_global_state_success_with_crash_rounds_revealed() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_crash_rounds_revealed(&mut svm);
+
+    let global_state = instructions[0].accounts[0].pubkey;
+
+    assert_transaction_success(send(&mut svm, instructions, signers));
+
+    assert!(svm.get_account(&global_state).is_none());
+}
+
+#[test]
+fn test_close_global_state_fails_with_crash_round_still_open() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_crash_round_still_open(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::CrashRoundOpen,
+    );
+}
+
+#[test]
+fn test_close_global_state_fails_with_sessions_still_open() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_sessions_still_open(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::SessionsStillOpen,
+    );
+}
+
+#[test]
+fn test_close_global_state_fails_when_game_not_winding_down() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let (instructions, signers) = TestSetup::with_game_not_winding_down(&mut svm);
+
+    assert_custom_transaction_error_at(
+        send(&mut svm, instructions, signers),
+        0,
+        zero_fun::GameError::GameNotWindingDown,
+    );
+}
+
+#[test]
+fn test_record_payout_does_not_restrict_when_winding_down() {
+    let mut global_state = GlobalState {
+        game_state: GameState::WindDown,
+        payout_breaker_threshold: 1,
+        ..Default::default()
+    };
+
+    assert!(!global_state.record_payout(2, 100));
+    assert!(global_state.is_winding_down());
+}
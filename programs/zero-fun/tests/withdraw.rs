@@ -17,9 +17,13 @@ use common::utils::{
 };
 
 use zero_fun::{
-    instruction::Withdraw, GameState, GlobalState, WithdrawArgs, ID as ZERO_FUN_PROGRAM_ID,
+    instruction::Withdraw, GameState, GlobalState, WithdrawArgs, DEFAULT_OFFSET,
+    ID as ZERO_FUN_PROGRAM_ID, WITHDRAW_BUCKET_LENGTH, WITHDRAW_WINDOW,
 };
 
+// The bankroll held on top of the vault's rent
+const VAULT_BALANCE: u64 = 500_000;
+
 struct TestSetup {}
 
 impl TestSetup {
@@ -28,19 +32,100 @@ impl TestSetup {
     pub fn with_default(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
         let instruction_admin = Keypair::new();
 
-        Self::builder(svm, instruction_admin.pubkey(), instruction_admin)
+        Self::builder(
+            svm,
+            instruction_admin.pubkey(),
+            instruction_admin,
+            0,
+            0,
+            0,
+            VAULT_BALANCE,
+        )
     }
 
     pub fn with_invalid_admin(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
         let instruction_admin = Keypair::new();
 
-        Self::builder(svm, Pubkey::new_unique(), instruction_admin)
+        Self::builder(
+            svm,
+            Pubkey::new_unique(),
+            instruction_admin,
+            0,
+            0,
+            0,
+            VAULT_BALANCE,
+        )
+    }
+
+    pub fn with_rent_withdrawn(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        // The vault must stay rent exempt
+        Self::builder(
+            svm,
+            instruction_admin.pubkey(),
+            instruction_admin,
+            0,
+            0,
+            0,
+            VAULT_BALANCE + 1,
+        )
+    }
+
+    pub fn with_reserve_withdrawn(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        // Only what is above the minimum reserve can be withdrawn
+        Self::builder(
+            svm,
+            instruction_admin.pubkey(),
+            instruction_admin,
+            1,
+            0,
+            0,
+            VAULT_BALANCE,
+        )
+    }
+
+    pub fn with_open_crash_bet(svm: &mut LiteSVM) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        // The stake of an open crash bet is owed back to the player if the bet wins
+        Self::builder(
+            svm,
+            instruction_admin.pubkey(),
+            instruction_admin,
+            0,
+            0,
+            1,
+            VAULT_BALANCE,
+        )
+    }
+
+    pub fn with_daily_limit_exceeded(
+        svm: &mut LiteSVM,
+    ) -> Result<([Instruction; 1], Vec<Keypair>)> {
+        let instruction_admin = Keypair::new();
+
+        Self::builder(
+            svm,
+            instruction_admin.pubkey(),
+            instruction_admin,
+            0,
+            VAULT_BALANCE - 1,
+            0,
+            VAULT_BALANCE,
+        )
     }
 
     fn builder(
         svm: &mut LiteSVM,
         state_admin: Pubkey,
         instruction_admin: Keypair,
+        min_reserve: u64,
+        daily_withdraw_limit: u64,
+        open_crash_stakes: u64,
+        withdraw_amount: u64,
     ) -> Result<([Instruction; 1], Vec<Keypair>)> {
         // Create the admin account
         svm.airdrop(&instruction_admin.pubkey(), 1_000_000_000)
@@ -57,18 +142,40 @@ impl TestSetup {
         let global_state = GlobalState {
             admin: state_admin,
             message_signer: Pubkey::default(),
-            max_deposit: 10u8,
-            max_payout: 100u8,
+            max_deposit: 10u16,
+            max_payout: 100u16,
+            min_deposit: 0,
+            max_deposit_lamports: 0,
+            max_payout_lamports: 0,
             game_state: GameState::Active,
             vault_bump: vault_bump as u8,
+            house_edge: 0u16,
+            referral_fee: 0,
+            max_referral_reward: 0,
+            protocol_fee: 0,
+            min_reserve,
+            daily_withdraw_limit,
+            withdraw_buckets: Default::default(),
+            last_withdraw_bucket: 0,
+            payout_breaker_threshold: 0,
+            payout_buckets: Default::default(),
+            last_payout_bucket: 0,
+            total_deposited: 0,
+            total_paid_out: 0,
+            total_swept: 0,
+            total_withdrawn: 0,
+            open_sessions: 0,
+            open_crash_stakes,
+            wind_down_start: 0,
+            default_offset: DEFAULT_OFFSET,
+            max_session_lifetime: 0,
         };
 
         create_global_state_account(svm, global_state_key, global_state);
 
         // Create vault account
         let rent = svm.minimum_balance_for_rent_exemption(0);
-        let withdraw_amount: u64 = 500_000u64;
-        create_vault_account(svm, vault_key, rent + withdraw_amount);
+        create_vault_account(svm, vault_key, rent + VAULT_BALANCE);
 
         let recipient_key = Pubkey::new_unique();
 
@@ -144,3 +251,158 @@ fn test_withdraw_fails_with_invalid_admin() {
         zero_fun::GameError::InvalidAdmin,
     );
 }
+
+#[test]
+fn test_withdraw_fails_with_rent_withdrawn() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_rent_withdrawn(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InsufficientFreeLiquidity,
+    );
+}
+
+#[test]
+fn test_withdraw_fails_with_reserve_withdrawn() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_reserve_withdrawn(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InsufficientFreeLiquidity,
+    );
+}
+
+#[test]
+fn test_withdraw_fails_with_open_crash_bet() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_open_crash_bet(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::InsufficientFreeLiquidity,
+    );
+}
+
+#[test]
+fn test_withdraw_fails_with_daily_limit_exceeded() {
+    let mut svm = LiteSVM::new();
+
+    add_zero_fun_program(&mut svm);
+
+    let result = TestSetup::with_daily_limit_exceeded(&mut svm);
+
+    let (instructions, signers) = match result {
+        Ok(result) => result,
+        Err(error) => panic!("Failed to create instruction: {}", error),
+    };
+
+    let recent_blockhash = svm.latest_blockhash();
+
+    let payer = signers[0].pubkey();
+
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &signers, recent_blockhash);
+
+    assert_custom_transaction_error_at(
+        svm.send_transaction(transaction),
+        0,
+        zero_fun::GameError::DailyWithdrawLimitExceeded,
+    );
+}
+
+#[test]
+fn test_record_withdrawal_rolls_window() {
+    let mut global_state = GlobalState {
+        daily_withdraw_limit: VAULT_BALANCE,
+        ..Default::default()
+    };
+
+    assert!(global_state.record_withdrawal(VAULT_BALANCE, 100).is_ok());
+    assert!(global_state
+        .record_withdrawal(1, 100 + WITHDRAW_WINDOW - WITHDRAW_BUCKET_LENGTH)
+        .is_err());
+
+    // The cap is counted again once the withdrawal rolled out of the window
+    assert!(global_state
+        .record_withdrawal(VAULT_BALANCE, 100 + WITHDRAW_WINDOW)
+        .is_ok());
+    assert_eq!(
+        global_state.get_withdrawn_in_window(100 + WITHDRAW_WINDOW),
+        VAULT_BALANCE
+    );
+}
+
+#[test]
+fn test_record_withdrawal_counts_both_sides_of_window_boundary() {
+    let mut global_state = GlobalState {
+        daily_withdraw_limit: VAULT_BALANCE,
+        ..Default::default()
+    };
+
+    assert!(global_state
+        .record_withdrawal(VAULT_BALANCE, WITHDRAW_WINDOW - 1)
+        .is_ok());
+
+    // A fixed window would start over here and allow the cap a second time
+    assert!(global_state
+        .record_withdrawal(VAULT_BALANCE, WITHDRAW_WINDOW)
+        .is_err());
+    assert!(global_state
+        .record_withdrawal(1, WITHDRAW_WINDOW + WITHDRAW_BUCKET_LENGTH)
+        .is_err());
+
+    assert!(global_state
+        .record_withdrawal(VAULT_BALANCE, 2 * WITHDRAW_WINDOW)
+        .is_ok());
+    assert_eq!(global_state.total_withdrawn, 2 * VAULT_BALANCE);
+}